@@ -9,7 +9,7 @@
 extern crate test;
 use test::Bencher;
 
-use simple_uuid::{self, Node, UUID};
+use simple_uuid::{self, Generator, Node, PerCoreGenerators, V7Generator, Version, UUID};
 
 #[bench]
 fn new_v1_from_system_time(b: &mut Bencher) {
@@ -18,7 +18,7 @@ fn new_v1_from_system_time(b: &mut Bencher) {
 
 #[bench]
 fn new_v1_from_mac_address(b: &mut Bencher) {
-    b.iter(|| Node::from(Node([0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80])));
+    b.iter(|| UUID::new_from_node(Node([0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80])));
 }
 
 #[bench]
@@ -40,3 +40,39 @@ fn new_v4_with_random_number(b: &mut Bencher) {
 fn new_v5_using_sha1(b: &mut Bencher) {
     b.iter(|| simple_uuid::v5!("test_data", UUID::NAMESPACE_X500));
 }
+
+#[bench]
+fn new_v7_with_random_number(b: &mut Bencher) {
+    b.iter(UUID::v7);
+}
+
+#[bench]
+fn fill_1000_v4(b: &mut Bencher) {
+    let mut uuids = vec![UUID::default(); 1_000];
+    b.iter(|| UUID::fill_v4(&mut uuids));
+}
+
+// Eight threads generating 1000 UUIDs each through `generator`.
+fn contend<G: Generator + Sync>(generator: &G) {
+    std::thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                (0..1_000).for_each(|_| {
+                    test::black_box(generator.generate().unwrap());
+                })
+            });
+        }
+    });
+}
+
+#[bench]
+fn contended_v7_shared_generator(b: &mut Bencher) {
+    let generator = V7Generator::new();
+    b.iter(|| contend(&generator));
+}
+
+#[bench]
+fn contended_v7_per_core_generators(b: &mut Bencher) {
+    let generators = PerCoreGenerators::new(Version::UNIX).unwrap();
+    b.iter(|| contend(&generators));
+}
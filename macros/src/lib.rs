@@ -0,0 +1,178 @@
+//! Procedural macros of the `simple-uuid` crate, re-exported by it under
+//! the `macros` feature.
+
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Parse a UUID literal at compile time into a constant `UUID`.
+///
+/// Accepts the hyphenated, simple, braced and urn forms, in any case.
+///
+/// The expansion names `::simple_uuid::UUID`, so the calling crate must
+/// depend on `simple-uuid` under that name, not renamed.
+#[proc_macro]
+pub fn uuid(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let (literal, span) = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(lit)), None) => (lit.to_string(), lit.span()),
+        (Some(TokenTree::Group(g)), None) if g.delimiter() == Delimiter::None => {
+            return uuid(g.stream());
+        }
+        (first, _) => {
+            let span = first.map_or_else(Span::call_site, |t| t.span());
+            return error("expected a single string literal", span);
+        }
+    };
+    let s = match literal.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(s) => s,
+        None => return error("expected a string literal", span),
+    };
+    match parse(s) {
+        Ok(bytes) => expand(bytes, span),
+        Err(message) => error(&message, span),
+    }
+}
+
+fn parse(s: &str) -> Result<[u8; 16], String> {
+    let lower = s.to_ascii_lowercase();
+    let s = lower
+        .strip_prefix("urn:uuid:")
+        .or_else(|| lower.strip_prefix('{').and_then(|s| s.strip_suffix('}')))
+        .unwrap_or(&lower);
+    let hyphens = match s.len() {
+        36 => true,
+        32 => false,
+        n => return Err(format!("invalid UUID length {}, expected 32 or 36", n)),
+    };
+
+    let mut bytes = [0u8; 16];
+    let mut digit = 0;
+    for (i, c) in s.chars().enumerate() {
+        if hyphens && [8, 13, 18, 23].contains(&i) {
+            if c != '-' {
+                return Err(format!("expected '-' at {}, found {:?}", i, c));
+            }
+            continue;
+        }
+        let v = c
+            .to_digit(16)
+            .ok_or_else(|| format!("invalid character {:?} at {}", c, i))?;
+        bytes[digit / 2] |= (v as u8) << (4 * (1 - digit % 2));
+        digit += 1;
+    }
+    Ok(bytes)
+}
+
+// `::simple_uuid::UUID::from_bytes([..])`: a proc-macro cannot name the
+// crate re-exporting it, so the path assumes `simple-uuid` is not renamed.
+fn expand(bytes: [u8; 16], span: Span) -> TokenStream {
+    let mut array = TokenStream::new();
+    for b in bytes.iter() {
+        array.extend(vec![
+            TokenTree::Literal(Literal::u8_suffixed(*b)),
+            TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+        ]);
+    }
+    let mut path = TokenStream::new();
+    for segment in ["simple_uuid", "UUID", "from_bytes"].iter() {
+        path.extend(vec![
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(proc_macro::Ident::new(segment, span)),
+        ]);
+    }
+    let args = TokenStream::from(TokenTree::Group(Group::new(Delimiter::Bracket, array)));
+    path.extend(vec![TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        args,
+    ))]);
+    path
+}
+
+// `::core::compile_error!("message")` at `span`.
+fn error(message: &str, span: Span) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    for segment in ["core", "compile_error"].iter() {
+        tokens.extend(vec![
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(proc_macro::Ident::new(segment, span)),
+        ]);
+    }
+    let mut lit = Literal::string(message);
+    lit.set_span(span);
+    tokens.extend(vec![
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from(TokenTree::Literal(lit)),
+        )),
+    ]);
+    tokens
+        .into_iter()
+        .map(|mut t| {
+            t.set_span(span);
+            t
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYTES: [u8; 16] = [
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ];
+
+    #[test]
+    fn parse_forms() {
+        for s in [
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "6BA7B810-9DAD-11D1-80B4-00C04FD430C8",
+            "6ba7b8109dad11d180b400c04fd430c8",
+            "{6ba7b810-9dad-11d1-80b4-00c04fd430c8}",
+            "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "URN:UUID:6ba7b8109dad11d180b400c04fd430c8",
+        ]
+        .iter()
+        {
+            assert_eq!(parse(s), Ok(BYTES), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for (s, message) in [
+            ("", "invalid UUID length 0, expected 32 or 36"),
+            (
+                "6ba7b810-9dad-11d1-80b4-00c04fd430c",
+                "invalid UUID length 35, expected 32 or 36",
+            ),
+            (
+                "{6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                "invalid UUID length 37, expected 32 or 36",
+            ),
+            (
+                "6ba7b810_9dad-11d1-80b4-00c04fd430c8",
+                "expected '-' at 8, found '_'",
+            ),
+            (
+                "6ba7b810-9dad-11d1-80b4-00c04fd430cg",
+                "invalid character 'g' at 35",
+            ),
+            (
+                "6ba7b8109dad11d180b400c04fd430c-",
+                "invalid character '-' at 31",
+            ),
+            (
+                "6ba7b810-9dad-11d1-80b4-00c04fd430\u{e9}",
+                "invalid character '\u{e9}' at 34",
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(parse(s), Err(message.to_string()), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+#![doc(cfg(feature = "async"))]
+#![cfg(feature = "async")]
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use futures_timer::Delay;
+
+use crate::{Generator, UuidError, UUID};
+
+/// An endless `Stream` of UUIDs, optionally rate limited.
+///
+/// Errors of the generator are yielded as items, and the stream goes on.
+pub struct UuidStream<G> {
+    generator: G,
+    interval: Option<Duration>,
+    delay: Option<Delay>,
+}
+
+impl<G: Generator + Unpin> UuidStream<G> {
+    /// New stream yielding values of `generator`, at most one per `interval`.
+    pub fn new(generator: G, interval: Option<Duration>) -> Self {
+        Self {
+            generator,
+            interval,
+            delay: None,
+        }
+    }
+}
+
+impl<G: Generator + Unpin> Stream for UuidStream<G> {
+    type Item = Result<UUID, UuidError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(delay) = this.delay.as_mut() {
+            ready!(Pin::new(delay).poll(cx));
+        }
+        this.delay = this.interval.map(Delay::new);
+        Poll::Ready(Some(this.generator.generate()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::StreamExt;
+    use std::time::Instant;
+
+    #[test]
+    fn stream_yields_generated_values() {
+        let stream = UuidStream::new(|| Ok(UUID::NAMESPACE_DNS), None);
+        let uuids: Vec<_> = block_on(stream.take(3).collect());
+        assert_eq!(uuids, vec![Ok(UUID::NAMESPACE_DNS); 3]);
+    }
+
+    #[test]
+    fn stream_is_rate_limited() {
+        let start = Instant::now();
+        let stream = UuidStream::new(|| Ok(UUID::default()), Some(Duration::from_millis(10)));
+        let uuids: Vec<_> = block_on(stream.take(3).collect());
+        assert_eq!(uuids.len(), 3);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn stream_yields_errors() {
+        let mut stream = UuidStream::new(|| Err(UuidError::Entropy), None);
+        assert_eq!(block_on(stream.next()), Some(Err(UuidError::Entropy)));
+        assert_eq!(block_on(stream.next()), Some(Err(UuidError::Entropy)));
+    }
+}
@@ -0,0 +1,373 @@
+//! Soak test of a generator, for qualifying platforms whose clocks step,
+//! such as virtual machines restored from snapshots and migrated containers.
+//!
+//! ```text
+//! soak [--config FILE] [--duration SECS] [--interval SECS]
+//!      [--checkpoint FILE] [--bloom-mib MIB]
+//! ```
+//!
+//! Runs the generator described by a JSON [`GeneratorConfig`], by default
+//! the one of [`UUID::new`], and every interval prints the UUIDs generated,
+//! their rate, the duplicates and the ordering violations seen so far.
+//! Duplicates are found with a Bloom filter, so a few may be false
+//! positives: the expected number is printed alongside. Versions 6 and 7
+//! must also increase from one UUID to the next, unless version 7 has no
+//! counter.
+//!
+//! With `--checkpoint`, the filter and counts are saved every interval and
+//! loaded on start, so duplicates are also found across restarts. Exits
+//! with status 1 if an ordering violation was seen, or clearly more
+//! duplicates than false positives are expected.
+
+use std::convert::TryInto;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use simple_uuid::config::GeneratorConfig;
+use simple_uuid::UUID;
+
+/// First bytes of a checkpoint file.
+const MAGIC: &[u8; 8] = b"UUIDSOAK";
+
+/// Bits set in the Bloom filter per UUID.
+const HASHES: u64 = 4;
+
+/// Length of a checkpoint before the words of the filter.
+const HEADER_LEN: usize = 8 + 5 * 8 + 16;
+
+const USAGE: &str = "usage: soak [--config FILE] [--duration SECS] [--interval SECS] \
+                     [--checkpoint FILE] [--bloom-mib MIB]";
+
+#[derive(Debug)]
+struct Options {
+    config: GeneratorConfig,
+    duration: Option<Duration>,
+    interval: Duration,
+    checkpoint: Option<PathBuf>,
+    bloom_mib: usize,
+}
+
+/// Bloom filter of the UUIDs seen.
+#[derive(Debug, PartialEq)]
+struct Bloom {
+    words: Vec<u64>,
+}
+
+impl Bloom {
+    fn new(mib: usize) -> Self {
+        Self {
+            words: vec![0; (mib.max(1) << 20) / 8],
+        }
+    }
+
+    fn bits(&self) -> u64 {
+        self.words.len() as u64 * 64
+    }
+
+    /// Insert `uuid`, returning whether it may already have been inserted.
+    fn insert(&mut self, uuid: &UUID) -> bool {
+        let (high, low) = ((uuid.as_u128() >> 64) as u64, uuid.as_u128() as u64);
+        let a = mix(high ^ mix(low));
+        let b = mix(low ^ mix(high)) | 1;
+        let mut seen = true;
+        for i in 0..HASHES {
+            let bit = a.wrapping_add(i.wrapping_mul(b)) % self.bits();
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            seen &= self.words[word] & mask != 0;
+            self.words[word] |= mask;
+        }
+        seen
+    }
+
+    /// False positives expected among `n` distinct UUIDs.
+    fn false_positives(&self, n: u64) -> f64 {
+        let m = self.bits() as f64;
+        let k = HASHES as f64;
+        // Sum of the false positive rates after each insertion.
+        (0..=100)
+            .map(|step| {
+                let inserted = n as f64 * f64::from(step) / 100.0;
+                (1.0 - (-k * inserted / m).exp()).powf(k)
+            })
+            .sum::<f64>()
+            * n as f64
+            / 101.0
+    }
+}
+
+// Finalizer of SplitMix64, spreading structured UUIDs over the filter.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Counts of a soak test, kept across restarts by the checkpoint.
+#[derive(Debug, Default, PartialEq)]
+struct Counts {
+    generated: u64,
+    duplicates: u64,
+    violations: u64,
+    errors: u64,
+    restarts: u64,
+    last: Option<UUID>,
+}
+
+fn save(path: &Path, bloom: &Bloom, counts: &Counts) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + bloom.words.len() * 8);
+    bytes.extend_from_slice(MAGIC);
+    for n in [
+        counts.generated,
+        counts.duplicates,
+        counts.violations,
+        counts.errors,
+        counts.restarts,
+    ] {
+        bytes.extend_from_slice(&n.to_le_bytes());
+    }
+    bytes.extend_from_slice(counts.last.unwrap_or_default().as_array());
+    for word in &bloom.words {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+
+    let mut tmp = path.to_path_buf().into_os_string();
+    tmp.push(".tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+fn load(path: &Path) -> io::Result<Option<(Bloom, Counts)>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "not a soak checkpoint");
+    if bytes.len() < HEADER_LEN
+        || &bytes[..8] != MAGIC
+        || !(bytes.len() - HEADER_LEN).is_multiple_of(8)
+    {
+        return Err(invalid());
+    }
+
+    let n = |i: usize| u64::from_le_bytes(bytes[8 + i * 8..16 + i * 8].try_into().unwrap());
+    let last = UUID::from_bytes(bytes[48..HEADER_LEN].try_into().unwrap());
+    let counts = Counts {
+        generated: n(0),
+        duplicates: n(1),
+        violations: n(2),
+        errors: n(3),
+        restarts: n(4),
+        last: Some(last).filter(|last| *last != UUID::default()),
+    };
+    let words: Vec<_> = bytes[HEADER_LEN..]
+        .chunks_exact(8)
+        .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+        .collect();
+    if words.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Some((Bloom { words }, counts)))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options {
+        config: GeneratorConfig::default(),
+        duration: None,
+        interval: Duration::from_secs(60),
+        checkpoint: None,
+        bloom_mib: 256,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value of `{}`", arg));
+        let secs = |value: String| {
+            value
+                .parse()
+                .map(Duration::from_secs)
+                .map_err(|_| format!("invalid seconds `{}`", value))
+        };
+        match arg.as_str() {
+            "--config" => {
+                let path = value()?;
+                let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+                options.config =
+                    serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e))?;
+            }
+            "--duration" => options.duration = Some(secs(value()?)?),
+            "--interval" => options.interval = secs(value()?)?.max(Duration::from_secs(1)),
+            "--checkpoint" => options.checkpoint = Some(value()?.into()),
+            "--bloom-mib" => {
+                let mib = value()?;
+                options.bloom_mib = mib.parse().map_err(|_| format!("invalid size `{}`", mib))?;
+            }
+            _ => return Err(format!("unknown argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+fn report(elapsed: Duration, rate: f64, bloom: &Bloom, counts: &Counts) {
+    println!(
+        "{:>8}s generated={} rate={:.0}/s duplicates={} (expected false positives {:.1}) \
+         violations={} errors={} restarts={}",
+        elapsed.as_secs(),
+        counts.generated,
+        rate,
+        counts.duplicates,
+        bloom.false_positives(counts.generated),
+        counts.violations,
+        counts.errors,
+        counts.restarts,
+    );
+}
+
+/// Whether the UUIDs of `config` must increase from one to the next: those
+/// of versions 6 and 7, except version 7 without a counter.
+fn is_ordered(config: &GeneratorConfig) -> bool {
+    match config.version {
+        6 => true,
+        7 => config.counter_bits != Some(0),
+        _ => false,
+    }
+}
+
+fn run(options: Options) -> Result<bool, String> {
+    let generator = options.config.build().map_err(|e| e.to_string())?;
+    let ordered = is_ordered(&options.config);
+
+    let checkpoint = |bloom: &Bloom, counts: &Counts| match &options.checkpoint {
+        Some(path) => save(path, bloom, counts).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => Ok(()),
+    };
+    let (mut bloom, mut counts) = match &options.checkpoint {
+        Some(path) => match load(path).map_err(|e| format!("{}: {}", path.display(), e))? {
+            Some((bloom, mut counts)) => {
+                counts.restarts += 1;
+                (bloom, counts)
+            }
+            None => (Bloom::new(options.bloom_mib), Counts::default()),
+        },
+        None => (Bloom::new(options.bloom_mib), Counts::default()),
+    };
+
+    let start = Instant::now();
+    let mut tick = (start, counts.generated);
+    loop {
+        // Read the clock once per batch to keep it out of the measured rate.
+        for _ in 0..1024 {
+            let uuid = match generator.generate() {
+                Ok(uuid) => uuid,
+                Err(_) => {
+                    counts.errors += 1;
+                    continue;
+                }
+            };
+            counts.generated += 1;
+            if bloom.insert(&uuid) {
+                counts.duplicates += 1;
+            }
+            if ordered && counts.last.is_some_and(|last| uuid <= last) {
+                counts.violations += 1;
+                eprintln!(
+                    "ordering violation: {} after {}",
+                    uuid,
+                    counts.last.unwrap()
+                );
+            }
+            counts.last = Some(uuid);
+        }
+
+        let now = Instant::now();
+        let done = options.duration.is_some_and(|d| now - start >= d);
+        if now - tick.0 >= options.interval || done {
+            let rate = (counts.generated - tick.1) as f64 / (now - tick.0).as_secs_f64();
+            report(now - start, rate, &bloom, &counts);
+            checkpoint(&bloom, &counts)?;
+            tick = (now, counts.generated);
+        }
+        if done {
+            // Five standard deviations above the false positives expected.
+            let expected = bloom.false_positives(counts.generated);
+            let tolerated = expected + 5.0 * expected.sqrt();
+            return Ok(counts.duplicates as f64 <= tolerated && counts.violations == 0);
+        }
+    }
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(run);
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("soak: {}", e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_finds_duplicates() {
+        let mut bloom = Bloom::new(1);
+        let uuids: Vec<_> = (0..10_000u128)
+            .map(|n| UUID::from_u128(0x017f_22e2_79b0_7cc3_98c4_dc0c_0c07_0000 + n))
+            .collect();
+        let seen = uuids.iter().filter(|uuid| bloom.insert(uuid)).count();
+        assert!((seen as f64) < 10.0 * bloom.false_positives(10_000).max(1.0));
+        assert!(uuids.iter().all(|uuid| bloom.insert(uuid)));
+    }
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let path = env::temp_dir().join(format!("simple-uuid-soak-{}", process::id()));
+        assert!(load(&path).unwrap().is_none());
+
+        let mut bloom = Bloom::new(1);
+        bloom.insert(&UUID::NAMESPACE_DNS);
+        let counts = Counts {
+            generated: 1,
+            restarts: 2,
+            last: Some(UUID::NAMESPACE_DNS),
+            ..Counts::default()
+        };
+        save(&path, &bloom, &counts).unwrap();
+        assert_eq!(load(&path).unwrap(), Some((bloom, counts)));
+
+        fs::write(&path, b"UUIDSOAK").unwrap();
+        assert!(load(&path).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn ordered_versions() {
+        let config = |json: &str| serde_json::from_str::<GeneratorConfig>(json).unwrap();
+        assert!(is_ordered(&config(r#"{ "version": 6 }"#)));
+        assert!(is_ordered(&config(r#"{ "version": 7 }"#)));
+        assert!(is_ordered(&config(
+            r#"{ "version": 7, "counter_bits": 12 }"#
+        )));
+        assert!(!is_ordered(&config(
+            r#"{ "version": 7, "counter_bits": 0 }"#
+        )));
+        assert!(!is_ordered(&config(r#"{ "version": 4 }"#)));
+    }
+
+    #[test]
+    fn runs_for_the_duration() {
+        let args = ["--config", "/nonexistent", "--duration", "0"];
+        assert!(parse_args(args.iter().map(|s| s.to_string())).is_err());
+
+        let args = ["--duration", "0", "--bloom-mib", "1"];
+        let options = parse_args(args.iter().map(|s| s.to_string())).unwrap();
+        assert_eq!(options.duration, Some(Duration::from_secs(0)));
+        assert_eq!(run(options), Ok(true));
+    }
+}
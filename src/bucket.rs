@@ -0,0 +1,65 @@
+use crate::UUID;
+
+/// Offset basis of 64-bit FNV-1a.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of 64-bit FNV-1a.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl UUID {
+    /// Bucket from `0` to `n_buckets - 1` assigned to the UUID for `salt`.
+    ///
+    /// The bucket is the 64-bit FNV-1a hash of the UTF-8 bytes of `salt`, a
+    /// zero byte and the 16 octets of the UUID, modulo `n_buckets`, so other
+    /// implementations can reproduce assignments. Using a different salt per
+    /// experiment keeps assignments of separate experiments independent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_buckets` is zero.
+    pub fn bucket(&self, salt: &str, n_buckets: u32) -> u32 {
+        assert!(n_buckets > 0, "n_buckets must not be zero");
+        let hash = salt
+            .as_bytes()
+            .iter()
+            .chain(&[0])
+            .chain(&self.0)
+            .fold(FNV_OFFSET, |h, b| {
+                (h ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+            });
+        (hash % u64::from(n_buckets)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_is_reproducible() {
+        // Expected values computed with an independent FNV-1a implementation.
+        assert_eq!(UUID::NAMESPACE_DNS.bucket("checkout-v2", 100), 13);
+        assert_eq!(UUID::NAMESPACE_URL.bucket("checkout-v2", 100), 50);
+        assert_eq!(UUID::NAMESPACE_DNS.bucket("onboarding", 100), 69);
+        assert_eq!(UUID::NAMESPACE_DNS.bucket("checkout-v2", 1), 0);
+    }
+
+    #[test]
+    fn buckets_are_balanced() {
+        let mut counts = [0u32; 4];
+        for i in 0..4000u128 {
+            counts[UUID::from_u128(i << 64 | 0x4000_8000).bucket("exp", 4) as usize] += 1;
+        }
+        assert!(
+            counts.iter().all(|c| (900..1100).contains(c)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_buckets() {
+        UUID::NAMESPACE_DNS.bucket("exp", 0);
+    }
+}
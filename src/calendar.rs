@@ -0,0 +1,123 @@
+//! Per-day activity counters for time-ordered version-7 UUIDs.
+
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
+
+use crate::UUID;
+
+/// Milliseconds in one UTC day.
+const MS_PER_DAY: u64 = 86_400_000;
+
+/// Days since the UNIX epoch of the timestamp embedded in a version-7 UUID.
+pub fn day_of(uuid: &UUID) -> Option<u32> {
+    if uuid.get_version_num() != 7 {
+        return None;
+    }
+    let mut ms = [0u8; 8];
+    ms[2..].copy_from_slice(&uuid.0[..6]);
+    Some((u64::from_be_bytes(ms) / MS_PER_DAY) as u32)
+}
+
+/// Number of version-7 UUIDs seen per UTC day, keyed by days since the UNIX epoch.
+#[derive(Debug, Default, Clone)]
+pub struct DailyIndex {
+    days: BTreeMap<u32, u64>,
+}
+
+impl DailyIndex {
+    /// New empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count the UUID under its day, returning that day, or `None` if it is not version-7.
+    pub fn insert(&mut self, uuid: &UUID) -> Option<u32> {
+        let day = day_of(uuid)?;
+        *self.days.entry(day).or_insert(0) += 1;
+        Some(day)
+    }
+
+    /// Uncount a previously inserted UUID, returning its day if it was counted.
+    pub fn remove(&mut self, uuid: &UUID) -> Option<u32> {
+        let day = day_of(uuid)?;
+        let count = self.days.get_mut(&day)?;
+        *count -= 1;
+        if *count == 0 {
+            self.days.remove(&day);
+        }
+        Some(day)
+    }
+
+    /// Number of UUIDs counted on `day`.
+    pub fn count(&self, day: u32) -> u64 {
+        self.days.get(&day).copied().unwrap_or(0)
+    }
+
+    /// Number of UUIDs counted over all days.
+    pub fn total(&self) -> u64 {
+        self.days.values().sum()
+    }
+
+    /// Days with at least one UUID within `range`, in ascending order, with their counts.
+    pub fn active_days<R: RangeBounds<u32>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.days.range(range).map(|(day, count)| (*day, *count))
+    }
+}
+
+impl<'a> Extend<&'a UUID> for DailyIndex {
+    fn extend<I: IntoIterator<Item = &'a UUID>>(&mut self, iter: I) {
+        for uuid in iter {
+            self.insert(uuid);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v7_at(ms: u64) -> UUID {
+        let mut bytes = [0u8; 16];
+        bytes[..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+        bytes[6] = 0x70;
+        bytes[8] = 0x80;
+        UUID(bytes)
+    }
+
+    #[test]
+    fn day_of_v7_only() {
+        assert_eq!(day_of(&v7_at(0)), Some(0));
+        assert_eq!(day_of(&v7_at(MS_PER_DAY * 19_000 + 1)), Some(19_000));
+        assert_eq!(day_of(&UUID::NAMESPACE_DNS), None);
+    }
+
+    #[test]
+    fn count_per_day_and_range() {
+        let mut index = DailyIndex::new();
+        index.extend(&[
+            v7_at(MS_PER_DAY),
+            v7_at(MS_PER_DAY + 5),
+            v7_at(MS_PER_DAY * 3),
+            v7_at(MS_PER_DAY * 10),
+        ]);
+        assert_eq!(index.insert(&UUID::NAMESPACE_DNS), None);
+
+        assert_eq!(index.count(1), 2);
+        assert_eq!(index.count(2), 0);
+        assert_eq!(index.total(), 4);
+        assert_eq!(
+            index.active_days(0..5).collect::<Vec<_>>(),
+            vec![(1, 2), (3, 1)]
+        );
+
+        assert_eq!(index.remove(&v7_at(MS_PER_DAY * 3)), Some(3));
+        assert_eq!(index.remove(&v7_at(MS_PER_DAY * 3)), None);
+        assert_eq!(
+            index.active_days(..).collect::<Vec<_>>(),
+            vec![(1, 2), (10, 1)]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Zero-copy reinterpretation of UUID columns as octets.
+//!
+//! [`UUID`] is `repr(transparent)` over `[u8; 16]`, so slices and vectors
+//! of UUIDs and of 16-octet arrays share one layout, and any 16 octets are a
+//! valid UUID. These helpers hand whole columns to I/O and serialization
+//! layers without copying them and without `unsafe` code of their own; the
+//! casts are checked by `bytemuck`.
+//!
+//! ```rust
+//! use simple_uuid::{cast, UUID};
+//!
+//! let uuids = vec![UUID::NAMESPACE_DNS, UUID::NAMESPACE_URL];
+//! assert_eq!(cast::as_bytes(&uuids).len(), 32);
+//! let arrays = cast::cast_vec(uuids);
+//! assert_eq!(arrays[0], UUID::NAMESPACE_DNS.as_u128().to_be_bytes());
+//! assert_eq!(cast::from_byte_array_vec(arrays)[1], UUID::NAMESPACE_URL);
+//! ```
+#![doc(cfg(feature = "bytemuck"))]
+#![cfg(feature = "bytemuck")]
+
+use crate::UUID;
+
+/// The octets of each UUID of `uuids`.
+pub fn cast_slice(uuids: &[UUID]) -> &[[u8; 16]] {
+    bytemuck::cast_slice(uuids)
+}
+
+/// The octets of each UUID of `uuids`, mutable.
+pub fn cast_slice_mut(uuids: &mut [UUID]) -> &mut [[u8; 16]] {
+    bytemuck::cast_slice_mut(uuids)
+}
+
+/// The UUIDs of `arrays`.
+pub fn from_byte_arrays(arrays: &[[u8; 16]]) -> &[UUID] {
+    bytemuck::cast_slice(arrays)
+}
+
+/// The UUIDs of `arrays`, mutable.
+pub fn from_byte_arrays_mut(arrays: &mut [[u8; 16]]) -> &mut [UUID] {
+    bytemuck::cast_slice_mut(arrays)
+}
+
+/// The octets of each UUID of `uuids`, keeping the allocation.
+pub fn cast_vec(uuids: Vec<UUID>) -> Vec<[u8; 16]> {
+    bytemuck::allocation::cast_vec(uuids)
+}
+
+/// The UUIDs of `arrays`, keeping the allocation.
+pub fn from_byte_array_vec(arrays: Vec<[u8; 16]>) -> Vec<UUID> {
+    bytemuck::allocation::cast_vec(arrays)
+}
+
+/// The octets of `uuids`, one UUID after the other.
+pub fn as_bytes(uuids: &[UUID]) -> &[u8] {
+    bytemuck::cast_slice(uuids)
+}
+
+/// The UUIDs of `bytes`, `None` if its length is not a multiple of 16.
+pub fn try_from_bytes(bytes: &[u8]) -> Option<&[UUID]> {
+    bytemuck::try_cast_slice(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casts_share_memory() {
+        let mut uuids = vec![UUID::NAMESPACE_DNS, UUID::default()];
+        let ptr = uuids.as_ptr() as usize;
+        cast_slice_mut(&mut uuids)[1][15] = 1;
+        assert_eq!(uuids[1], UUID::from_u128(1));
+        assert_eq!(cast_slice(&uuids)[0], UUID::NAMESPACE_DNS.0);
+
+        let arrays = cast_vec(uuids);
+        assert_eq!(arrays.as_ptr() as usize, ptr);
+        assert_eq!(from_byte_arrays(&arrays)[0], UUID::NAMESPACE_DNS);
+        let uuids = from_byte_array_vec(arrays);
+        assert_eq!(uuids.as_ptr() as usize, ptr);
+
+        let bytes = as_bytes(&uuids);
+        assert_eq!(&bytes[..16], &UUID::NAMESPACE_DNS.0);
+        assert_eq!(try_from_bytes(bytes), Some(&uuids[..]));
+        assert_eq!(try_from_bytes(&bytes[1..]), None);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::thread;
+use std::time::Duration;
+
+use crate::{Generator, UuidError, UUID};
+
+/// Number of previously emitted UUIDs a clock rollback may jump back to.
+const HISTORY_LEN: usize = 16;
+
+/// Probabilities of the faults injected by a `ChaosGenerator`, each in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    /// Chance of emitting a UUID whose leading 48 bits, the timestamp of
+    /// version-6 and version-7 UUIDs, are copied from an earlier emission.
+    pub rollback: f64,
+    /// Chance of emitting the previous UUID again.
+    pub duplicate: f64,
+    /// Chance of sleeping for `delay` before emitting.
+    pub latency: f64,
+    /// Sleep injected on latency faults.
+    pub delay: Duration,
+    /// Seed of the fault schedule, so a failing run can be replayed.
+    pub seed: u64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            rollback: 0.0,
+            duplicate: 0.0,
+            latency: 0.0,
+            delay: Duration::from_millis(1),
+            seed: 0x853c_49e6_748f_ea9b,
+        }
+    }
+}
+
+/// Number of faults a `ChaosGenerator` injected so far.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChaosStats {
+    /// Emitted UUIDs with a rolled back timestamp.
+    pub rollbacks: u64,
+    /// Emitted duplicates.
+    pub duplicates: u64,
+    /// Injected sleeps.
+    pub delays: u64,
+}
+
+/// Wraps a generator and injects faults into its output for chaos testing.
+pub struct ChaosGenerator<G> {
+    inner: G,
+    config: ChaosConfig,
+    state: u64,
+    history: VecDeque<UUID>,
+    stats: ChaosStats,
+}
+
+impl<G: Generator> ChaosGenerator<G> {
+    /// Inject faults described by `config` into the output of `inner`.
+    pub fn wrap(inner: G, config: ChaosConfig) -> Self {
+        Self {
+            inner,
+            state: config.seed | 1,
+            config,
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            stats: ChaosStats::default(),
+        }
+    }
+
+    /// Faults injected so far.
+    pub fn stats(&self) -> ChaosStats {
+        self.stats
+    }
+
+    /// Next UUID of the inner generator, possibly faulty.
+    ///
+    /// Errors of the inner generator are returned as is, without faults.
+    pub fn generate(&mut self) -> Result<UUID, UuidError> {
+        if self.chance(self.config.latency) {
+            self.stats.delays += 1;
+            thread::sleep(self.config.delay);
+        }
+
+        if let Some(last) = self.history.back().copied() {
+            if self.chance(self.config.duplicate) {
+                self.stats.duplicates += 1;
+                return Ok(last);
+            }
+        }
+
+        let mut uuid = self.inner.generate()?;
+        if !self.history.is_empty() && self.chance(self.config.rollback) {
+            let i = self.roll() as usize % self.history.len();
+            let earlier = self.history[i];
+            uuid.0[..6].copy_from_slice(&earlier.0[..6]);
+            self.stats.rollbacks += 1;
+        }
+
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(uuid);
+        Ok(uuid)
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        p > 0.0 && ((self.roll() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    // xorshift64*, good enough to schedule faults.
+    fn roll(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl<G: Generator> Iterator for ChaosGenerator<G> {
+    type Item = Result<UUID, UuidError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.generate())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn counter() -> impl Fn() -> Result<UUID, UuidError> {
+        let n = Cell::new(0u128);
+        move || {
+            n.set(n.get() + 1);
+            Ok(UUID((n.get() << 80).to_be_bytes()))
+        }
+    }
+
+    #[test]
+    fn no_faults_by_default() {
+        let mut chaos = ChaosGenerator::wrap(counter(), ChaosConfig::default());
+        let uuids: Vec<UUID> = (0..100).map(|_| chaos.generate().unwrap()).collect();
+        assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(chaos.stats(), ChaosStats::default());
+    }
+
+    #[test]
+    fn inject_duplicates() {
+        let config = ChaosConfig {
+            duplicate: 1.0,
+            ..ChaosConfig::default()
+        };
+        let mut chaos = ChaosGenerator::wrap(counter(), config);
+        let first = chaos.generate().unwrap();
+        assert_eq!(chaos.generate(), Ok(first));
+        assert_eq!(chaos.stats().duplicates, 1);
+    }
+
+    #[test]
+    fn inject_rollbacks() {
+        let config = ChaosConfig {
+            rollback: 1.0,
+            ..ChaosConfig::default()
+        };
+        let mut chaos = ChaosGenerator::wrap(counter(), config);
+        let first = chaos.generate().unwrap();
+        let second = chaos.generate().unwrap();
+        assert_eq!(second.0[..6], first.0[..6]);
+        assert_eq!(chaos.stats().rollbacks, 1);
+    }
+
+    #[test]
+    fn inject_latency() {
+        let config = ChaosConfig {
+            latency: 1.0,
+            ..ChaosConfig::default()
+        };
+        let mut chaos = ChaosGenerator::wrap(counter(), config);
+        chaos.generate().unwrap();
+        assert_eq!(chaos.stats().delays, 1);
+    }
+
+    #[test]
+    fn propagate_inner_errors() {
+        let config = ChaosConfig {
+            rollback: 1.0,
+            ..ChaosConfig::default()
+        };
+        let mut chaos = ChaosGenerator::wrap(|| Err(UuidError::Clock), config);
+        assert_eq!(chaos.next(), Some(Err(UuidError::Clock)));
+        assert_eq!(chaos.stats(), ChaosStats::default());
+    }
+}
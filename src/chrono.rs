@@ -0,0 +1,103 @@
+//! Conversions between the timestamps of UUIDs and `chrono` date-times.
+#![doc(cfg(feature = "chrono"))]
+#![cfg(feature = "chrono")]
+
+use std::time::SystemTime;
+
+use ::chrono::{DateTime, Utc};
+
+use crate::{Layout, TimeStamp, UuidError, UUID};
+
+impl TimeStamp {
+    /// Timestamp of `datetime`, truncated to 100 ns; fails with
+    /// `UuidError::Clock` before 1582-10-15 or past the year 5236.
+    pub fn from_datetime(datetime: DateTime<Utc>) -> Result<TimeStamp, UuidError> {
+        TimeStamp::from_system_time(SystemTime::from(datetime))
+    }
+
+    /// The date-time of the timestamp.
+    pub fn to_datetime(&self) -> DateTime<Utc> {
+        DateTime::from(self.to_system_time())
+    }
+}
+
+impl UUID {
+    /// Creation date-time of a version-1, 6 or 7 UUID, `None` for other
+    /// versions.
+    pub fn get_datetime(&self) -> Option<DateTime<Utc>> {
+        self.get_time().map(|time| time.to_datetime())
+    }
+
+    /// New UUID version-1 at `datetime`, with the node and clock sequence
+    /// of [`UUID::new_from_utc`].
+    #[cfg(feature = "mac_addr")]
+    #[doc(cfg(feature = "mac_addr"))]
+    pub fn v1_at(datetime: DateTime<Utc>) -> Result<Layout, UuidError> {
+        UUID::new_from_utc(TimeStamp::from_datetime(datetime)?.ticks())
+    }
+
+    /// New UUID version-7 at `datetime`, for backfilling historical records;
+    /// fails before the UNIX epoch or past the year 10889.
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn v7_at(datetime: DateTime<Utc>) -> Result<Layout, UuidError> {
+        UUID::v7_from_timestamp(SystemTime::from(datetime))
+    }
+}
+
+impl Layout {
+    /// Creation date-time of a version-1, 6 or 7 UUID, `None` for other
+    /// versions.
+    pub fn get_datetime(&self) -> Option<DateTime<Utc>> {
+        self.to_uuid().get_datetime()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::chrono::TimeZone;
+
+    #[test]
+    fn datetime_of_uuids() {
+        let datetime = Utc.with_ymd_and_hms(2022, 2, 22, 19, 22, 22).unwrap();
+        let v1 = UUID::parse_str("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+        let v6 = UUID::parse_str("1ec9414c-232a-6b00-b3c8-9f6bdeced846").unwrap();
+        let v7 = UUID::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+        for uuid in [v1, v6, v7] {
+            assert_eq!(uuid.get_datetime(), Some(datetime));
+        }
+        assert_eq!(
+            UUID::NAMESPACE_DNS
+                .decode()
+                .get_datetime()
+                .map(|d| d.timestamp()),
+            Some(886_630_433)
+        );
+        assert_eq!(UUID::NIL.get_datetime(), None);
+        let last = UUID::parse_str("ffffffff-ffff-1fff-8000-000000000000").unwrap();
+        assert_eq!(
+            last.get_datetime().map(|d| d.format("%F").to_string()),
+            Some("5236-03-31".to_string())
+        );
+        assert_eq!(
+            TimeStamp::from_datetime(datetime).unwrap().to_datetime(),
+            datetime
+        );
+    }
+
+    #[cfg(all(feature = "mac_addr", feature = "rand_num"))]
+    #[test]
+    fn uuids_at_datetime() {
+        let datetime = Utc.with_ymd_and_hms(1999, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(
+            UUID::v1_at(datetime).unwrap().get_datetime(),
+            Some(datetime)
+        );
+        assert_eq!(
+            UUID::v7_at(datetime).unwrap().get_datetime(),
+            Some(datetime)
+        );
+        assert!(UUID::v7_at(Utc.with_ymd_and_hms(1969, 1, 1, 0, 0, 0).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{TimeSource, UuidError};
+
+/// Time read by version-1, 6 and 7 generation, in 100-ns ticks since the
+/// UNIX epoch.
+///
+/// [`SystemClock`] reads the installed [`TimeSource`]; other implementations
+/// make tests deterministic or read TSC, PTP or simulation time. Closures
+/// returning the ticks are clocks too.
+pub trait Clock {
+    /// 100-ns ticks since the UNIX epoch.
+    fn now_100ns(&self) -> u64;
+
+    /// Same as `now_100ns`, failing with `UuidError::Clock` when the time is
+    /// unavailable; generation reads the time through this method.
+    fn try_now_100ns(&self) -> Result<u64, UuidError> {
+        Ok(self.now_100ns())
+    }
+}
+
+/// The clock of the installed [`TimeSource`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    /// # Panics
+    ///
+    /// Panics if the system clock is unavailable.
+    fn now_100ns(&self) -> u64 {
+        self.try_now_100ns().expect("system clock is unavailable")
+    }
+
+    fn try_now_100ns(&self) -> Result<u64, UuidError> {
+        Ok((TimeSource::now()?.as_nanos() / 100) as u64)
+    }
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_100ns(&self) -> u64 {
+        self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_and_closure_clocks() {
+        let ticks = SystemClock.try_now_100ns().unwrap();
+        assert!(ticks > 16_000_000_000_000_000);
+        assert!(SystemClock.now_100ns() >= ticks);
+        assert_eq!((|| 42).try_now_100ns(), Ok(42));
+    }
+}
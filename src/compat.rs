@@ -0,0 +1,3 @@
+//! Facades mirroring the API of other UUID crates, for drop-in migration.
+
+pub mod uuid;
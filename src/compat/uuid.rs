@@ -0,0 +1,357 @@
+//! Names and signatures of the mainstream `uuid` crate.
+//!
+//! Switching a crate over only needs `use simple_uuid::compat::uuid::Uuid;`.
+//! Items whose behavior would differ from `uuid` are left out on purpose,
+//! so a missing method is a compile error rather than a silent change.
+
+use core::convert::TryInto;
+use core::str::FromStr;
+
+use crate::format::{decode_hex_formats, MAX_ENCODED_LEN};
+use crate::{Format, UUID};
+
+/// A UUID with the API of `uuid::Uuid`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+pub struct Uuid(UUID);
+
+/// Error parsing or building a `Uuid`, like `uuid::Error`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Error(ErrorKind);
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum ErrorKind {
+    Parse,
+    ByteLength(usize),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            ErrorKind::Parse => write!(
+                fmt,
+                "invalid UUID string, expected simple, hyphenated, braced or urn form"
+            ),
+            ErrorKind::ByteLength(len) => {
+                write!(fmt, "invalid length: expected 16 bytes, found {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Uuid {
+    /// UUID namespace for domain name system (DNS).
+    pub const NAMESPACE_DNS: Uuid = Uuid(UUID::NAMESPACE_DNS);
+    /// UUID namespace for ISO object identifiers (OIDs).
+    pub const NAMESPACE_OID: Uuid = Uuid(UUID::NAMESPACE_OID);
+    /// UUID namespace for uniform resource locators (URLs).
+    pub const NAMESPACE_URL: Uuid = Uuid(UUID::NAMESPACE_URL);
+    /// UUID namespace for X.500 distinguished names (DNs).
+    pub const NAMESPACE_X500: Uuid = Uuid(UUID::NAMESPACE_X500);
+
+    /// The nil UUID, all zeros.
+    pub const fn nil() -> Self {
+        Uuid(UUID::NIL)
+    }
+
+    /// The max UUID, all ones.
+    pub const fn max() -> Self {
+        Uuid(UUID::MAX)
+    }
+
+    /// New random version-4 UUID.
+    ///
+    /// Like `uuid`, panics if the random number generator fails.
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn new_v4() -> Self {
+        Uuid(
+            UUID::new_from_rand()
+                .expect("random number generator failed")
+                .to_uuid(),
+        )
+    }
+
+    /// Parse the simple, hyphenated, braced or urn form.
+    pub fn parse_str(input: &str) -> Result<Self, Error> {
+        decode_hex_formats(input)
+            .map(Uuid)
+            .ok_or(Error(ErrorKind::Parse))
+    }
+
+    /// Same as `parse_str`.
+    pub fn try_parse(input: &str) -> Result<Self, Error> {
+        Self::parse_str(input)
+    }
+
+    /// UUID from its 16 big-endian octets.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Uuid(UUID(bytes))
+    }
+
+    /// UUID from a slice of exactly 16 big-endian octets.
+    pub fn from_slice(b: &[u8]) -> Result<Self, Error> {
+        b.try_into()
+            .map(Self::from_bytes)
+            .map_err(|_| Error(ErrorKind::ByteLength(b.len())))
+    }
+
+    /// UUID from a 128-bit big-endian value.
+    pub const fn from_u128(v: u128) -> Self {
+        Self::from_bytes(v.to_be_bytes())
+    }
+
+    /// UUID from its field values, with `d4` holding the last 8 octets.
+    pub const fn from_fields(d1: u32, d2: u16, d3: u16, d4: &[u8; 8]) -> Self {
+        Uuid(UUID::from_fields(d1, d2, d3, d4))
+    }
+
+    /// UUID from field values whose first three fields are little-endian.
+    pub const fn from_fields_le(d1: u32, d2: u16, d3: u16, d4: &[u8; 8]) -> Self {
+        Uuid(UUID::from_fields_le(d1, d2, d3, d4))
+    }
+
+    /// The field values of the UUID, with the last 8 octets as a reference.
+    pub fn as_fields(&self) -> (u32, u16, u16, &[u8; 8]) {
+        self.0.as_fields()
+    }
+
+    /// The field values of the UUID with the first three fields little-endian.
+    pub fn to_fields_le(&self) -> (u32, u16, u16, &[u8; 8]) {
+        self.0.as_fields_le()
+    }
+
+    /// The 16 big-endian octets of the UUID.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &(self.0).0
+    }
+
+    /// Consume the UUID into its 16 big-endian octets.
+    pub const fn into_bytes(self) -> [u8; 16] {
+        (self.0).0
+    }
+
+    /// The UUID as a 128-bit big-endian value.
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes((self.0).0)
+    }
+
+    /// Whether all octets are zero.
+    pub const fn is_nil(&self) -> bool {
+        self.0.is_nil()
+    }
+
+    /// Whether all octets are `0xff`.
+    pub const fn is_max(&self) -> bool {
+        self.0.is_max()
+    }
+
+    /// Raw version number of the UUID.
+    pub const fn get_version_num(&self) -> usize {
+        self.0.get_version_num() as usize
+    }
+
+    /// Adapter formatting the UUID as `8-4-4-4-12` hex digits.
+    pub const fn hyphenated(self) -> fmt::Hyphenated {
+        fmt::Hyphenated(self)
+    }
+
+    /// Adapter formatting the UUID as 32 hex digits.
+    pub const fn simple(self) -> fmt::Simple {
+        fmt::Simple(self)
+    }
+
+    /// Adapter formatting the UUID as a braced hyphenated string.
+    pub const fn braced(self) -> fmt::Braced {
+        fmt::Braced(self)
+    }
+
+    /// Adapter formatting the UUID as a `urn:uuid:` URN.
+    pub const fn urn(self) -> fmt::Urn {
+        fmt::Urn(self)
+    }
+}
+
+impl From<UUID> for Uuid {
+    fn from(uuid: UUID) -> Self {
+        Uuid(uuid)
+    }
+}
+
+impl From<Uuid> for UUID {
+    fn from(uuid: Uuid) -> Self {
+        uuid.0
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::parse_str(s)
+    }
+}
+
+impl core::fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.hyphenated(), f)
+    }
+}
+
+impl core::fmt::Display for Uuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.hyphenated(), f)
+    }
+}
+
+impl core::fmt::LowerHex for Uuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(&self.hyphenated(), f)
+    }
+}
+
+impl core::fmt::UpperHex for Uuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::UpperHex::fmt(&self.hyphenated(), f)
+    }
+}
+
+fn write_encoded(
+    uuid: &Uuid,
+    format: Format,
+    upper: bool,
+    f: &mut core::fmt::Formatter<'_>,
+) -> core::fmt::Result {
+    let mut buf = [0u8; MAX_ENCODED_LEN];
+    let n = uuid.0.encode_case_into(format, upper, &mut buf).unwrap();
+    f.pad(core::str::from_utf8(&buf[..n]).unwrap())
+}
+
+/// Formatting adapters, like `uuid::fmt`.
+pub mod fmt {
+    use core::fmt;
+
+    use super::{write_encoded, Uuid};
+    use crate::Format;
+
+    macro_rules! adapter {
+        ($(#[$doc:meta])* $name:ident, $format:expr) => {
+            $(#[$doc])*
+            #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+            pub struct $name(pub(super) Uuid);
+
+            impl $name {
+                /// Wrap a UUID in the adapter.
+                pub const fn from_uuid(uuid: Uuid) -> Self {
+                    $name(uuid)
+                }
+
+                /// The wrapped UUID.
+                pub const fn as_uuid(&self) -> &Uuid {
+                    &self.0
+                }
+
+                /// Unwrap the UUID.
+                pub const fn into_uuid(self) -> Uuid {
+                    self.0
+                }
+            }
+
+            impl From<Uuid> for $name {
+                fn from(uuid: Uuid) -> Self {
+                    $name(uuid)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write_encoded(&self.0, $format, false, f)
+                }
+            }
+
+            impl fmt::LowerHex for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write_encoded(&self.0, $format, false, f)
+                }
+            }
+
+            impl fmt::UpperHex for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write_encoded(&self.0, $format, true, f)
+                }
+            }
+        };
+    }
+
+    adapter!(
+        /// Formats a UUID as `8-4-4-4-12` hex digits.
+        Hyphenated,
+        Format::Hyphenated
+    );
+    adapter!(
+        /// Formats a UUID as 32 hex digits.
+        Simple,
+        Format::Simple
+    );
+    adapter!(
+        /// Formats a UUID as a braced hyphenated string.
+        Braced,
+        Format::Braced
+    );
+    adapter!(
+        /// Formats a UUID as a `urn:uuid:` URN.
+        Urn,
+        Format::Urn
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DNS: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+    #[test]
+    fn parse_and_format_like_uuid() {
+        let uuid = Uuid::parse_str(DNS).unwrap();
+        assert_eq!(uuid, Uuid::NAMESPACE_DNS);
+        assert_eq!(uuid.to_string(), DNS);
+        assert_eq!(
+            uuid.simple().to_string(),
+            "6ba7b8109dad11d180b400c04fd430c8"
+        );
+        assert_eq!(format!("{}", uuid.braced()), format!("{{{}}}", DNS));
+        assert_eq!(
+            format!("{:X}", uuid.urn()),
+            "urn:uuid:6BA7B810-9DAD-11D1-80B4-00C04FD430C8"
+        );
+        assert_eq!(Uuid::parse_str(&uuid.urn().to_string()), Ok(uuid));
+        assert!(Uuid::parse_str("6ba7b810").is_err());
+    }
+
+    #[test]
+    fn bytes_fields_and_integers() {
+        let uuid = Uuid::NAMESPACE_DNS;
+        assert_eq!(Uuid::from_bytes(uuid.into_bytes()), uuid);
+        assert_eq!(Uuid::from_u128(uuid.as_u128()), uuid);
+        assert_eq!(Uuid::from_slice(uuid.as_bytes()), Ok(uuid));
+        assert!(Uuid::from_slice(&[0; 15]).is_err());
+
+        let (d1, d2, d3, d4) = uuid.as_fields();
+        assert_eq!((d1, d2, d3), (0x6ba7_b810, 0x9dad, 0x11d1));
+        assert_eq!(Uuid::from_fields(d1, d2, d3, d4), uuid);
+        let (d1, d2, d3, d4) = uuid.to_fields_le();
+        assert_eq!((d1, d2, d3), (0x10b8_a76b, 0xad9d, 0xd111));
+        assert_eq!(Uuid::from_fields_le(d1, d2, d3, d4), uuid);
+        assert_eq!(uuid.get_version_num(), 1);
+        assert!(Uuid::nil().is_nil());
+        assert!(Uuid::max().is_max());
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn new_v4_version() {
+        assert_eq!(Uuid::new_v4().get_version_num(), 4);
+    }
+}
@@ -0,0 +1,143 @@
+use core::fmt;
+
+use crate::fields::*;
+use crate::UUID;
+
+/// A field of the UUID layout.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum FieldName {
+    /// The low field of the Timestamp.
+    FieldLow,
+    /// The mid field of the Timestamp.
+    FieldMid,
+    /// The high field of the Timestamp multiplexed with the version number.
+    FieldHighAndVersion,
+    /// The high field of the ClockSeq multiplexed with the variant.
+    ClockSeqHighAndReserved,
+    /// The low field of the ClockSeq.
+    ClockSeqLow,
+    /// IEEE 802 MAC-address.
+    Node,
+}
+
+impl FieldName {
+    /// Every field, in memory order.
+    pub const ALL: [FieldName; 6] = [
+        FieldName::FieldLow,
+        FieldName::FieldMid,
+        FieldName::FieldHighAndVersion,
+        FieldName::ClockSeqHighAndReserved,
+        FieldName::ClockSeqLow,
+        FieldName::Node,
+    ];
+
+    /// Snake-case name of the field, as in `Layout`.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            FieldName::FieldLow => "field_low",
+            FieldName::FieldMid => "field_mid",
+            FieldName::FieldHighAndVersion => "field_high_and_version",
+            FieldName::ClockSeqHighAndReserved => "clock_seq_high_and_reserved",
+            FieldName::ClockSeqLow => "clock_seq_low",
+            FieldName::Node => "node",
+        }
+    }
+
+    /// Byte offset and width of the field.
+    pub const fn span(&self) -> (usize, usize) {
+        match self {
+            FieldName::FieldLow => (FIELD_LOW_OFFSET, FIELD_LOW_WIDTH),
+            FieldName::FieldMid => (FIELD_MID_OFFSET, FIELD_MID_WIDTH),
+            FieldName::FieldHighAndVersion => {
+                (FIELD_HIGH_AND_VERSION_OFFSET, FIELD_HIGH_AND_VERSION_WIDTH)
+            }
+            FieldName::ClockSeqHighAndReserved => (
+                CLOCK_SEQ_HIGH_AND_RESERVED_OFFSET,
+                CLOCK_SEQ_HIGH_AND_RESERVED_WIDTH,
+            ),
+            FieldName::ClockSeqLow => (CLOCK_SEQ_LOW_OFFSET, CLOCK_SEQ_LOW_WIDTH),
+            FieldName::Node => (NODE_OFFSET, NODE_WIDTH),
+        }
+    }
+}
+
+impl fmt::Display for FieldName {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// Iterator over the layout fields of a UUID, see `UUID::components_iter`.
+#[derive(Debug, Clone)]
+pub struct Components {
+    uuid: UUID,
+    next: usize,
+}
+
+impl Iterator for Components {
+    type Item = (FieldName, u64);
+
+    fn next(&mut self) -> Option<(FieldName, u64)> {
+        let name = *FieldName::ALL.get(self.next)?;
+        self.next += 1;
+        Some((name, self.uuid.field(name)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = FieldName::ALL.len() - self.next;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Components {}
+
+impl UUID {
+    /// Big-endian value of one layout field.
+    pub fn field(&self, name: FieldName) -> u64 {
+        let (offset, width) = name.span();
+        self.0[offset..offset + width]
+            .iter()
+            .fold(0, |v, b| v << 8 | u64::from(*b))
+    }
+
+    /// Every layout field with its value, in memory order.
+    pub fn components_iter(&self) -> Components {
+        Components {
+            uuid: *self,
+            next: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_of_namespace() {
+        let fields: Vec<(FieldName, u64)> = UUID::NAMESPACE_DNS.components_iter().collect();
+        assert_eq!(
+            fields,
+            vec![
+                (FieldName::FieldLow, 0x6ba7_b810),
+                (FieldName::FieldMid, 0x9dad),
+                (FieldName::FieldHighAndVersion, 0x11d1),
+                (FieldName::ClockSeqHighAndReserved, 0x80),
+                (FieldName::ClockSeqLow, 0xb4),
+                (FieldName::Node, 0x00c0_4fd4_30c8),
+            ]
+        );
+    }
+
+    #[test]
+    fn components_differ() {
+        let differ: Vec<FieldName> = UUID::NAMESPACE_DNS
+            .components_iter()
+            .zip(UUID::NAMESPACE_URL.components_iter())
+            .filter(|(a, b)| a.1 != b.1)
+            .map(|(a, _)| a.0)
+            .collect();
+        assert_eq!(differ, vec![FieldName::FieldLow]);
+        assert_eq!(FieldName::FieldLow.to_string(), "field_low");
+    }
+}
@@ -0,0 +1,91 @@
+use core::fmt;
+
+use crate::format::decode_hyphenated;
+use crate::UUID;
+
+/// A key made of `N` UUIDs, ordered lexicographically, e.g. `(tenant_id, resource_id)`.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct CompositeKey<const N: usize>(pub [UUID; N]);
+
+impl<const N: usize> CompositeKey<N> {
+    /// Separator between the UUIDs of the textual form.
+    pub const SEPARATOR: char = ':';
+
+    /// The `16 * N` octets of the key, UUID after UUID.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|uuid| uuid.0).collect()
+    }
+
+    /// Key from exactly `16 * N` octets, as written by `to_vec`.
+    pub fn from_slice(b: &[u8]) -> Option<Self> {
+        if b.len() != 16 * N {
+            return None;
+        }
+        let mut key = [UUID::default(); N];
+        for (uuid, chunk) in key.iter_mut().zip(b.chunks_exact(16)) {
+            uuid.0.copy_from_slice(chunk);
+        }
+        Some(Self(key))
+    }
+
+    /// Key from `N` hyphenated UUIDs joined by `:`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut key = [UUID::default(); N];
+        let mut parts = s.split(Self::SEPARATOR);
+        for uuid in key.iter_mut() {
+            *uuid = decode_hyphenated(parts.next()?)?;
+        }
+        match parts.next() {
+            Some(_) => None,
+            None => Some(Self(key)),
+        }
+    }
+}
+
+impl<const N: usize> From<[UUID; N]> for CompositeKey<N> {
+    fn from(key: [UUID; N]) -> Self {
+        Self(key)
+    }
+}
+
+impl<const N: usize> fmt::Display for CompositeKey<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, uuid) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(fmt, "{}", Self::SEPARATOR)?;
+            }
+            write!(fmt, "{:x}", uuid)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAIR: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8:6ba7b811-9dad-11d1-80b4-00c04fd430c8";
+
+    #[test]
+    fn composite_key_round_trip() {
+        let key = CompositeKey([UUID::NAMESPACE_DNS, UUID::NAMESPACE_URL]);
+        assert_eq!(key.to_string(), PAIR);
+        assert_eq!(CompositeKey::parse(PAIR), Some(key));
+        assert_eq!(key.to_vec().len(), 32);
+        assert_eq!(CompositeKey::from_slice(&key.to_vec()), Some(key));
+    }
+
+    #[test]
+    fn composite_key_rejects_wrong_arity() {
+        assert_eq!(CompositeKey::<3>::parse(PAIR), None);
+        assert_eq!(CompositeKey::<1>::parse(PAIR), None);
+        assert_eq!(CompositeKey::<2>::from_slice(&[0u8; 16]), None);
+    }
+
+    #[test]
+    fn composite_key_order() {
+        let a = CompositeKey([UUID::NAMESPACE_DNS, UUID::NAMESPACE_X500]);
+        let b = CompositeKey([UUID::NAMESPACE_URL, UUID::NAMESPACE_DNS]);
+        assert!(a < b);
+    }
+}
@@ -0,0 +1,403 @@
+//! Generators chosen and tuned by configuration rather than code.
+//!
+//! A [`GeneratorConfig`] deserializes from any `serde` format, such as a
+//! TOML section or a JSON object, and builds the generator it describes:
+//!
+//! ```rust
+//! # #[cfg(feature = "rand_num")] {
+//! use simple_uuid::config::GeneratorConfig;
+//! use simple_uuid::Generator;
+//!
+//! let config: GeneratorConfig = serde_json::from_str(
+//!     r#"{ "version": 7, "counter_bits": 12, "rng": { "seeded": 42 } }"#,
+//! )
+//! .unwrap();
+//! let generator = config.build().unwrap();
+//! assert_eq!(generator.generate().unwrap().get_version_num(), 7);
+//! # }
+//! ```
+#![doc(cfg(feature = "config"))]
+#![cfg(feature = "config")]
+
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use ::serde::Deserialize;
+#[cfg(feature = "rand_num")]
+use rand_core::{OsRng, RngCore, SeedableRng};
+
+#[cfg(feature = "mac_addr")]
+use crate::Node;
+#[cfg(feature = "rand_num")]
+use crate::{Clock, UUID};
+use crate::{Generator, UuidError};
+
+/// Description of a generator, see [`GeneratorConfig::build`].
+///
+/// Every field is optional; fields that do not apply to the version are
+/// rejected rather than ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorConfig {
+    /// Version of the UUIDs: 1, 4, 6 or 7. Defaults to
+    /// [`UUID::DEFAULT_VERSION`](crate::UUID::DEFAULT_VERSION) when random
+    /// numbers are enabled, else to 1.
+    pub version: u8,
+    /// Node of version-1 and 6 UUIDs, by default the MAC-address of this
+    /// device.
+    pub node: Option<NodeConfig>,
+    /// Epoch of version-7 timestamps in milliseconds since the UNIX epoch,
+    /// for schemes counting from a custom epoch; by default the UNIX epoch.
+    pub epoch_ms: Option<u64>,
+    /// Counter bits of version-7 UUIDs: 42 for the dedicated counter method
+    /// of a [`V7Generator`](crate::V7Generator), the default, 12 for its
+    /// monotonic random method, and 0 for no counter and no ordering within
+    /// a millisecond.
+    pub counter_bits: Option<u8>,
+    /// File keeping the clock state of version-1 and 6 UUIDs across
+    /// restarts, see [`V1Context::with_state`](crate::V1Context::with_state).
+    pub state_path: Option<PathBuf>,
+    /// Source of the random bits of version-4 and 7 UUIDs.
+    pub rng: RngPolicy,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            version: default_version(),
+            node: None,
+            epoch_ms: None,
+            counter_bits: None,
+            state_path: None,
+            rng: RngPolicy::default(),
+        }
+    }
+}
+
+/// Node of a [`GeneratorConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeConfig {
+    /// The MAC-address of this device, else the random node of the process.
+    Device,
+    /// The random node of the process.
+    Random,
+    /// Six hexadecimal octets separated by `-` or `:`.
+    Fixed(String),
+}
+
+/// Source of random bits of a [`GeneratorConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RngPolicy {
+    /// A generator per thread seeded from the operating system, as
+    /// [`UUID::new`](crate::UUID::new).
+    #[default]
+    ThreadLocal,
+    /// The operating system for every UUID.
+    Os,
+    /// A ChaCha20 generator seeded with this value, for reproducible UUIDs
+    /// in tests; never in production.
+    Seeded(u64),
+}
+
+/// Invalid [`GeneratorConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A field with an unsupported value, or not applying to the version.
+    Field {
+        /// Name of the field.
+        field: &'static str,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The generator could not be created.
+    Generator(UuidError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Field { field, reason } => write!(fmt, "invalid `{}`: {}", field, reason),
+            ConfigError::Generator(e) => write!(fmt, "cannot create generator: {}", e),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl From<UuidError> for ConfigError {
+    fn from(e: UuidError) -> Self {
+        ConfigError::Generator(e)
+    }
+}
+
+impl GeneratorConfig {
+    /// The generator described by the configuration.
+    pub fn build(&self) -> Result<Box<dyn Generator + Send + Sync>, ConfigError> {
+        match self.version {
+            #[cfg(feature = "mac_addr")]
+            1 | 6 => self.build_time(),
+            #[cfg(feature = "rand_num")]
+            4 => self.build_rand(),
+            #[cfg(feature = "rand_num")]
+            7 => self.build_unix(),
+            _ => Err(ConfigError::Field {
+                field: "version",
+                reason: "cannot be generated with the enabled features",
+            }),
+        }
+    }
+
+    #[cfg(feature = "mac_addr")]
+    fn build_time(&self) -> Result<Box<dyn Generator + Send + Sync>, ConfigError> {
+        self.reject(self.epoch_ms.is_some(), "epoch_ms")?;
+        self.reject(self.counter_bits.is_some(), "counter_bits")?;
+        self.reject(self.rng != RngPolicy::ThreadLocal, "rng")?;
+
+        let node = match &self.node {
+            None | Some(NodeConfig::Device) => crate::time::default_node()?,
+            #[cfg(feature = "rand_num")]
+            Some(NodeConfig::Random) => crate::NodeSource::node(&crate::RandomNode)?,
+            #[cfg(not(feature = "rand_num"))]
+            Some(NodeConfig::Random) => {
+                return Err(ConfigError::Field {
+                    field: "node",
+                    reason: "random nodes need the `rand_num` feature",
+                })
+            }
+            Some(NodeConfig::Fixed(node)) => parse_node(node).ok_or(ConfigError::Field {
+                field: "node",
+                reason: "expected six hexadecimal octets",
+            })?,
+        };
+        let mut ctx = crate::V1Context::new(node)?;
+        if let Some(path) = &self.state_path {
+            ctx = ctx.with_state(crate::state::FileStore::new(path))?;
+        }
+        Ok(match self.version {
+            1 => Box::new(ctx),
+            _ => Box::new(move || ctx.v6().map(|uuid| uuid.to_uuid())),
+        })
+    }
+
+    #[cfg(feature = "rand_num")]
+    fn build_rand(&self) -> Result<Box<dyn Generator + Send + Sync>, ConfigError> {
+        self.reject(self.node.is_some(), "node")?;
+        self.reject(self.epoch_ms.is_some(), "epoch_ms")?;
+        self.reject(self.counter_bits.is_some(), "counter_bits")?;
+        self.reject(self.state_path.is_some(), "state_path")?;
+
+        Ok(match self.rng.rng() {
+            None => Box::new(UUID::new_v4_forever),
+            Some(rng) => {
+                let rng = std::sync::Mutex::new(rng);
+                Box::new(move || {
+                    let mut rng = rng.lock().unwrap_or_else(|e| e.into_inner());
+                    UUID::v4_from_rng(&mut **rng).map(|uuid| uuid.to_uuid())
+                })
+            }
+        })
+    }
+
+    #[cfg(feature = "rand_num")]
+    fn build_unix(&self) -> Result<Box<dyn Generator + Send + Sync>, ConfigError> {
+        use crate::{RolloverPolicy, V7Generator, V7Method};
+
+        self.reject(self.node.is_some(), "node")?;
+        self.reject(self.state_path.is_some(), "state_path")?;
+
+        let offset = self.epoch_ms.unwrap_or(0).checked_mul(10_000);
+        let clock = EpochClock(offset.ok_or(ConfigError::Field {
+            field: "epoch_ms",
+            reason: "out of range",
+        })?);
+        let method = match self.counter_bits {
+            None | Some(42) => V7Method::Counter,
+            Some(12) => V7Method::RandomIncrement(RolloverPolicy::default()),
+            Some(0) => {
+                return Ok(match self.rng.rng() {
+                    None => {
+                        Box::new(move || UUID::v7_with_clock(&clock).map(|uuid| uuid.to_uuid()))
+                    }
+                    Some(rng) => {
+                        let rng = std::sync::Mutex::new(rng);
+                        Box::new(move || {
+                            let ms = clock.try_now_100ns()? / 10_000;
+                            let mut rng = rng.lock().unwrap_or_else(|e| e.into_inner());
+                            UUID::v7_from_millis_and_rng(ms, &mut **rng).map(|uuid| uuid.to_uuid())
+                        })
+                    }
+                })
+            }
+            Some(_) => {
+                return Err(ConfigError::Field {
+                    field: "counter_bits",
+                    reason: "expected 0, 12 or 42",
+                })
+            }
+        };
+        let generator = V7Generator::with_method(method).with_clock(clock);
+        Ok(match self.rng.rng() {
+            None => Box::new(generator),
+            Some(rng) => Box::new(generator.with_boxed_rng(rng)),
+        })
+    }
+
+    // Fail on `field` when it is set for a version it does not apply to.
+    #[cfg(any(feature = "mac_addr", feature = "rand_num"))]
+    fn reject(&self, set: bool, field: &'static str) -> Result<(), ConfigError> {
+        match set {
+            true => Err(ConfigError::Field {
+                field,
+                reason: "does not apply to the version",
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "rand_num")]
+impl RngPolicy {
+    // The generator of the policy, `None` for the thread-local one.
+    fn rng(&self) -> Option<Box<dyn RngCore + Send>> {
+        match *self {
+            RngPolicy::ThreadLocal => None,
+            RngPolicy::Os => Some(Box::new(OsRng)),
+            RngPolicy::Seeded(seed) => {
+                Some(Box::new(rand_chacha::ChaCha20Rng::seed_from_u64(seed)))
+            }
+        }
+    }
+}
+
+/// System clock counting from a custom epoch, `offset` 100-ns ticks after
+/// the UNIX epoch.
+#[cfg(feature = "rand_num")]
+#[derive(Debug, Clone, Copy)]
+struct EpochClock(u64);
+
+#[cfg(feature = "rand_num")]
+impl Clock for EpochClock {
+    fn now_100ns(&self) -> u64 {
+        crate::SystemClock.now_100ns().saturating_sub(self.0)
+    }
+
+    fn try_now_100ns(&self) -> Result<u64, UuidError> {
+        crate::SystemClock
+            .try_now_100ns()?
+            .checked_sub(self.0)
+            .ok_or(UuidError::Clock)
+    }
+}
+
+#[cfg(feature = "rand_num")]
+fn default_version() -> u8 {
+    UUID::DEFAULT_VERSION as u8
+}
+
+#[cfg(not(feature = "rand_num"))]
+fn default_version() -> u8 {
+    1
+}
+
+// Node of six hexadecimal octets separated by `-` or `:`.
+#[cfg(feature = "mac_addr")]
+fn parse_node(s: &str) -> Option<Node> {
+    let mut node = [0u8; 6];
+    let mut octets = s.split(['-', ':']);
+    for octet in node.iter_mut() {
+        let hex = octets.next()?;
+        if hex.len() != 2 {
+            return None;
+        }
+        *octet = u8::from_str_radix(hex, 16).ok()?;
+    }
+    match octets.next() {
+        None => Some(Node(node)),
+        Some(_) => None,
+    }
+}
+
+#[cfg(all(test, feature = "mac_addr", feature = "rand_num"))]
+mod tests {
+    use super::*;
+
+    fn config(json: &str) -> GeneratorConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn builds_configured_generators() {
+        let v7 = config(r#"{ "version": 7, "epoch_ms": 1600000000000, "rng": { "seeded": 1 } }"#);
+        let generator = v7.build().unwrap();
+        let (a, b) = (generator.generate().unwrap(), generator.generate().unwrap());
+        assert!(a < b);
+        let since_epoch = a.timestamp_ms().unwrap();
+        let since_unix = UUID::v7().unwrap().to_uuid().timestamp_ms().unwrap();
+        assert!(since_unix - since_epoch >= 1_600_000_000_000);
+
+        for bits in [0, 12, 42] {
+            let json = format!(
+                r#"{{ "version": 7, "counter_bits": {}, "rng": "os" }}"#,
+                bits
+            );
+            let uuid = config(&json).build().unwrap().generate().unwrap();
+            assert_eq!(uuid.get_version_num(), 7);
+        }
+
+        let seeded = config(r#"{ "version": 4, "rng": { "seeded": 9 } }"#);
+        assert_eq!(
+            seeded.build().unwrap().generate(),
+            seeded.build().unwrap().generate()
+        );
+
+        let path = std::env::temp_dir().join(format!("simple-uuid-config-{}", std::process::id()));
+        let v6 = GeneratorConfig {
+            version: 6,
+            node: Some(NodeConfig::Fixed("02:00:5e:10:00:01".into())),
+            state_path: Some(path.clone()),
+            ..GeneratorConfig::default()
+        };
+        let uuid = v6.build().unwrap().generate().unwrap().decode();
+        assert_eq!(uuid.get_node(), Node([0x02, 0x00, 0x5e, 0x10, 0x00, 0x01]));
+        assert_eq!(uuid.get_version_num(), 6);
+        assert!(path.exists());
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(config("{}").version, UUID::DEFAULT_VERSION as u8);
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let error = |json: &str| config(json).build().err().unwrap();
+        assert_eq!(
+            error(r#"{ "version": 3 }"#),
+            ConfigError::Field {
+                field: "version",
+                reason: "cannot be generated with the enabled features"
+            }
+        );
+        assert!(matches!(
+            error(r#"{ "version": 4, "epoch_ms": 0 }"#),
+            ConfigError::Field {
+                field: "epoch_ms",
+                ..
+            }
+        ));
+        assert!(matches!(
+            error(r#"{ "version": 7, "counter_bits": 20 }"#),
+            ConfigError::Field {
+                field: "counter_bits",
+                ..
+            }
+        ));
+        assert!(matches!(
+            error(r#"{ "version": 1, "node": { "fixed": "02:00:5e" } }"#),
+            ConfigError::Field { field: "node", .. }
+        ));
+        assert!(serde_json::from_str::<GeneratorConfig>(r#"{ "versions": 4 }"#).is_err());
+    }
+}
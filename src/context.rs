@@ -0,0 +1,320 @@
+#![doc(cfg(feature = "mac_addr"))]
+#![cfg(feature = "mac_addr")]
+
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use rand_core::{OsRng, RngCore};
+
+use crate::fields::VARIANT_RFC;
+use crate::state::{GeneratorState, StateStore};
+use crate::{
+    Clock, Generator, Layout, MacAddressNode, Node, NodeSource, SystemClock, TimeStamp, UuidError,
+    UUID,
+};
+
+/// Timestamps this close behind the last one are taken for a burst faster
+/// than the clock resolution rather than for the clock going backwards.
+const BURST_TICKS: u64 = 10_000;
+
+/// How far past the timestamp of a UUID the saved timestamp is set, ten
+/// seconds, so the state is written once per interval rather than per UUID.
+const SAVE_AHEAD_TICKS: u64 = 100_000_000;
+
+// Clock state shared by the version-1 and 6 constructors of `UUID` in this
+// process; its node is unused.
+static PROCESS: OnceLock<V1Context> = OnceLock::new();
+
+/// Shared state of version-1 and 6 generation: the node, the clock sequence
+/// and the last timestamp.
+///
+/// Within a burst faster than the clock, the timestamp of each UUID is one
+/// tick past the last one; when the clock goes backwards, the clock sequence
+/// is incremented instead, so UUIDs of one context never repeat. Share a
+/// context by reference or through an `Arc`.
+///
+/// With a [`StateStore`] the state also survives restarts, see
+/// [`V1Context::with_state`].
+pub struct V1Context {
+    node: Node,
+    state: Mutex<ClockState>,
+    store: Option<Box<dyn StateStore + Send + Sync>>,
+}
+
+// Last timestamp and clock sequence, and the timestamp saved to the store.
+#[derive(Debug)]
+struct ClockState {
+    last: u64,
+    clock_seq: u16,
+    saved_until: u64,
+}
+
+impl fmt::Debug for V1Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("V1Context")
+            .field("node", &self.node)
+            .field("state", &self.state)
+            .field("persistent", &self.store.is_some())
+            .finish()
+    }
+}
+
+impl V1Context {
+    /// New context for `node`, starting at a random clock sequence.
+    pub fn new(node: Node) -> Result<Self, UuidError> {
+        let mut key = [0u8; 2];
+        OsRng
+            .try_fill_bytes(&mut key)
+            .map_err(|_| UuidError::Entropy)?;
+        Ok(Self::with_clock_seq(node, u16::from_be_bytes(key)))
+    }
+
+    /// New context for `node`, starting at the 14-bit `clock_seq`.
+    pub fn with_clock_seq(node: Node, clock_seq: u16) -> Self {
+        Self {
+            node,
+            state: Mutex::new(ClockState {
+                last: 0,
+                clock_seq: clock_seq & 0x3fff,
+                saved_until: 0,
+            }),
+            store: None,
+        }
+    }
+
+    /// Persist the node, clock sequence and last timestamp to `store`,
+    /// resuming from what it holds, as `rfc4122` 4.2.1.1 asks of stable
+    /// storage.
+    ///
+    /// The saved timestamp is kept ten seconds ahead of the UUIDs issued, so
+    /// the store is written about once per ten seconds. When it is found
+    /// ahead of the clock on restart, as after a quick restart or a clock
+    /// set back, the clock sequence is incremented. State saved for another
+    /// node is ignored.
+    pub fn with_state<S: StateStore + Send + Sync + 'static>(
+        mut self,
+        store: S,
+    ) -> Result<Self, UuidError> {
+        let state = self.state.get_mut().unwrap_or_else(|e| e.into_inner());
+        match store.load()? {
+            Some(saved) if saved.node == Some(self.node) => {
+                state.last = saved.last_timestamp;
+                state.clock_seq = saved.clock_seq & 0x3fff;
+            }
+            _ => state.last = 0,
+        }
+        state.saved_until = 0;
+        self.store = Some(Box::new(store));
+        Ok(self)
+    }
+
+    /// New context for the node of `source`, read once.
+    pub fn with_node_source<S: NodeSource + ?Sized>(source: &S) -> Result<Self, UuidError> {
+        Self::new(source.node()?)
+    }
+
+    /// New context for the MAC-address of this device.
+    pub fn from_device() -> Result<Self, UuidError> {
+        Self::with_node_source(&MacAddressNode)
+    }
+
+    /// Node of the UUIDs of this context.
+    pub fn node(&self) -> Node {
+        self.node
+    }
+
+    /// Current clock sequence.
+    pub fn clock_seq(&self) -> u16 {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clock_seq
+    }
+
+    /// New UUID version-1.
+    pub fn v1(&self) -> Result<Layout, UuidError> {
+        self.v1_with_clock(&SystemClock)
+    }
+
+    /// New UUID version-1 at the time of `clock`.
+    pub fn v1_with_clock<C: Clock + ?Sized>(&self, clock: &C) -> Result<Layout, UuidError> {
+        let (utc, clock_seq) = self.next(TimeStamp::from_clock(clock)?.ticks())?;
+        Ok(Layout::time_fields(utc, clock_seq, self.node))
+    }
+
+    /// New UUID version-6, sorting by creation time.
+    pub fn v6(&self) -> Result<Layout, UuidError> {
+        self.v6_with_clock(&SystemClock)
+    }
+
+    /// New UUID version-6 at the time of `clock`.
+    pub fn v6_with_clock<C: Clock + ?Sized>(&self, clock: &C) -> Result<Layout, UuidError> {
+        let (utc, clock_seq) = self.next(TimeStamp::from_clock(clock)?.ticks())?;
+        Ok(Layout::ordered_time_fields(utc, clock_seq, self.node))
+    }
+
+    // The clock state of this process, initialized on first use.
+    pub(crate) fn process() -> Result<&'static V1Context, UuidError> {
+        if let Some(ctx) = PROCESS.get() {
+            return Ok(ctx);
+        }
+        let ctx = V1Context::new(Node::default())?;
+        Ok(PROCESS.get_or_init(|| ctx))
+    }
+
+    // Clock sequence fields of a UUID of a time given by the caller rather
+    // than read from the clock: the clock sequence is advanced, as when the
+    // clock goes backwards, so two UUIDs of the same time differ. The last
+    // timestamp is left alone.
+    pub(crate) fn bump_clock_seq(&self) -> Result<(u8, u8), UuidError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.clock_seq = (state.clock_seq + 1) & 0x3fff;
+        if let Some(store) = &self.store {
+            store.save(&GeneratorState {
+                node: Some(self.node),
+                clock_seq: state.clock_seq,
+                last_timestamp: state.saved_until,
+                counter: 0,
+            })?;
+        }
+        let clock_seq = state.clock_seq;
+        Ok(((clock_seq >> 8) as u8 | VARIANT_RFC, clock_seq as u8))
+    }
+
+    // Timestamp and clock sequence fields of the UUID read at `utc`.
+    pub(crate) fn next(&self, utc: u64) -> Result<(u64, (u8, u8)), UuidError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let clock_seq = state.clock_seq;
+        if utc > state.last {
+            state.last = utc;
+        } else if utc + BURST_TICKS > state.last {
+            state.last += 1;
+        } else {
+            state.last = utc;
+            state.clock_seq = (clock_seq + 1) & 0x3fff;
+        }
+
+        if let Some(store) = &self.store {
+            if state.last >= state.saved_until || state.clock_seq != clock_seq {
+                let saved_until = state.last + SAVE_AHEAD_TICKS;
+                store.save(&GeneratorState {
+                    node: Some(self.node),
+                    clock_seq: state.clock_seq,
+                    last_timestamp: saved_until,
+                    counter: 0,
+                })?;
+                state.saved_until = saved_until;
+            }
+        }
+
+        let (utc, clock_seq) = (state.last, state.clock_seq);
+        Ok((utc, ((clock_seq >> 8) as u8 | VARIANT_RFC, clock_seq as u8)))
+    }
+}
+
+impl Generator for V1Context {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        self.v1().map(|uuid| uuid.to_uuid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryStore;
+    use crate::Version;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn stable_clock_seq_without_duplicates() {
+        let ctx = V1Context::with_clock_seq(Node([1, 2, 3, 4, 5, 6]), 0x1234);
+        let ids: HashSet<UUID> = (0..10_000).map(|_| ctx.generate().unwrap()).collect();
+        assert_eq!(ids.len(), 10_000);
+        assert_eq!(ctx.clock_seq(), 0x1234);
+
+        let uuid = ctx.v6().unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::REORDERED));
+        assert_eq!(uuid.get_clock_seq(), 0x1234);
+        assert_eq!(uuid.get_node(), Node([1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn process_state_shared_between_threads() {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    (0..1_000)
+                        .map(|_| UUID::new_from_node(Node::default()).unwrap().to_uuid())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut ids = HashSet::new();
+        for h in handles {
+            ids.extend(h.join().unwrap());
+        }
+        assert_eq!(ids.len(), 4_000);
+        let ctx = V1Context::process().unwrap();
+        assert!(std::ptr::eq(ctx, V1Context::process().unwrap()));
+    }
+
+    #[test]
+    fn custom_clock() {
+        let ctx = V1Context::with_clock_seq(Node::default(), 1);
+        let uuid = ctx.v1_with_clock(&|| 16_456_896_000_000_000).unwrap();
+        assert_eq!(uuid.to_uuid().timestamp_ms(), Some(1_645_689_600_000));
+        let uuid = ctx.v6_with_clock(&|| 16_456_896_000_000_000).unwrap();
+        assert_eq!(uuid.to_uuid().timestamp_ms(), Some(1_645_689_600_000));
+        assert!(matches!(
+            ctx.v1_with_clock(&|| u64::MAX),
+            Err(UuidError::Clock)
+        ));
+    }
+
+    #[test]
+    fn burst_and_clock_regression() {
+        let ctx = V1Context::with_clock_seq(Node::default(), 0x3fff);
+        assert_eq!(ctx.next(1_000_000), Ok((1_000_000, (0xbf, 0xff))));
+        assert_eq!(ctx.next(1_000_000).unwrap().0, 1_000_001);
+        assert_eq!(ctx.next(999_999).unwrap().0, 1_000_002);
+
+        assert_eq!(ctx.next(500_000), Ok((500_000, (0x80, 0x00))));
+        assert_eq!(ctx.clock_seq(), 0);
+    }
+
+    #[test]
+    fn state_survives_restart() {
+        let node = Node([1, 2, 3, 4, 5, 6]);
+        let store = Arc::new(MemoryStore::new());
+        let ctx = V1Context::with_clock_seq(node, 7)
+            .with_state(Arc::clone(&store))
+            .unwrap();
+        ctx.next(1_000_000).unwrap();
+        ctx.next(1_000_001).unwrap();
+        let saved = store.load().unwrap().unwrap();
+        assert_eq!(saved.node, Some(node));
+        assert_eq!(saved.clock_seq, 7);
+        assert_eq!(saved.last_timestamp, 1_000_000 + SAVE_AHEAD_TICKS);
+
+        // Restarted before the saved timestamp: new clock sequence.
+        let ctx = V1Context::with_clock_seq(node, 100)
+            .with_state(Arc::clone(&store))
+            .unwrap();
+        assert_eq!(ctx.clock_seq(), 7);
+        assert_eq!(ctx.next(2_000_000), Ok((2_000_000, (0x80, 8))));
+        assert_eq!(store.load().unwrap().unwrap().clock_seq, 8);
+
+        // Restarted later: same clock sequence.
+        let ctx = V1Context::with_clock_seq(node, 100)
+            .with_state(Arc::clone(&store))
+            .unwrap();
+        assert_eq!(ctx.next(3 * SAVE_AHEAD_TICKS).unwrap().1, (0x80, 8));
+
+        // Another node: state ignored.
+        let ctx = V1Context::with_clock_seq(Node::default(), 100)
+            .with_state(store)
+            .unwrap();
+        assert_eq!(ctx.clock_seq(), 100);
+    }
+}
@@ -0,0 +1,371 @@
+//! Cross-checks name-based generation and parsing against other implementations.
+//!
+//! Each available implementation (`uuidgen`, Python's `uuid` module, Node's
+//! `crypto`) is fed the same corpus, and the answers are collected in a
+//! `CompatReport`. Implementations missing from the host are skipped.
+#![doc(cfg(feature = "crosscheck"))]
+#![cfg(feature = "crosscheck")]
+
+use core::fmt;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crate::format::decode_hex_formats;
+use crate::{Version, UUID};
+
+const PYTHON_SCRIPT: &str = r#"
+import sys, uuid
+for line in sys.stdin:
+    kind, *args = line.rstrip("\n").split("\t")
+    try:
+        if kind == "v3":
+            print(uuid.uuid3(uuid.UUID(args[0]), args[1]))
+        elif kind == "v5":
+            print(uuid.uuid5(uuid.UUID(args[0]), args[1]))
+        else:
+            uuid.UUID(args[0])
+            print("accept")
+    except ValueError:
+        print("reject")
+"#;
+
+const NODE_SCRIPT: &str = r#"
+const crypto = require("crypto");
+const lines = require("fs").readFileSync(0, "utf8").split("\n").filter((l) => l.length);
+for (const line of lines) {
+  const [kind, a, b] = line.split("\t");
+  if (kind === "parse") {
+    console.log("-");
+    continue;
+  }
+  const ns = Buffer.from(a.replace(/-/g, ""), "hex");
+  const h = crypto.createHash(kind === "v3" ? "md5" : "sha1").update(ns).update(b, "utf8").digest().subarray(0, 16);
+  h[6] = (h[6] & 0x0f) | (kind === "v3" ? 0x30 : 0x50);
+  h[8] = (h[8] & 0x3f) | 0x80;
+  const x = h.toString("hex");
+  console.log(`${x.slice(0, 8)}-${x.slice(8, 12)}-${x.slice(12, 16)}-${x.slice(16, 20)}-${x.slice(20)}`);
+}
+"#;
+
+/// Another UUID implementation found on the host.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Implementation {
+    /// The util-linux `uuidgen` command.
+    Uuidgen,
+    /// Python's `uuid` module, through `python3`.
+    Python,
+    /// Node's `crypto` module, through `node`.
+    Node,
+}
+
+impl Implementation {
+    /// Every implementation the harness knows about.
+    pub const ALL: [Implementation; 3] = [
+        Implementation::Uuidgen,
+        Implementation::Python,
+        Implementation::Node,
+    ];
+
+    /// Name of the program backing the implementation.
+    pub const fn program(&self) -> &'static str {
+        match self {
+            Implementation::Uuidgen => "uuidgen",
+            Implementation::Python => "python3",
+            Implementation::Node => "node",
+        }
+    }
+
+    /// Whether the program can be run on this host.
+    pub fn is_available(&self) -> bool {
+        Command::new(self.program())
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    // Answers every check in order, `None` where the implementation has no opinion.
+    fn answer(&self, checks: &[Check]) -> io::Result<Vec<Option<String>>> {
+        match self {
+            Implementation::Uuidgen => checks.iter().map(uuidgen).collect(),
+            Implementation::Python => {
+                run_script(Command::new("python3").args(["-c", PYTHON_SCRIPT]), checks)
+            }
+            Implementation::Node => {
+                run_script(Command::new("node").args(["-e", NODE_SCRIPT]), checks)
+            }
+        }
+    }
+}
+
+/// One input of the corpus.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Check {
+    /// Generate a name-based UUID; `version` is `Version::MD5` or `Version::SHA1`.
+    NameBased {
+        /// Hash of the name-based version.
+        version: Version,
+        /// Namespace of the name.
+        ns: UUID,
+        /// Name to hash, without tabs or newlines.
+        name: String,
+    },
+    /// Accept or reject a textual UUID.
+    Parse {
+        /// String to parse, without tabs or newlines.
+        input: String,
+    },
+}
+
+impl Check {
+    /// Answer of this crate.
+    pub fn ours(&self) -> String {
+        match self {
+            Check::NameBased { version, ns, name } => {
+                let layout = match version {
+                    Version::MD5 => UUID::v3_bytes(name.as_bytes(), *ns),
+                    _ => UUID::v5_bytes(name.as_bytes(), *ns),
+                };
+                match layout {
+                    Ok(layout) => format!("{:x}", layout.to_uuid()),
+                    Err(_) => "reject".to_string(),
+                }
+            }
+            Check::Parse { input } => match decode_hex_formats(input) {
+                Some(_) => "accept".to_string(),
+                None => "reject".to_string(),
+            },
+        }
+    }
+
+    fn line(&self) -> String {
+        match self {
+            Check::NameBased { version, ns, name } => {
+                let kind = if *version == Version::MD5 { "v3" } else { "v5" };
+                format!("{}\t{:x}\t{}\n", kind, ns, name)
+            }
+            Check::Parse { input } => format!("parse\t{}\n", input),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::NameBased { version, ns, name } => {
+                write!(fmt, "{:?}({:x}, {:?})", version, ns, name)
+            }
+            Check::Parse { input } => write!(fmt, "parse({:?})", input),
+        }
+    }
+}
+
+/// Answers of one implementation to one check.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Outcome {
+    /// The checked input.
+    pub check: Check,
+    /// The implementation compared against.
+    pub implementation: Implementation,
+    /// Answer of this crate.
+    pub ours: String,
+    /// Answer of the other implementation.
+    pub theirs: String,
+}
+
+impl Outcome {
+    /// Whether both implementations agree.
+    pub fn matches(&self) -> bool {
+        self.ours.eq_ignore_ascii_case(&self.theirs)
+    }
+}
+
+/// Result of a cross-check run.
+#[derive(Debug, Default, Clone)]
+pub struct CompatReport {
+    /// Every compared answer.
+    pub outcomes: Vec<Outcome>,
+    /// Implementations that were not found or failed to run.
+    pub skipped: Vec<Implementation>,
+}
+
+impl CompatReport {
+    /// Outcomes where the implementations disagree.
+    pub fn mismatches(&self) -> impl Iterator<Item = &Outcome> {
+        self.outcomes.iter().filter(|o| !o.matches())
+    }
+
+    /// Whether every compared answer agrees.
+    pub fn is_compatible(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for o in self.outcomes.iter() {
+            let status = if o.matches() { "ok" } else { "MISMATCH" };
+            writeln!(
+                fmt,
+                "{:<8} {:<8} {}",
+                status,
+                o.implementation.program(),
+                o.check
+            )?;
+            if !o.matches() {
+                writeln!(fmt, "         ours:   {}", o.ours)?;
+                writeln!(fmt, "         theirs: {}", o.theirs)?;
+            }
+        }
+        for i in self.skipped.iter() {
+            writeln!(fmt, "skipped  {}", i.program())?;
+        }
+        let total = self.outcomes.len();
+        let failed = self.mismatches().count();
+        write!(fmt, "{} compared, {} mismatched", total, failed)
+    }
+}
+
+/// Runs a corpus of checks against the available implementations.
+#[derive(Debug, Default, Clone)]
+pub struct Harness {
+    checks: Vec<Check>,
+}
+
+impl Harness {
+    /// New harness with an empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New harness with name-based checks over the standard namespaces and
+    /// parse checks over common and malformed inputs.
+    pub fn with_default_corpus() -> Self {
+        let mut harness = Self::new();
+        let namespaces = [
+            UUID::NAMESPACE_DNS,
+            UUID::NAMESPACE_OID,
+            UUID::NAMESPACE_URL,
+            UUID::NAMESPACE_X500,
+        ];
+        for ns in namespaces.iter() {
+            for name in ["", "python.org", "www.example.com", "\u{e9}t\u{e9}"].iter() {
+                for version in [Version::MD5, Version::SHA1].iter() {
+                    harness.add(Check::NameBased {
+                        version: *version,
+                        ns: *ns,
+                        name: name.to_string(),
+                    });
+                }
+            }
+        }
+        let inputs = [
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "6BA7B810-9DAD-11D1-80B4-00C04FD430C8",
+            "6ba7b8109dad11d180b400c04fd430c8",
+            "{6ba7b810-9dad-11d1-80b4-00c04fd430c8}",
+            "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c",
+            "6ba7b810-9dad-11d1-80b4-00c04fd430cg",
+            "6ba7b8109dad-11d1-80b4-00c04fd430c8",
+            "",
+        ];
+        for input in inputs.iter() {
+            harness.add(Check::Parse {
+                input: input.to_string(),
+            });
+        }
+        harness
+    }
+
+    /// Add a check to the corpus.
+    pub fn add(&mut self, check: Check) -> &mut Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Checks of the corpus.
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    /// Compare this crate against each of `implementations` that is available.
+    pub fn run(&self, implementations: &[Implementation]) -> CompatReport {
+        let mut report = CompatReport::default();
+        for implementation in implementations.iter() {
+            let answers = match implementation.is_available() {
+                true => implementation.answer(&self.checks),
+                false => Err(io::ErrorKind::NotFound.into()),
+            };
+            let answers = match answers {
+                Ok(answers) => answers,
+                Err(_) => {
+                    report.skipped.push(*implementation);
+                    continue;
+                }
+            };
+            for (check, theirs) in self.checks.iter().zip(answers) {
+                if let Some(theirs) = theirs {
+                    report.outcomes.push(Outcome {
+                        check: check.clone(),
+                        implementation: *implementation,
+                        ours: check.ours(),
+                        theirs,
+                    });
+                }
+            }
+        }
+        report
+    }
+}
+
+fn uuidgen(check: &Check) -> io::Result<Option<String>> {
+    let (flag, ns, name) = match check {
+        Check::NameBased { version, ns, name } => {
+            let flag = if *version == Version::MD5 {
+                "--md5"
+            } else {
+                "--sha1"
+            };
+            (flag, format!("{:x}", ns), name)
+        }
+        Check::Parse { .. } => return Ok(None),
+    };
+    let out = Command::new("uuidgen")
+        .args([flag, "--namespace", &ns, "--name", name])
+        .output()?;
+    Ok(Some(
+        String::from_utf8_lossy(&out.stdout).trim().to_string(),
+    ))
+}
+
+fn run_script(cmd: &mut Command, checks: &[Check]) -> io::Result<Vec<Option<String>>> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    {
+        let mut stdin = child.stdin.take().expect("piped stdin");
+        for check in checks.iter() {
+            stdin.write_all(check.line().as_bytes())?;
+        }
+    }
+    let out = child.wait_with_output()?;
+    let answers: Vec<Option<String>> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|l| match l {
+            "-" => None,
+            _ => Some(l.to_string()),
+        })
+        .collect();
+    if answers.len() != checks.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing answers",
+        ));
+    }
+    Ok(answers)
+}
@@ -0,0 +1,136 @@
+#![doc(cfg(feature = "rand_num"))]
+#![cfg(feature = "rand_num")]
+
+use std::sync::Mutex;
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+
+use crate::{Generator, UuidError, Version, UUID};
+
+/// Timestamp of the first version-7 UUID unless
+/// [`DeterministicGenerator::with_start_ms`] is called: 2022-02-22T19:22:22Z.
+const DEFAULT_START_MS: u64 = 1_645_557_742_000;
+
+// Random number generator and timestamp of the next version-7 UUID.
+#[derive(Debug)]
+struct State {
+    rng: ChaCha20Rng,
+    ms: u64,
+}
+
+/// Generator of a reproducible sequence of version-4 or 7 UUIDs, for
+/// snapshot tests and simulations; never in production.
+///
+/// The random bits are drawn from a ChaCha20 generator seeded with the seed,
+/// and version-7 timestamps come from a virtual clock advancing by one
+/// millisecond per UUID, so the same seed always gives the same UUIDs.
+///
+/// ```rust
+/// use simple_uuid::{DeterministicGenerator, Generator, Version};
+///
+/// let a = DeterministicGenerator::from_seed(42).with_version(Version::UNIX);
+/// let b = DeterministicGenerator::from_seed(42).with_version(Version::UNIX);
+/// let first = a.generate().unwrap();
+/// assert_eq!(b.generate(), Ok(first));
+/// assert!(a.generate().unwrap() > first);
+/// ```
+#[derive(Debug)]
+pub struct DeterministicGenerator {
+    version: Version,
+    state: Mutex<State>,
+}
+
+impl DeterministicGenerator {
+    /// New generator of version-4 UUIDs seeded with `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            version: Version::RAND,
+            state: Mutex::new(State {
+                rng: ChaCha20Rng::seed_from_u64(seed),
+                ms: DEFAULT_START_MS,
+            }),
+        }
+    }
+
+    /// Generate UUIDs of `version`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is neither version-4 nor 7.
+    pub fn with_version(self, version: Version) -> Self {
+        assert!(
+            matches!(version, Version::RAND | Version::UNIX),
+            "deterministic UUIDs are version-4 or 7"
+        );
+        Self { version, ..self }
+    }
+
+    /// Start the virtual clock of version-7 UUIDs at `ms` milliseconds since
+    /// the UNIX epoch.
+    pub fn with_start_ms(self, ms: u64) -> Self {
+        self.lock().ms = ms;
+        self
+    }
+
+    /// Version of the generated UUIDs.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Generator for DeterministicGenerator {
+    /// Next UUID of the sequence; fails once the virtual clock no longer fits
+    /// in the 48 bits of a version-7 timestamp.
+    fn generate(&self) -> Result<UUID, UuidError> {
+        let mut state = self.lock();
+        let State { rng, ms } = &mut *state;
+        let layout = match self.version {
+            Version::UNIX => {
+                let layout = UUID::v7_from_millis_and_rng(*ms, rng)?;
+                *ms += 1;
+                layout
+            }
+            _ => UUID::v4_from_rng(rng)?,
+        };
+        Ok(layout.to_uuid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(generator: &DeterministicGenerator) -> Vec<UUID> {
+        (0..100).map(|_| generator.generate().unwrap()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let v4 = sequence(&DeterministicGenerator::from_seed(7));
+        assert_eq!(v4, sequence(&DeterministicGenerator::from_seed(7)));
+        assert_ne!(v4, sequence(&DeterministicGenerator::from_seed(8)));
+        assert!(v4.iter().all(|uuid| uuid.get_version_num() == 4));
+
+        let unix = || DeterministicGenerator::from_seed(7).with_version(Version::UNIX);
+        let v7 = sequence(&unix());
+        assert_eq!(v7, sequence(&unix()));
+        assert!(v7.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(v7[0].timestamp_ms(), Some(DEFAULT_START_MS));
+        assert_eq!(v7[99].timestamp_ms(), Some(DEFAULT_START_MS + 99));
+
+        let late = unix().with_start_ms((1 << 48) - 1);
+        assert_eq!(late.generate().unwrap().timestamp_ms(), Some((1 << 48) - 1));
+        assert_eq!(late.generate(), Err(UuidError::Clock));
+    }
+
+    #[test]
+    #[should_panic(expected = "version-4 or 7")]
+    fn rejects_other_versions() {
+        DeterministicGenerator::from_seed(0).with_version(Version::TIME);
+    }
+}
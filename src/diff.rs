@@ -0,0 +1,107 @@
+use core::fmt;
+
+use crate::{FieldName, UUID};
+
+/// Differences between two UUIDs, see `UUID::diff`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct UuidDiff {
+    left: UUID,
+    right: UUID,
+}
+
+impl UuidDiff {
+    /// Whether both UUIDs are identical.
+    pub fn is_empty(&self) -> bool {
+        self.left == self.right
+    }
+
+    /// Layout fields that differ, with the left and right values.
+    pub fn fields(&self) -> impl Iterator<Item = (FieldName, u64, u64)> + '_ {
+        self.left
+            .components_iter()
+            .zip(self.right.components_iter())
+            .filter(|(l, r)| l.1 != r.1)
+            .map(|(l, r)| (l.0, l.1, r.1))
+    }
+
+    /// Positions of the differing bits, 0 being the most significant bit of octet 0.
+    pub fn bits(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..128u8).filter(move |i| {
+            let mask = 0x80 >> (i % 8);
+            (self.left.0[*i as usize / 8] ^ self.right.0[*i as usize / 8]) & mask != 0
+        })
+    }
+
+    /// Number of differing bits.
+    pub fn count_bits(&self) -> u32 {
+        self.left
+            .0
+            .iter()
+            .zip(self.right.0.iter())
+            .map(|(l, r)| (l ^ r).count_ones())
+            .sum()
+    }
+}
+
+impl fmt::Display for UuidDiff {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(fmt, "identical");
+        }
+        for (i, (name, l, r)) in self.fields().enumerate() {
+            if i > 0 {
+                writeln!(fmt)?;
+            }
+            let delta = r as i128 - l as i128;
+            write!(
+                fmt,
+                "{}: {:#x} -> {:#x} ({:+}, {} bits)",
+                name,
+                l,
+                r,
+                delta,
+                (l ^ r).count_ones()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl UUID {
+    /// Fields and bits in which `other` differs from this UUID.
+    pub fn diff(&self, other: &UUID) -> UuidDiff {
+        UuidDiff {
+            left: *self,
+            right: *other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_near_duplicates() {
+        let mut other = UUID::NAMESPACE_DNS;
+        other.0[9] += 1;
+
+        let diff = UUID::NAMESPACE_DNS.diff(&other);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.fields().collect::<Vec<_>>(),
+            vec![(FieldName::ClockSeqLow, 0xb4, 0xb5)]
+        );
+        assert_eq!(diff.bits().collect::<Vec<_>>(), vec![79]);
+        assert_eq!(diff.count_bits(), 1);
+        assert_eq!(diff.to_string(), "clock_seq_low: 0xb4 -> 0xb5 (+1, 1 bits)");
+    }
+
+    #[test]
+    fn diff_identical() {
+        let diff = UUID::NAMESPACE_DNS.diff(&UUID::NAMESPACE_DNS);
+        assert!(diff.is_empty());
+        assert_eq!(diff.bits().count(), 0);
+        assert_eq!(diff.to_string(), "identical");
+    }
+}
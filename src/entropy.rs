@@ -0,0 +1,203 @@
+#[cfg(feature = "rand_num")]
+use core::cell::{Cell, RefCell};
+#[cfg(all(unix, feature = "rand_num"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(unix, feature = "rand_num"))]
+use std::sync::OnceLock;
+
+#[cfg(feature = "rand_num")]
+use rand_chacha::ChaCha12Rng;
+#[cfg(feature = "rand_num")]
+use rand_core::{OsRng, RngCore, SeedableRng};
+
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+use crate::{UuidError, UUID};
+
+/// Bytes drawn from a thread's generator before it is reseeded from the
+/// operating system.
+#[cfg(feature = "rand_num")]
+const RESEED_BYTES: usize = 64 * 1024;
+
+// Random bits of the calling thread: a generator seeded from the operating
+// system, the bytes it may still produce before being reseeded, and the
+// forks of the process when it was seeded.
+#[cfg(feature = "rand_num")]
+thread_local! {
+    static RNG: RefCell<Option<(ChaCha12Rng, usize, usize)>> = const { RefCell::new(None) };
+    static SEEDS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Forks of the process, counted in the child by a `pthread_atfork` handler
+/// so that a forked child reseeds instead of repeating the UUIDs of its
+/// parent.
+#[cfg(all(unix, feature = "rand_num"))]
+static FORKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Forks of the process, `None` if they cannot be counted because the
+/// handler could not be registered.
+#[cfg(all(unix, feature = "rand_num"))]
+fn forks() -> Option<usize> {
+    static REGISTERED: OnceLock<bool> = OnceLock::new();
+
+    extern "C" fn child() {
+        FORKS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let registered = REGISTERED.get_or_init(|| {
+        // SAFETY: `child` only increments an atomic, which is async-signal
+        // safe as a handler run in the child after `fork` must be.
+        unsafe { libc::pthread_atfork(None, None, Some(child)) == 0 }
+    });
+    registered.then(|| FORKS.load(Ordering::Relaxed))
+}
+
+#[cfg(all(not(unix), feature = "rand_num"))]
+fn forks() -> Option<usize> {
+    Some(0)
+}
+
+/// Whether [`UUID::new`](crate::UUID::new), [`UUID::new_from_rand`](crate::UUID::new_from_rand)
+/// and [`UUID::v7`](crate::UUID::v7) take no lock on the calling thread.
+///
+/// With the `rand_num` feature their random bits come from a ChaCha
+/// generator owned by the calling thread, seeded from the operating system
+/// once every 64 KiB and after a fork, so a syscall is made once per 4096
+/// version-4 UUIDs instead of for each one; the time is read with atomics
+/// only. Without it, on a thread that is exiting and has lost its
+/// generator, or when forks cannot be detected, every UUID reads the
+/// operating system and this is `false`.
+/// Generators keeping state shared between threads, such as
+/// [`V7Generator`](crate::V7Generator) and [`V1Context`](crate::V1Context),
+/// still lock it.
+pub fn generation_is_lock_free() -> bool {
+    #[cfg(feature = "rand_num")]
+    return RNG.try_with(|_| ()).is_ok() && forks().is_some();
+    #[cfg(not(feature = "rand_num"))]
+    false
+}
+
+/// Fill `dest` with random bits from the generator of the calling thread.
+#[cfg(feature = "rand_num")]
+pub(crate) fn fill(dest: &mut [u8]) -> Result<(), UuidError> {
+    RNG.try_with(|rng| {
+        // A generator that may have been copied by an undetected fork would
+        // repeat the UUIDs of the other process.
+        let forks = match forks() {
+            Some(forks) => forks,
+            None => return OsRng.try_fill_bytes(dest).map_err(|_| UuidError::Entropy),
+        };
+        let mut rng = rng.borrow_mut();
+        let (rng, left) = match &mut *rng {
+            Some((rng, left, seeded_at)) if *left >= dest.len() && *seeded_at == forks => {
+                (rng, left)
+            }
+            slot => {
+                let seeded = ChaCha12Rng::from_rng(OsRng).map_err(|_| UuidError::Entropy)?;
+                SEEDS.with(|seeds| seeds.set(seeds.get() + 1));
+                let (rng, left, _) = slot.insert((seeded, RESEED_BYTES, forks));
+                (rng, left)
+            }
+        };
+        rng.fill_bytes(dest);
+        *left -= dest.len();
+        Ok(())
+    })
+    // The thread is exiting and its generator is gone.
+    .unwrap_or_else(|_| OsRng.try_fill_bytes(dest).map_err(|_| UuidError::Entropy))
+}
+
+/// Fill `dest` with random bits from the operating system, without the
+/// `rand_core` generators.
+#[cfg(all(feature = "getrandom", not(feature = "rand_num")))]
+pub(crate) fn fill(dest: &mut [u8]) -> Result<(), UuidError> {
+    getrandom::getrandom(dest).map_err(|_| UuidError::Entropy)
+}
+
+/// UUIDs filled per draw from the generator by [`fill_uuids`].
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+const BULK_UUIDS: usize = 256;
+
+/// Fill all bits of `uuids` with random bits, drawn 4 KiB at a time.
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+pub(crate) fn fill_uuids(uuids: &mut [UUID]) -> Result<(), UuidError> {
+    let mut buf = [0u8; BULK_UUIDS * 16];
+    for chunk in uuids.chunks_mut(BULK_UUIDS) {
+        let bytes = &mut buf[..chunk.len() * 16];
+        fill(bytes)?;
+        for (uuid, bytes) in chunk.iter_mut().zip(bytes.chunks_exact(16)) {
+            uuid.0.copy_from_slice(bytes);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "rand_num"))]
+mod tests {
+    use super::*;
+    use crate::UUID;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn seeds_once_per_4096_uuids() {
+        assert!(generation_is_lock_free());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    let uuids: Vec<_> = (0..10_000)
+                        .map(|_| UUID::new_v4_forever().unwrap())
+                        .collect();
+                    (uuids, SEEDS.with(Cell::get))
+                })
+            })
+            .collect();
+        let mut all = HashSet::new();
+        for handle in handles {
+            let (uuids, seeds) = handle.join().unwrap();
+            assert_eq!(seeds, 3);
+            all.extend(uuids);
+        }
+        assert_eq!(all.len(), 40_000);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn forked_child_reseeds() {
+        // Seed the generator of this thread before forking.
+        UUID::new_v4_forever().unwrap();
+
+        let mut fds = [0; 2];
+        // SAFETY: `fds` has room for the two descriptors of the pipe.
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        // SAFETY: the child only generates UUIDs, writes them to the pipe
+        // and exits without unwinding or running destructors.
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0);
+        if pid == 0 {
+            let mut child = [0u8; 48];
+            for bytes in child.chunks_exact_mut(16) {
+                bytes.copy_from_slice(UUID::new_v4_forever().unwrap_or_default().as_array());
+            }
+            // SAFETY: `child` is 48 initialized octets.
+            unsafe {
+                libc::write(fds[1], child.as_ptr().cast(), 48);
+                libc::_exit(0);
+            }
+        }
+
+        let parent: Vec<_> = (0..3).map(|_| UUID::new_v4_forever().unwrap()).collect();
+        let mut child = [0u8; 48];
+        // SAFETY: `child` has room for the 48 octets read.
+        let read = unsafe { libc::read(fds[0], child.as_mut_ptr().cast(), 48) };
+        // SAFETY: `pid` is the child forked above and the descriptors are open.
+        unsafe {
+            libc::waitpid(pid, std::ptr::null_mut(), 0);
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+        assert_eq!(read, 48);
+        for (uuid, bytes) in parent.iter().zip(child.chunks_exact(16)) {
+            assert_ne!(uuid.as_array()[..], *bytes);
+        }
+    }
+}
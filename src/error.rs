@@ -0,0 +1,68 @@
+use core::fmt;
+
+use crate::ParseError;
+
+/// Error of the fallible operations of this crate.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum UuidError {
+    /// A textual UUID could not be parsed.
+    Parse(ParseError),
+    /// The system clock is unavailable or set before the UNIX epoch.
+    Clock,
+    /// No MAC address could be found for the node field.
+    Node,
+    /// The random number generator failed.
+    Entropy,
+    /// Generator state could not be loaded or saved.
+    State,
+    /// The generator cannot produce UUIDs of this version number.
+    UnsupportedVersion(u8),
+    /// More generators would share a node than there are clock sequences.
+    TooManyShards,
+}
+
+impl fmt::Display for UuidError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UuidError::Parse(e) => write!(fmt, "parse error: {}", e),
+            UuidError::Clock => write!(fmt, "system clock is unavailable"),
+            UuidError::Node => write!(fmt, "no MAC address found"),
+            UuidError::Entropy => write!(fmt, "random number generator failed"),
+            UuidError::State => write!(fmt, "generator state store failed"),
+            UuidError::UnsupportedVersion(v) => write!(fmt, "unsupported version {}", v),
+            UuidError::TooManyShards => write!(fmt, "more shards than clock sequences"),
+        }
+    }
+}
+
+impl std::error::Error for UuidError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UuidError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for UuidError {
+    fn from(e: ParseError) -> Self {
+        UuidError::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UUID;
+
+    #[test]
+    fn parse_error_converts() {
+        let e: UuidError = UUID::parse_str("6ba7b810").unwrap_err().into();
+        assert!(matches!(e, UuidError::Parse(_)));
+        assert_eq!(
+            e.to_string(),
+            "parse error: invalid length: expected 36, found 8"
+        );
+    }
+}
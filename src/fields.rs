@@ -0,0 +1,86 @@
+//! Bit-field constants of the 16-octet UUID layout defined in `rfc4122`.
+//!
+//! Offsets are byte indices into the big-endian memory representation,
+//! and widths are in bytes.
+
+/// Byte offset of the low field of the timestamp.
+pub const FIELD_LOW_OFFSET: usize = 0;
+/// Width of the low field of the timestamp.
+pub const FIELD_LOW_WIDTH: usize = 4;
+
+/// Byte offset of the mid field of the timestamp.
+pub const FIELD_MID_OFFSET: usize = 4;
+/// Width of the mid field of the timestamp.
+pub const FIELD_MID_WIDTH: usize = 2;
+
+/// Byte offset of the high field of the timestamp multiplexed with the version.
+pub const FIELD_HIGH_AND_VERSION_OFFSET: usize = 6;
+/// Width of the high field of the timestamp multiplexed with the version.
+pub const FIELD_HIGH_AND_VERSION_WIDTH: usize = 2;
+
+/// Byte offset of the high field of the clock sequence multiplexed with the variant.
+pub const CLOCK_SEQ_HIGH_AND_RESERVED_OFFSET: usize = 8;
+/// Width of the high field of the clock sequence multiplexed with the variant.
+pub const CLOCK_SEQ_HIGH_AND_RESERVED_WIDTH: usize = 1;
+
+/// Byte offset of the low field of the clock sequence.
+pub const CLOCK_SEQ_LOW_OFFSET: usize = 9;
+/// Width of the low field of the clock sequence.
+pub const CLOCK_SEQ_LOW_WIDTH: usize = 1;
+
+/// Byte offset of the node field.
+pub const NODE_OFFSET: usize = 10;
+/// Width of the node field.
+pub const NODE_WIDTH: usize = 6;
+
+/// Byte holding the version number in its most significant 4 bits.
+pub const VERSION_BYTE: usize = FIELD_HIGH_AND_VERSION_OFFSET;
+/// Mask selecting the version number within [`VERSION_BYTE`].
+pub const VERSION_MASK: u8 = 0xf0;
+/// Shift moving the version number down to the low bits.
+pub const VERSION_SHIFT: u32 = 4;
+
+/// Byte holding the variant in its most significant bits.
+pub const VARIANT_BYTE: usize = CLOCK_SEQ_HIGH_AND_RESERVED_OFFSET;
+/// Mask selecting the two variant bits of an `rfc4122` UUID within [`VARIANT_BYTE`].
+pub const VARIANT_MASK: u8 = 0xc0;
+/// Value of the masked variant bits of an `rfc4122` UUID.
+pub const VARIANT_RFC: u8 = 0x80;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_cover_all_octets() {
+        let fields = [
+            (FIELD_LOW_OFFSET, FIELD_LOW_WIDTH),
+            (FIELD_MID_OFFSET, FIELD_MID_WIDTH),
+            (FIELD_HIGH_AND_VERSION_OFFSET, FIELD_HIGH_AND_VERSION_WIDTH),
+            (
+                CLOCK_SEQ_HIGH_AND_RESERVED_OFFSET,
+                CLOCK_SEQ_HIGH_AND_RESERVED_WIDTH,
+            ),
+            (CLOCK_SEQ_LOW_OFFSET, CLOCK_SEQ_LOW_WIDTH),
+            (NODE_OFFSET, NODE_WIDTH),
+        ];
+
+        let mut next = 0;
+        for (offset, width) in fields.iter() {
+            assert_eq!(*offset, next);
+            next += width;
+        }
+        assert_eq!(next, 16);
+    }
+
+    #[test]
+    fn masks_match_namespace() {
+        // 6ba7b810-9dad-11d1-80b4-00c04fd430c8
+        let bytes = [
+            0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4,
+            0x30, 0xc8,
+        ];
+        assert_eq!((bytes[VERSION_BYTE] & VERSION_MASK) >> VERSION_SHIFT, 1);
+        assert_eq!(bytes[VARIANT_BYTE] & VARIANT_MASK, VARIANT_RFC);
+    }
+}
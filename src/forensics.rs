@@ -0,0 +1,177 @@
+//! Reconstruction of the state of a generator from the UUIDs it produced.
+//!
+//! Given version-1, 6 or 7 UUIDs of one producer in the order they were
+//! observed, [`infer_state`] recovers the node, the clock sequence changes
+//! and the generation rate, for audits of third-party systems or of leaked
+//! IDs.
+
+use crate::fields::{VARIANT_BYTE, VARIANT_MASK, VARIANT_RFC};
+use crate::{Node, UTC_EPOCH, UUID};
+
+/// Change of the clock sequence between two consecutive version-1 or 6 UUIDs.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ClockSeqChange {
+    /// Index of the first UUID with the new clock sequence.
+    pub index: usize,
+    /// Clock sequence before the change.
+    pub from: u16,
+    /// Clock sequence after the change.
+    pub to: u16,
+    /// Whether the timestamp went backwards, the usual cause of a change.
+    pub clock_regressed: bool,
+}
+
+/// State of a generator inferred by [`infer_state`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InferredState {
+    /// Distinct nodes of the version-1 and 6 UUIDs, in order of appearance.
+    pub nodes: Vec<Node>,
+    /// Clock sequence changes, in order.
+    pub clock_seq_changes: Vec<ClockSeqChange>,
+    /// Consecutive UUIDs whose timestamp goes backwards.
+    pub regressions: usize,
+    /// Earliest and latest timestamps, in 100-ns ticks since the UNIX epoch.
+    pub span: Option<(u64, u64)>,
+    /// UUIDs per second over the span, if longer than zero.
+    pub rate: Option<f64>,
+    /// UUIDs that are not of version 1, 6 or 7 of the `rfc4122` variant.
+    pub ignored: usize,
+}
+
+impl InferredState {
+    /// The node of the producer, if all its version-1 and 6 UUIDs share one.
+    pub fn node(&self) -> Option<Node> {
+        match self.nodes.as_slice() {
+            [node] => Some(*node),
+            _ => None,
+        }
+    }
+}
+
+/// Infer the state of the generator of `uuids`, given in generation order.
+pub fn infer_state(uuids: &[UUID]) -> InferredState {
+    let mut state = InferredState::default();
+    let mut last: Option<(u64, Option<u16>)> = None;
+    let mut count = 0;
+    for (index, uuid) in uuids.iter().enumerate() {
+        let (ticks, clock_seq) = match observe(uuid) {
+            Some(observed) => observed,
+            None => {
+                state.ignored += 1;
+                continue;
+            }
+        };
+        count += 1;
+        if clock_seq.is_some() {
+            let node = uuid.decode().get_node();
+            if !state.nodes.contains(&node) {
+                state.nodes.push(node);
+            }
+        }
+
+        if let Some((last_ticks, last_seq)) = last {
+            let regressed = ticks < last_ticks;
+            state.regressions += regressed as usize;
+            if let (Some(from), Some(to)) = (last_seq, clock_seq) {
+                if from != to {
+                    state.clock_seq_changes.push(ClockSeqChange {
+                        index,
+                        from,
+                        to,
+                        clock_regressed: regressed,
+                    });
+                }
+            }
+        }
+        last = Some((ticks, clock_seq));
+        state.span = Some(match state.span {
+            Some((min, max)) => (min.min(ticks), max.max(ticks)),
+            None => (ticks, ticks),
+        });
+    }
+
+    state.rate = match state.span {
+        Some((min, max)) if max > min => Some((count - 1) as f64 * 1e7 / (max - min) as f64),
+        _ => None,
+    };
+    state
+}
+
+// Timestamp in 100-ns ticks since the UNIX epoch, and the clock sequence of
+// version-1 and 6 UUIDs.
+fn observe(uuid: &UUID) -> Option<(u64, Option<u16>)> {
+    if uuid.0[VARIANT_BYTE] & VARIANT_MASK != VARIANT_RFC {
+        return None;
+    }
+    let layout = uuid.decode();
+    let gregorian = match uuid.get_version_num() {
+        1 => {
+            u64::from(layout.field_high_and_version & 0xfff) << 48
+                | u64::from(layout.field_mid) << 32
+                | u64::from(layout.field_low)
+        }
+        6 => uuid.v6_view()?.timestamp(),
+        7 => return Some((uuid.v7_view()?.unix_ts_ms * 10_000, None)),
+        _ => return None,
+    };
+    Some((
+        gregorian.saturating_sub(UTC_EPOCH),
+        Some(layout.get_clock_seq()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Version-1 UUID of node 1:2:3:4:5:6 at `ticks` past the UNIX epoch.
+    fn v1(ticks: u64, clock_seq: u16) -> UUID {
+        let t = ticks + UTC_EPOCH;
+        UUID::from_u128(
+            u128::from(t & 0xffff_ffff) << 96
+                | u128::from(t >> 32 & 0xffff) << 80
+                | 0x1 << 76
+                | u128::from(t >> 48 & 0xfff) << 64
+                | u128::from(0x8000 | clock_seq) << 48
+                | 0x0102_0304_0506,
+        )
+    }
+
+    #[test]
+    fn v1_sequence() {
+        let uuids = [
+            v1(10_000_000, 7),
+            v1(15_000_000, 7),
+            v1(12_000_000, 8),
+            UUID::from_u128(0x936d_a01f_9abd_4d9d_80c7_02af_85c8_22a8),
+            UUID::MAX,
+            v1(20_000_000, 8),
+        ];
+        let state = infer_state(&uuids);
+        assert_eq!(state.node(), Some(Node([1, 2, 3, 4, 5, 6])));
+        assert_eq!(
+            state.clock_seq_changes,
+            vec![ClockSeqChange {
+                index: 2,
+                from: 7,
+                to: 8,
+                clock_regressed: true
+            }]
+        );
+        assert_eq!(state.regressions, 1);
+        assert_eq!(state.span, Some((10_000_000, 20_000_000)));
+        assert_eq!(state.rate, Some(3.0));
+        assert_eq!(state.ignored, 2);
+    }
+
+    #[test]
+    fn v7_rate() {
+        let uuids: Vec<UUID> = (0..=100u128)
+            .map(|i| UUID::from_u128((1_000 + i * 10) << 80 | 0x7 << 76 | 0b10 << 62))
+            .collect();
+        let state = infer_state(&uuids);
+        assert!(state.nodes.is_empty());
+        assert_eq!(state.rate, Some(100.0));
+        assert_eq!(infer_state(&[]).span, None);
+    }
+}
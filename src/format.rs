@@ -0,0 +1,347 @@
+use crate::{ParseError, UUID};
+
+/// Textual encoding a UUID string appears to be written in.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Format {
+    /// The canonical `8-4-4-4-12` form.
+    Hyphenated,
+    /// 32 hex digits without separators.
+    Simple,
+    /// The hyphenated form wrapped in curly braces.
+    Braced,
+    /// The hyphenated form prefixed with `urn:uuid:`.
+    Urn,
+    /// The `8_4_4_4_12` form, selectable by double-click.
+    Selectable,
+    /// 26 characters of Crockford's base32 alphabet.
+    Base32,
+    /// 22 characters of base64 (standard or URL-safe), optionally padded to 24.
+    Base64,
+    /// Eight proquints separated by hyphens.
+    Proquint,
+    /// None of the known encodings.
+    Unknown,
+}
+
+const URN_PREFIX: &str = "urn:uuid:";
+
+const PROQUINT_CONSONANTS: &[u8] = b"bdfghjklmnprstvz";
+
+const PROQUINT_VOWELS: &[u8] = b"aiou";
+
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Length of the longest encoding written by `UUID::encode_into`.
+pub(crate) const MAX_ENCODED_LEN: usize = 45;
+
+impl UUID {
+    /// Guess the textual encoding of `s` without decoding it.
+    pub fn detect_format(s: &str) -> Format {
+        let b = s.as_bytes();
+        match b.len() {
+            32 if b.iter().all(u8::is_ascii_hexdigit) => Format::Simple,
+            36 if is_grouped(b, b'-') => Format::Hyphenated,
+            36 if is_grouped(b, b'_') => Format::Selectable,
+            38 if b[0] == b'{' && b[37] == b'}' && is_grouped(&b[1..37], b'-') => Format::Braced,
+            45 if b[..9].eq_ignore_ascii_case(URN_PREFIX.as_bytes())
+                && is_grouped(&b[9..], b'-') =>
+            {
+                Format::Urn
+            }
+            26 if b.iter().all(|c| is_base32(*c)) => Format::Base32,
+            22 if b.iter().all(|c| is_base64(*c)) => Format::Base64,
+            24 if b[22..] == *b"==" && b[..22].iter().all(|c| is_base64(*c)) => Format::Base64,
+            47 if is_proquint(b) => Format::Proquint,
+            _ => Format::Unknown,
+        }
+    }
+
+    /// Write the lowercase hex encoding of the UUID in `format` into `buf`,
+    /// returning the number of bytes written, or `None` if the format
+    /// cannot be encoded.
+    pub(crate) fn encode_into(&self, format: Format, buf: &mut [u8]) -> Option<usize> {
+        self.encode_case_into(format, false, buf)
+    }
+
+    /// Same as `encode_into`, with uppercase hex digits if `upper` is set.
+    pub(crate) fn encode_case_into(
+        &self,
+        format: Format,
+        upper: bool,
+        buf: &mut [u8],
+    ) -> Option<usize> {
+        let hex = if upper { HEX_UPPER } else { HEX_LOWER };
+        let (prefix, sep, suffix): (&[u8], Option<u8>, &[u8]) = match format {
+            Format::Simple => (b"", None, b""),
+            Format::Hyphenated => (b"", Some(b'-'), b""),
+            Format::Braced => (b"{", Some(b'-'), b"}"),
+            Format::Urn => (URN_PREFIX.as_bytes(), Some(b'-'), b""),
+            Format::Selectable => (b"", Some(b'_'), b""),
+            _ => return None,
+        };
+
+        buf[..prefix.len()].copy_from_slice(prefix);
+        let mut n = prefix.len();
+        for (i, byte) in self.0.iter().enumerate() {
+            if let (Some(sep), 4 | 6 | 8 | 10) = (sep, i) {
+                buf[n] = sep;
+                n += 1;
+            }
+            buf[n] = hex[(byte >> 4) as usize];
+            buf[n + 1] = hex[(byte & 0xf) as usize];
+            n += 2;
+        }
+        buf[n..n + suffix.len()].copy_from_slice(suffix);
+        Some(n + suffix.len())
+    }
+
+    /// The `8_4_4_4_12` form, with underscores so that a double-click
+    /// selects the whole UUID in browsers and terminals.
+    pub fn to_selectable(&self) -> String {
+        self.encoded_string(Format::Selectable, false)
+    }
+
+    /// The hyphenated form with lowercase ASCII hex digits, whatever the locale.
+    pub fn to_lower_string(&self) -> String {
+        self.encoded_string(Format::Hyphenated, false)
+    }
+
+    /// The hyphenated form with uppercase ASCII hex digits, whatever the locale.
+    pub fn to_upper_string(&self) -> String {
+        self.encoded_string(Format::Hyphenated, true)
+    }
+
+    /// Whether two textual IDs are equal, ignoring the case of ASCII letters only.
+    pub fn eq_ignore_case(a: &str, b: &str) -> bool {
+        a.eq_ignore_ascii_case(b)
+    }
+
+    // Encodes in a single pass into a string allocated once.
+    fn encoded_string(&self, format: Format, upper: bool) -> String {
+        let mut buf = [0u8; MAX_ENCODED_LEN];
+        let n = self.encode_case_into(format, upper, &mut buf).unwrap();
+        let mut s = String::with_capacity(n);
+        s.push_str(core::str::from_utf8(&buf[..n]).unwrap());
+        s
+    }
+
+    /// Whether `s` is this UUID in the simple, hyphenated, braced, urn or
+    /// selectable form, in any case, without allocating.
+    pub fn eq_str(&self, s: &str) -> bool {
+        decode_hex_formats(s).or_else(|| UUID::from_selectable(s)) == Some(*self)
+    }
+
+    /// Decode the `8_4_4_4_12` form written by `to_selectable`, in any case.
+    pub fn from_selectable(s: &str) -> Option<UUID> {
+        decode_groups(s, 0, s.len(), Some(b'_')).ok()
+    }
+}
+
+/// Decode the canonical `8-4-4-4-12` form, in any case.
+pub(crate) fn decode_hyphenated(s: &str) -> Option<UUID> {
+    decode_groups(s, 0, s.len(), Some(b'-')).ok()
+}
+
+/// Decode the simple, hyphenated, braced or urn form, in any case.
+pub(crate) fn decode_hex_formats(s: &str) -> Option<UUID> {
+    decode_formats(s).ok()
+}
+
+/// Decode the simple, hyphenated, braced or urn form, in any case, failing
+/// at the first offending character.
+pub(crate) fn decode_formats(s: &str) -> Result<UUID, ParseError> {
+    let b = s.as_bytes();
+    if b.len() >= 9 && b[..9].eq_ignore_ascii_case(URN_PREFIX.as_bytes()) {
+        return decode_groups(s, 9, b.len(), Some(b'-'));
+    }
+    if b.first() == Some(&b'{') {
+        return match s.chars().last() {
+            Some('}') if b.len() > 1 => decode_groups(s, 1, b.len() - 1, Some(b'-')),
+            found => Err(ParseError::InvalidCharacter {
+                index: b.len() - found.map_or(0, char::len_utf8),
+                found: found.unwrap_or('{'),
+            }),
+        };
+    }
+    let sep = if b.len() == 32 { None } else { Some(b'-') };
+    decode_groups(s, 0, b.len(), sep)
+}
+
+/// Decodes `s[start..end]` as 32 hex digits, in `8-4-4-4-12` groups
+/// separated by `sep` if set, reporting indices into the whole of `s`.
+pub(crate) fn decode_groups(
+    s: &str,
+    start: usize,
+    end: usize,
+    sep: Option<u8>,
+) -> Result<UUID, ParseError> {
+    let expected = if sep.is_some() { 36 } else { 32 };
+    if end - start != expected {
+        return Err(ParseError::InvalidLength {
+            expected,
+            found: end - start,
+        });
+    }
+
+    let mut bytes = [0u8; 16];
+    let mut digit = 0;
+    for (index, c) in s.as_bytes()[start..end].iter().enumerate() {
+        let found = || s[start + index..].chars().next().unwrap_or('\u{fffd}');
+        if let (Some(sep), 8 | 13 | 18 | 23) = (sep, index) {
+            if *c != sep {
+                return Err(ParseError::InvalidSeparator {
+                    index: start + index,
+                    found: found(),
+                });
+            }
+            continue;
+        }
+        let v = hex_value(*c).ok_or_else(|| ParseError::InvalidCharacter {
+            index: start + index,
+            found: found(),
+        })?;
+        bytes[digit / 2] |= v << (4 * (1 - digit % 2));
+        digit += 1;
+    }
+    Ok(UUID(bytes))
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn is_grouped(b: &[u8], sep: u8) -> bool {
+    b.iter().enumerate().all(|(i, c)| match i {
+        8 | 13 | 18 | 23 => *c == sep,
+        _ => c.is_ascii_hexdigit(),
+    })
+}
+
+fn is_base32(c: u8) -> bool {
+    c.is_ascii_alphanumeric() && !matches!(c.to_ascii_uppercase(), b'I' | b'L' | b'O' | b'U')
+}
+
+fn is_base64(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'+' | b'/' | b'-' | b'_')
+}
+
+fn is_proquint(b: &[u8]) -> bool {
+    b.chunks(6).all(|q| {
+        q[..5].iter().enumerate().all(|(i, c)| match i {
+            1 | 3 => PROQUINT_VOWELS.contains(c),
+            _ => PROQUINT_CONSONANTS.contains(c),
+        }) && (q.len() == 5 || q[5] == b'-')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_textual_formats() {
+        let cases = [
+            ("6ba7b810-9dad-11d1-80b4-00c04fd430c8", Format::Hyphenated),
+            ("6BA7B8109DAD11D180B400C04FD430C8", Format::Simple),
+            ("{6ba7b810-9dad-11d1-80b4-00c04fd430c8}", Format::Braced),
+            ("URN:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8", Format::Urn),
+            ("6ba7b810_9dad_11d1_80b4_00c04fd430c8", Format::Selectable),
+            ("3bmywh0qdd278r1d00r17x8c68", Format::Base32),
+            ("a6e4EJ2tEdGAtADAT9QwyA", Format::Base64),
+            ("a6e4EJ2tEdGAtADAT9QwyA==", Format::Base64),
+            (
+                "lusab-babad-gutih-tugad-gutuk-bisog-mudof-sakat",
+                Format::Proquint,
+            ),
+        ];
+
+        for (s, f) in cases.iter() {
+            assert_eq!(UUID::detect_format(s), *f, "{}", s);
+        }
+    }
+
+    #[test]
+    fn encode_into_buffer() {
+        let mut buf = [0u8; MAX_ENCODED_LEN];
+        let n = UUID::NAMESPACE_DNS
+            .encode_into(Format::Urn, &mut buf)
+            .unwrap();
+        assert_eq!(&buf[..n], b"urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+
+        let n = UUID::NAMESPACE_DNS
+            .encode_into(Format::Simple, &mut buf)
+            .unwrap();
+        assert_eq!(&buf[..n], b"6ba7b8109dad11d180b400c04fd430c8");
+
+        assert_eq!(
+            UUID::NAMESPACE_DNS.encode_into(Format::Base32, &mut buf),
+            None
+        );
+    }
+
+    #[test]
+    fn selectable_round_trip() {
+        let s = UUID::NAMESPACE_URL.to_selectable();
+        assert_eq!(s, "6ba7b811_9dad_11d1_80b4_00c04fd430c8");
+        assert_eq!(UUID::from_selectable(&s), Some(UUID::NAMESPACE_URL));
+        assert_eq!(
+            UUID::from_selectable("6BA7B811_9DAD_11D1_80B4_00C04FD430C8"),
+            Some(UUID::NAMESPACE_URL)
+        );
+        assert_eq!(
+            UUID::from_selectable("6ba7b811-9dad-11d1-80b4-00c04fd430c8"),
+            None
+        );
+    }
+
+    #[test]
+    fn ascii_case_strings() {
+        let uuid = UUID::NAMESPACE_X500;
+        assert_eq!(
+            uuid.to_lower_string(),
+            "6ba7b814-9dad-11d1-80b4-00c04fd430c8"
+        );
+        assert_eq!(
+            uuid.to_upper_string(),
+            "6BA7B814-9DAD-11D1-80B4-00C04FD430C8"
+        );
+        assert!(UUID::eq_ignore_case(
+            &uuid.to_lower_string(),
+            &uuid.to_upper_string()
+        ));
+        assert!(!UUID::eq_ignore_case("6ba7b814", "6ba7b815"));
+        assert!(!UUID::eq_ignore_case("\u{131}", "I"));
+    }
+
+    #[test]
+    fn eq_str_common_formats() {
+        let uuid = UUID::NAMESPACE_OID;
+        assert!(uuid.eq_str("6ba7b812-9dad-11d1-80b4-00c04fd430c8"));
+        assert!(uuid.eq_str("6BA7B8129DAD11D180B400C04FD430C8"));
+        assert!(uuid.eq_str("{6ba7b812-9dad-11d1-80b4-00c04fd430c8}"));
+        assert!(uuid.eq_str("urn:uuid:6ba7b812-9dad-11d1-80b4-00c04fd430c8"));
+        assert!(uuid.eq_str("6ba7b812_9dad_11d1_80b4_00c04fd430c8"));
+        assert!(!uuid.eq_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8"));
+        assert!(!uuid.eq_str("6ba7b812"));
+    }
+
+    #[test]
+    fn detect_unknown_format() {
+        assert_eq!(UUID::detect_format(""), Format::Unknown);
+        assert_eq!(
+            UUID::detect_format("6ba7b810-9dad-11d1-80b4_00c04fd430c8"),
+            Format::Unknown
+        );
+        assert_eq!(
+            UUID::detect_format("{6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+            Format::Unknown
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Bounds for deletion scans over stores keyed by version-7 UUIDs.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::fields::{VARIANT_BYTE, VARIANT_RFC, VERSION_BYTE};
+use crate::UUID;
+
+/// Largest millisecond timestamp a version-7 UUID can hold.
+const MAX_MS: u64 = (1 << 48) - 1;
+
+/// Smallest version-7 UUID generated at `cutoff`.
+///
+/// Every version-7 UUID generated strictly before `cutoff` sorts below it, so
+/// a scan of the keys `< expired_before(cutoff)` finds the expired entries.
+/// Times before the UNIX epoch clamp to it.
+pub fn expired_before(cutoff: SystemTime) -> UUID {
+    let ms = cutoff
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis().min(u128::from(MAX_MS)) as u64);
+    let mut bytes = [0u8; 16];
+    bytes[..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+    bytes[VERSION_BYTE] = 0x70;
+    bytes[VARIANT_BYTE] = VARIANT_RFC;
+    UUID(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn bound_sorts_between_expired_and_live() {
+        let cutoff = UNIX_EPOCH + Duration::from_millis(1_645_557_742_000);
+        let bound = expired_before(cutoff);
+        assert_eq!(bound.timestamp_ms(), Some(1_645_557_742_000));
+
+        // Last possible UUID of the previous millisecond, first of the cutoff one.
+        let mut expired = [0xff; 16];
+        expired[..6].copy_from_slice(&1_645_557_741_999u64.to_be_bytes()[2..]);
+        expired[6] = 0x7f;
+        expired[8] = 0xbf;
+        assert!(UUID(expired) < bound);
+        assert!(bound <= bound);
+    }
+
+    #[test]
+    fn bound_clamps() {
+        assert_eq!(
+            expired_before(UNIX_EPOCH - Duration::from_secs(1)).timestamp_ms(),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+use crate::{GeneratorMetrics, UuidError, UUID};
+
+/// A source of UUIDs that can be shared between threads.
+pub trait Generator {
+    /// Next UUID of the generator.
+    fn generate(&self) -> Result<UUID, UuidError>;
+
+    /// Counters the generator updates itself, such as counter rollovers,
+    /// for a [`SharedGenerator`](crate::SharedGenerator) to export; none
+    /// by default.
+    fn shared_metrics(&self) -> Option<Arc<GeneratorMetrics>> {
+        None
+    }
+}
+
+impl<F: Fn() -> Result<UUID, UuidError>> Generator for F {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        self()
+    }
+}
+
+impl<G: Generator + ?Sized> Generator for Arc<G> {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        (**self).generate()
+    }
+
+    fn shared_metrics(&self) -> Option<Arc<GeneratorMetrics>> {
+        (**self).shared_metrics()
+    }
+}
+
+/// Keeps one generator per tenant, created on first use.
+///
+/// Each tenant's counters are isolated, so exhausting one tenant's counter
+/// never affects the ordering of another tenant's IDs.
+pub struct GeneratorRouter<K, G> {
+    tenants: RwLock<HashMap<K, Arc<G>>>,
+    factory: Box<dyn Fn(&K) -> G + Send + Sync>,
+}
+
+impl<K: Eq + Hash + Clone, G: Generator> GeneratorRouter<K, G> {
+    /// New router building each tenant's generator with `factory`.
+    pub fn new<F: Fn(&K) -> G + Send + Sync + 'static>(factory: F) -> Self {
+        Self {
+            tenants: RwLock::new(HashMap::new()),
+            factory: Box::new(factory),
+        }
+    }
+
+    /// Generator of `tenant`, created if needed.
+    pub fn for_tenant(&self, tenant: &K) -> Arc<G> {
+        if let Some(g) = self.read().get(tenant) {
+            return Arc::clone(g);
+        }
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        let g = tenants
+            .entry(tenant.clone())
+            .or_insert_with(|| Arc::new((self.factory)(tenant)));
+        Arc::clone(g)
+    }
+
+    /// Drop the generator of `tenant`, returning it if there was one.
+    pub fn remove(&self, tenant: &K) -> Option<Arc<G>> {
+        self.tenants
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(tenant)
+    }
+
+    /// Number of tenants with a generator.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Whether no tenant has a generator yet.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<K, Arc<G>>> {
+        self.tenants.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::thread;
+
+    struct Counter(u64, AtomicU64);
+
+    impl Generator for Counter {
+        fn generate(&self) -> Result<UUID, UuidError> {
+            let n = self.1.fetch_add(1, Ordering::SeqCst);
+            Ok(UUID(
+                (u128::from(self.0) << 64 | u128::from(n)).to_be_bytes(),
+            ))
+        }
+    }
+
+    #[test]
+    fn generators_are_per_tenant() {
+        let router = GeneratorRouter::new(|tenant: &u64| Counter(*tenant, AtomicU64::new(0)));
+        assert!(router.is_empty());
+
+        let a = router.for_tenant(&1).generate().unwrap();
+        let b = router.for_tenant(&1).generate().unwrap();
+        let c = router.for_tenant(&2).generate().unwrap();
+        assert!(a < b);
+        assert_eq!(c.0[15], 0);
+        assert_eq!(router.len(), 2);
+
+        assert!(router.remove(&1).is_some());
+        assert_eq!(router.for_tenant(&1).generate().unwrap(), a);
+    }
+
+    #[test]
+    fn router_is_shared_between_threads() {
+        let router = Arc::new(GeneratorRouter::new(|tenant: &String| {
+            Counter(tenant.len() as u64, AtomicU64::new(0))
+        }));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let router = Arc::clone(&router);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        router.for_tenant(&"acme".to_string()).generate().unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        let next = router.for_tenant(&"acme".to_string()).generate().unwrap();
+        assert_eq!(next.0[15], 144);
+    }
+
+    #[test]
+    fn closures_are_generators() {
+        let g = || Ok(UUID::NAMESPACE_DNS);
+        assert_eq!(g.generate(), Ok(UUID::NAMESPACE_DNS));
+    }
+}
@@ -0,0 +1,99 @@
+//! GraphQL `UUID` scalar for `async-graphql` and `juniper` schemas.
+//!
+//! Values are serialized in the hyphenated form; inputs are parsed in the
+//! simple, hyphenated, braced or urn form, with the parse error reported to
+//! the client.
+#![cfg(any(feature = "async-graphql", feature = "juniper"))]
+
+#[cfg(feature = "async-graphql")]
+use crate::ParseOptions;
+use crate::UUID;
+
+#[cfg(feature = "async-graphql")]
+#[doc(cfg(feature = "async-graphql"))]
+#[async_graphql::Scalar(name = "UUID")]
+impl async_graphql::ScalarType for UUID {
+    fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
+        match &value {
+            async_graphql::Value::String(s) => {
+                UUID::parse_with(s, &ParseOptions::new()).map_err(|e| {
+                    async_graphql::InputValueError::custom(format!("invalid UUID: {}", e))
+                })
+            }
+            _ => Err(async_graphql::InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> async_graphql::Value {
+        async_graphql::Value::String(self.to_string())
+    }
+}
+
+#[cfg(feature = "juniper")]
+#[juniper::graphql_scalar(
+    name = "UUID",
+    with = juniper_scalar,
+    parse_token(String),
+    description = "A universally unique identifier, in the hyphenated form."
+)]
+#[allow(dead_code)] // only carries the scalar definition
+type JuniperUuid = UUID;
+
+#[cfg(feature = "juniper")]
+mod juniper_scalar {
+    use juniper::{InputValue, ScalarValue, Value};
+
+    use crate::{ParseOptions, UUID};
+
+    pub(super) fn to_output<S: ScalarValue>(v: &UUID) -> Value<S> {
+        Value::scalar(v.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<UUID, String> {
+        let s = v
+            .as_string_value()
+            .ok_or_else(|| format!("expected a UUID string, found {}", v))?;
+        UUID::parse_with(s, &ParseOptions::new()).map_err(|e| format!("invalid UUID: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "async-graphql")]
+    #[test]
+    fn async_graphql_scalar() {
+        use async_graphql::{ScalarType, Value};
+
+        let value = UUID::NAMESPACE_DNS.to_value();
+        assert_eq!(
+            value,
+            Value::String("6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string())
+        );
+        assert_eq!(
+            <UUID as ScalarType>::parse(value).ok(),
+            Some(UUID::NAMESPACE_DNS)
+        );
+        let err = <UUID as ScalarType>::parse(Value::String("nope".to_string())).unwrap_err();
+        assert!(err
+            .into_server_error(Default::default())
+            .message
+            .contains("invalid UUID"));
+        assert!(<UUID as ScalarType>::parse(Value::Null).is_err());
+    }
+
+    #[cfg(feature = "juniper")]
+    #[test]
+    fn juniper_scalar() {
+        use juniper::{DefaultScalarValue, FromInputValue, InputValue, ToInputValue};
+
+        let input: InputValue<DefaultScalarValue> = UUID::NAMESPACE_URL.to_input_value();
+        assert_eq!(UUID::from_input_value(&input), Ok(UUID::NAMESPACE_URL));
+        let bad = InputValue::<DefaultScalarValue>::scalar("6ba7b811".to_string());
+        assert!(UUID::from_input_value(&bad)
+            .unwrap_err()
+            .message()
+            .contains("invalid UUID"));
+    }
+}
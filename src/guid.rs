@@ -0,0 +1,345 @@
+use core::fmt;
+
+use crate::{ParseError, UUID};
+
+/// GUID with the layout of the Win32 and UEFI `GUID` structure.
+///
+/// The fields are native integers, so in memory the first three are
+/// little-endian on the platforms these structures come from; convert to
+/// and from [`UUID`] with `From` and read or write the 16 octets of PE/COFF
+/// and UEFI data with [`Guid::from_bytes_le`] and [`Guid::to_bytes_le`].
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Clone, Copy)]
+pub struct Guid {
+    /// The first 4 octets.
+    pub data1: u32,
+    /// The next 2 octets.
+    pub data2: u16,
+    /// The next 2 octets, with the version.
+    pub data3: u16,
+    /// The last 8 octets, with the variant.
+    pub data4: [u8; 8],
+}
+
+const _: () = assert!(core::mem::size_of::<Guid>() == 16 && core::mem::align_of::<Guid>() == 4);
+
+impl Guid {
+    /// GUID of `uuid`.
+    pub const fn from_uuid(uuid: UUID) -> Guid {
+        let b = uuid.0;
+        Guid {
+            data1: u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            data2: u16::from_be_bytes([b[4], b[5]]),
+            data3: u16::from_be_bytes([b[6], b[7]]),
+            data4: [b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]],
+        }
+    }
+
+    /// UUID of the GUID.
+    pub const fn to_uuid(&self) -> UUID {
+        UUID::from_fields(self.data1, self.data2, self.data3, &self.data4)
+    }
+
+    /// GUID of its 16 octets stored as in Windows and UEFI, with the first
+    /// three fields little-endian.
+    pub const fn from_bytes_le(b: [u8; 16]) -> Guid {
+        Guid {
+            data1: u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            data2: u16::from_le_bytes([b[4], b[5]]),
+            data3: u16::from_le_bytes([b[6], b[7]]),
+            data4: [b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]],
+        }
+    }
+
+    /// The 16 octets of the GUID stored as in Windows and UEFI, the inverse
+    /// of `from_bytes_le`.
+    pub const fn to_bytes_le(&self) -> [u8; 16] {
+        let [a0, a1, a2, a3] = self.data1.to_le_bytes();
+        let [b0, b1] = self.data2.to_le_bytes();
+        let [c0, c1] = self.data3.to_le_bytes();
+        let d = self.data4;
+        [
+            a0, a1, a2, a3, b0, b1, c0, c1, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7],
+        ]
+    }
+}
+
+impl From<UUID> for Guid {
+    fn from(uuid: UUID) -> Self {
+        Guid::from_uuid(uuid)
+    }
+}
+
+impl From<Guid> for UUID {
+    fn from(guid: Guid) -> Self {
+        guid.to_uuid()
+    }
+}
+
+/// The hyphenated form of the UUID.
+impl fmt::Display for Guid {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.to_uuid(), fmt)
+    }
+}
+
+/// Parse a GUID definition of a C header, returning its name and UUID.
+///
+/// Both `DEFINE_GUID(name, 0x..., 0x..., 0x..., 0x.., ...)` and brace
+/// initializers such as `static const GUID name = { 0x..., 0x..., 0x...,
+/// { 0x.., ... } };` are read; the initializer may be given alone, without
+/// a name. Fields are hexadecimal or decimal, with optional `U` and `L`
+/// suffixes, and a trailing `;` is ignored.
+///
+/// ```rust
+/// use simple_uuid::{parse_c_define_guid, UUID};
+///
+/// let header = "DEFINE_GUID(GUID_DEVCLASS_NET, 0x4d36e972L, 0xe325, 0x11ce, \
+///               0xbf, 0xc1, 0x08, 0x00, 0x2b, 0xe1, 0x03, 0x18);";
+/// let (name, uuid) = parse_c_define_guid(header).unwrap();
+/// assert_eq!(name, Some("GUID_DEVCLASS_NET"));
+/// assert_eq!(uuid.to_string(), "4d36e972-e325-11ce-bfc1-08002be10318");
+/// ```
+pub fn parse_c_define_guid(s: &str) -> Result<(Option<&str>, UUID), ParseError> {
+    let end = s.trim_end().trim_end_matches(';').trim_end().len();
+    let start = end - s[..end].trim_start().len();
+
+    if s[start..end].starts_with("DEFINE_GUID") {
+        let open = expect(s, start + "DEFINE_GUID".len(), '(')?;
+        if !s[..end].ends_with(')') {
+            return Err(unexpected(s, last_char(s, end)));
+        }
+        let mut fields = split_fields(s, open, end - 1);
+        let (index, name) = fields.next().unwrap_or((end - 1, ""));
+        let name = identifier(s, index, name)?;
+        let uuid = guid_fields(fields, end - 1)?;
+        return Ok((Some(name), uuid));
+    }
+
+    let open = match s[start..end].find(['{', '=']) {
+        Some(i) if s[start + i..].starts_with('=') => {
+            let value = &s[start + i + 1..end];
+            let brace = value
+                .find('{')
+                .unwrap_or(value.len() - value.trim_start().len());
+            start + i + 1 + brace
+        }
+        found => start + found.unwrap_or(0),
+    };
+    let name = match s[start..open].find('=') {
+        Some(eq) => {
+            let decl = s[start..start + eq].trim_end();
+            let word = decl.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+            Some(identifier(s, start + word, &decl[word..])?)
+        }
+        None if s[start..open].trim().is_empty() => None,
+        None => return Err(unexpected(s, start)),
+    };
+    expect(s, open, '{')?;
+    if !s[..end].ends_with('}') {
+        return Err(unexpected(s, last_char(s, end)));
+    }
+
+    // The 8 octets may be grouped in their own braces.
+    let mut fields = split_fields(s, open + 1, end - 1).collect::<Vec<_>>();
+    if let Some(first) = fields.iter().position(|(_, f)| f.starts_with('{')) {
+        let last = fields.len() - 1;
+        let (index, field) = &mut fields[first];
+        *field = field[1..].trim_start();
+        *index += 1;
+        match fields[last].1.strip_suffix('}') {
+            Some(field) if first == 3 => fields[last].1 = field.trim_end(),
+            _ => return Err(unexpected(s, fields[last].0)),
+        }
+    }
+    let uuid = guid_fields(fields.into_iter(), end - 1)?;
+    Ok((name, uuid))
+}
+
+// UUID of the 11 fields of a GUID, `end` being where a missing field would be.
+fn guid_fields<'a>(
+    mut fields: impl Iterator<Item = (usize, &'a str)>,
+    end: usize,
+) -> Result<UUID, ParseError> {
+    let mut field = |max: u64| -> Result<u64, ParseError> {
+        let (index, field) = fields
+            .next()
+            .ok_or(ParseError::InvalidField { index: end })?;
+        match number(field) {
+            Some(value) if value <= max => Ok(value),
+            _ => Err(ParseError::InvalidField { index }),
+        }
+    };
+    let d1 = field(u64::from(u32::MAX))? as u32;
+    let d2 = field(u64::from(u16::MAX))? as u16;
+    let d3 = field(u64::from(u16::MAX))? as u16;
+    let mut d4 = [0u8; 8];
+    for b in d4.iter_mut() {
+        *b = field(u64::from(u8::MAX))? as u8;
+    }
+    match fields.next() {
+        Some((index, _)) => Err(ParseError::InvalidField { index }),
+        None => Ok(UUID::from_fields(d1, d2, d3, &d4)),
+    }
+}
+
+// Comma-separated fields of `s[start..end]`, trimmed, with their indices;
+// a trailing comma is allowed.
+fn split_fields(s: &str, start: usize, end: usize) -> impl Iterator<Item = (usize, &str)> {
+    let region = &s[start..end];
+    let mut fields = Vec::new();
+    let mut from = 0;
+    for (i, part) in region.split(',').enumerate() {
+        let trimmed = part.trim();
+        let index = start + from + (part.len() - part.trim_start().len());
+        from += part.len() + 1;
+        if !(trimmed.is_empty() && from > region.len() && i > 0) {
+            fields.push((index, trimmed));
+        }
+    }
+    fields.into_iter()
+}
+
+// Value of a C integer literal.
+fn number(field: &str) -> Option<u64> {
+    let digits = field.trim_end_matches(['u', 'U', 'l', 'L']);
+    match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => digits.parse().ok(),
+    }
+}
+
+fn identifier<'a>(s: &str, index: usize, name: &'a str) -> Result<&'a str, ParseError> {
+    let bad = name
+        .char_indices()
+        .find(|&(i, c)| !(c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())));
+    match bad {
+        Some((i, _)) => Err(unexpected(s, index + i)),
+        None if name.is_empty() => Err(ParseError::InvalidField { index }),
+        None => Ok(name),
+    }
+}
+
+// Index after `c`, expected after optional whitespace at `index`.
+fn expect(s: &str, index: usize, c: char) -> Result<usize, ParseError> {
+    let at = index + (s[index..].len() - s[index..].trim_start().len());
+    if !s[at..].starts_with(c) {
+        return Err(unexpected(s, at));
+    }
+    Ok(at + c.len_utf8())
+}
+
+// Index of the last character of `s[..end]`.
+fn last_char(s: &str, end: usize) -> usize {
+    s[..end].char_indices().next_back().map_or(0, |(i, _)| i)
+}
+
+fn unexpected(s: &str, index: usize) -> ParseError {
+    ParseError::InvalidCharacter {
+        index,
+        found: s[index..].chars().next().unwrap_or('\u{fffd}'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NET: &str = "4d36e972-e325-11ce-bfc1-08002be10318";
+
+    #[test]
+    fn define_guid_and_initializers() {
+        let inputs = [
+            (
+                "DEFINE_GUID(GUID_DEVCLASS_NET, 0x4d36e972, 0xe325, 0x11ce, 0xbf, 0xc1, \
+                 0x08, 0x00, 0x2b, 0xe1, 0x03, 0x18)",
+                Some("GUID_DEVCLASS_NET"),
+            ),
+            (
+                "static const GUID IID_Net = { 0x4D36E972UL, 0xE325, 0x11CE, \
+                 { 0xBF, 0xC1, 0x08, 0x00, 0x2B, 0xE1, 0x03, 0x18 } };",
+                Some("IID_Net"),
+            ),
+            (
+                "{ 0x4d36e972, 0xe325, 4558, 0xbf, 0xc1, 8, 0, 0x2b, 0xe1, 0x03, 0x18, }",
+                None,
+            ),
+        ];
+        for (input, name) in inputs.iter() {
+            let (found, uuid) = parse_c_define_guid(input).unwrap();
+            assert_eq!(found, *name, "{}", input);
+            assert_eq!(uuid.to_string(), NET);
+        }
+    }
+
+    #[test]
+    fn guid_layout_and_bytes() {
+        let uuid = UUID::parse_str(NET).unwrap();
+        let guid = Guid::from(uuid);
+        assert_eq!(guid.data1, 0x4d36_e972);
+        assert_eq!(guid.data3, 0x11ce);
+        assert_eq!(UUID::from(guid), uuid);
+        assert_eq!(guid.to_string(), NET);
+
+        let le = guid.to_bytes_le();
+        assert_eq!(le[..8], [0x72, 0xe9, 0x36, 0x4d, 0x25, 0xe3, 0xce, 0x11]);
+        assert_eq!(le[8..], uuid.as_array()[8..]);
+        assert_eq!(Guid::from_bytes_le(le), guid);
+    }
+
+    #[test]
+    fn invalid_definitions() {
+        assert_eq!(
+            parse_c_define_guid(
+                "DEFINE_GUID(X, 0x4d36e972, 0x1e325, 0x11ce, 0, 0, 0, 0, 0, 0, 0, 0)"
+            ),
+            Err(ParseError::InvalidField { index: 27 })
+        );
+        assert_eq!(
+            parse_c_define_guid("DEFINE_GUID(X, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10)"),
+            Err(ParseError::InvalidField { index: 44 })
+        );
+        assert_eq!(
+            parse_c_define_guid("DEFINE_GUID(1X, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11)"),
+            Err(ParseError::InvalidCharacter {
+                index: 12,
+                found: '1'
+            })
+        );
+        assert_eq!(
+            parse_c_define_guid("GUID x = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]"),
+            Err(ParseError::InvalidCharacter {
+                index: 9,
+                found: '['
+            })
+        );
+
+        assert_eq!(
+            parse_c_define_guid("DEFINE_GUID(é"),
+            Err(ParseError::InvalidCharacter {
+                index: 12,
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            parse_c_define_guid("{é"),
+            Err(ParseError::InvalidCharacter {
+                index: 1,
+                found: 'é'
+            })
+        );
+        for s in [
+            "DEFINE_GUID(é, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11)",
+            "GUID ä = {1, 2, 3, {4, 5, 6, 7, 8, 9, 10, 11}}",
+            "GUID x = {1, 2, 3, {4, 5, 6, 7, 8, 9, 10, ß}}",
+            "{1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11 ü}",
+            "ü{",
+            "=€",
+        ] {
+            assert!(parse_c_define_guid(s).is_err(), "{}", s);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+//! Ready-made helpers for web frameworks.
+
+pub mod http;
@@ -0,0 +1,186 @@
+//! Request IDs for servers built on the 1.x `http` crate, such as Axum and
+//! Hyper.
+//!
+//! ```ignore
+//! let id = simple_uuid::integrations::http::ensure_request_id(req.headers_mut())?;
+//! ```
+//!
+//! Handlers extract a [`RequestId`] directly in Axum with the `axum`
+//! feature, and in Actix, whose header map is its own, with the
+//! `actix-web` feature:
+//!
+//! ```ignore
+//! async fn handler(RequestId(id): RequestId) -> String {
+//!     format!("request {}", id)
+//! }
+//! ```
+#![doc(cfg(feature = "http_integration"))]
+#![cfg(feature = "http_integration")]
+
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{HeaderSink, UuidError, REQUEST_ID_HEADER, UUID};
+
+/// The `x-request-id` header.
+pub const REQUEST_ID: HeaderName = HeaderName::from_static(REQUEST_ID_HEADER);
+
+/// ID carried by the `x-request-id` header, if it holds a hyphenated UUID.
+pub fn request_id(headers: &HeaderMap) -> Option<UUID> {
+    let value = headers.get(REQUEST_ID)?.to_str().ok()?;
+    UUID::parse_str(value).ok()
+}
+
+/// Propagate the ID of the `x-request-id` header, or set it to a new
+/// version-7 UUID when missing or invalid.
+pub fn ensure_request_id(headers: &mut HeaderMap) -> Result<UUID, UuidError> {
+    if let Some(uuid) = request_id(headers) {
+        return Ok(uuid);
+    }
+    let uuid = UUID::v7()?.to_uuid();
+    headers.insert_header(REQUEST_ID_HEADER, uuid.to_string().as_bytes());
+    Ok(uuid)
+}
+
+/// ID of a request: the `x-request-id` header if it holds a hyphenated UUID,
+/// else a new version-7 UUID.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct RequestId(pub UUID);
+
+/// Propagates the header or sets it on the request, see
+/// [`ensure_request_id`], failing with `500 Internal Server Error` when no
+/// UUID can be generated.
+#[cfg(feature = "axum")]
+#[doc(cfg(feature = "axum"))]
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for RequestId {
+    type Rejection = axum::http::StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _: &S,
+    ) -> Result<Self, Self::Rejection> {
+        ensure_request_id(&mut parts.headers)
+            .map(RequestId)
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+/// Propagates the header, or generates an ID the request does not carry;
+/// stamp it on the response with [`HeaderSink`].
+#[cfg(feature = "actix-web")]
+#[doc(cfg(feature = "actix-web"))]
+impl actix_web::FromRequest for RequestId {
+    type Error = actix_web::Error;
+    type Future = core::future::Ready<Result<Self, actix_web::Error>>;
+
+    fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let header = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| UUID::parse_str(value).ok());
+        core::future::ready(match header {
+            Some(uuid) => Ok(RequestId(uuid)),
+            None => UUID::v7()
+                .map(|layout| RequestId(layout.to_uuid()))
+                .map_err(actix_web::error::ErrorInternalServerError),
+        })
+    }
+}
+
+/// Sets the header of an Actix response, skipping values it rejects.
+#[cfg(feature = "actix-web")]
+#[doc(cfg(feature = "actix-web"))]
+impl HeaderSink for actix_web::http::header::HeaderMap {
+    fn insert_header(&mut self, name: &'static str, value: &[u8]) {
+        use actix_web::http::header::{HeaderName, HeaderValue};
+
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_bytes(value),
+        ) {
+            self.insert(name, value);
+        }
+    }
+}
+
+/// Sets the header, skipping names or values `http` rejects.
+impl HeaderSink for HeaderMap {
+    fn insert_header(&mut self, name: &'static str, value: &[u8]) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_bytes(value),
+        ) {
+            self.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdStamper, Version};
+
+    #[test]
+    fn propagate_or_generate() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUEST_ID,
+            HeaderValue::from_static("6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+        );
+        assert_eq!(ensure_request_id(&mut headers), Ok(UUID::NAMESPACE_DNS));
+
+        headers.insert(REQUEST_ID, HeaderValue::from_static("not-an-id"));
+        let id = ensure_request_id(&mut headers).unwrap();
+        assert_eq!(id.decode().get_version(), Some(Version::UNIX));
+        assert_eq!(request_id(&headers), Some(id));
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn axum_extractor() {
+        use axum::extract::FromRequestParts;
+
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        let extract = |parts: &mut axum::http::request::Parts| {
+            futures::executor::block_on(RequestId::from_request_parts(parts, &())).unwrap()
+        };
+        let RequestId(id) = extract(&mut parts);
+        assert_eq!(request_id(&parts.headers), Some(id));
+        assert_eq!(extract(&mut parts), RequestId(id));
+    }
+
+    #[cfg(feature = "actix-web")]
+    #[test]
+    fn actix_extractor() {
+        use actix_web::test::TestRequest;
+        use actix_web::FromRequest;
+
+        let extract = |req: TestRequest| {
+            let (req, mut payload) = req.to_http_parts();
+            futures::executor::block_on(RequestId::from_request(&req, &mut payload)).unwrap()
+        };
+        let id = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+        let req = TestRequest::default().insert_header((REQUEST_ID_HEADER, id));
+        assert_eq!(extract(req), RequestId(UUID::NAMESPACE_DNS));
+        let RequestId(id) = extract(TestRequest::default());
+        assert_eq!(id.decode().get_version(), Some(Version::UNIX));
+
+        let mut headers = actix_web::http::header::HeaderMap::new();
+        IdStamper::new(|| Ok(UUID::NAMESPACE_URL))
+            .stamp(&mut headers)
+            .unwrap();
+        assert_eq!(
+            headers.get(REQUEST_ID_HEADER).unwrap(),
+            "6ba7b811-9dad-11d1-80b4-00c04fd430c8"
+        );
+    }
+
+    #[test]
+    fn stamp_header_map() {
+        let mut headers = HeaderMap::new();
+        IdStamper::new(|| Ok(UUID::NAMESPACE_URL))
+            .stamp(&mut headers)
+            .unwrap();
+        assert_eq!(request_id(&headers), Some(UUID::NAMESPACE_URL));
+    }
+}
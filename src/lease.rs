@@ -0,0 +1,169 @@
+//! Blocks of time-ordered UUIDs reserved from a central issuer and minted locally.
+//!
+//! A grant is a UUID whose final 32 bits are cleared, and a count: the
+//! holder mints the UUIDs whose final 32 bits run from 0 to `count - 1`.
+//! The issuer takes the first UUID from a monotonic generator such as
+//! `V7Generator`, then skips the UUIDs sharing all but their final 32 bits
+//! with it: everything it issues afterwards sorts above the whole block,
+//! so blocks never overlap.
+
+use std::convert::TryInto;
+
+use crate::UUID;
+
+/// Command byte of an encoded `LeaseRequest`.
+pub const LEASE_COMMAND: u8 = b'R';
+
+/// Request for a block of `count` UUIDs.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct LeaseRequest {
+    /// Number of UUIDs requested.
+    pub count: u32,
+}
+
+impl LeaseRequest {
+    /// Length of the encoded request.
+    pub const LEN: usize = 5;
+
+    /// The command byte and the count, big-endian.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let c = self.count.to_be_bytes();
+        [LEASE_COMMAND, c[0], c[1], c[2], c[3]]
+    }
+
+    /// Request from the form written by `to_bytes`.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        match b {
+            [LEASE_COMMAND, count @ ..] => Some(Self {
+                count: u32::from_be_bytes(count.try_into().ok()?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A block of `count` UUIDs starting at `first`.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct LeaseGrant {
+    first: UUID,
+    count: u32,
+}
+
+impl LeaseGrant {
+    /// Length of the encoded grant.
+    pub const LEN: usize = 20;
+
+    /// Grant of `count` UUIDs sharing all but the final 32 bits of `base`.
+    ///
+    /// The issuer must not issue other UUIDs sharing those bits, even when
+    /// its generator increments the final bits; see the module.
+    pub fn new(base: UUID, count: u32) -> Self {
+        Self {
+            first: UUID::from_u128(base.as_u128() & !0xffff_ffff),
+            count,
+        }
+    }
+
+    /// First UUID of the block.
+    pub fn first(&self) -> UUID {
+        self.first
+    }
+
+    /// Number of UUIDs in the block.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// The `i`th UUID of the block.
+    pub fn get(&self, i: u32) -> Option<UUID> {
+        (i < self.count).then(|| UUID::from_u128(self.first.as_u128() | u128::from(i)))
+    }
+
+    /// Whether `uuid` belongs to the block.
+    pub fn contains(&self, uuid: &UUID) -> bool {
+        let offset = uuid.as_u128().wrapping_sub(self.first.as_u128());
+        offset < u128::from(self.count)
+    }
+
+    /// The UUIDs of the block, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = UUID> + '_ {
+        (0..self.count).filter_map(move |i| self.get(i))
+    }
+
+    /// The first UUID and the count, big-endian.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut b = [0u8; Self::LEN];
+        b[..16].copy_from_slice(&self.first.0);
+        b[16..].copy_from_slice(&self.count.to_be_bytes());
+        b
+    }
+
+    /// Grant from the form written by `to_bytes`, `None` if malformed.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        if b.len() != Self::LEN {
+            return None;
+        }
+        let first = UUID(b[..16].try_into().ok()?);
+        (first.as_u128() & 0xffff_ffff == 0).then(|| Self {
+            first,
+            count: u32::from_be_bytes(b[16..].try_into().unwrap()),
+        })
+    }
+}
+
+/// Client reserving blocks from an `IdService`.
+#[cfg(feature = "service")]
+#[doc(cfg(feature = "service"))]
+pub struct LeaseClient {
+    client: crate::service::ServiceClient,
+}
+
+#[cfg(feature = "service")]
+impl LeaseClient {
+    /// Connect to the service at `addr`.
+    pub fn connect<A: std::net::ToSocketAddrs>(addr: A) -> std::io::Result<Self> {
+        crate::service::ServiceClient::connect(addr).map(|client| Self { client })
+    }
+
+    /// Reserve a block of `n` UUIDs to mint locally.
+    pub fn reserve(&mut self, n: u32) -> std::io::Result<LeaseGrant> {
+        let body = self
+            .client
+            .call(&LeaseRequest { count: n }.to_bytes(), LeaseGrant::LEN)?;
+        LeaseGrant::from_bytes(&body)
+            .filter(|grant| grant.count() == n)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed grant"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_round_trip() {
+        let request = LeaseRequest { count: 1_000 };
+        assert_eq!(LeaseRequest::from_bytes(&request.to_bytes()), Some(request));
+        assert_eq!(LeaseRequest::from_bytes(b"H"), None);
+    }
+
+    #[test]
+    fn grant_mints_within_range() {
+        let grant = LeaseGrant::new(
+            UUID::from_u128(0x0190_0000_0000_7000_8000_0000_dead_beef),
+            3,
+        );
+        assert_eq!(
+            grant.first().as_u128(),
+            0x0190_0000_0000_7000_8000_0000_0000_0000
+        );
+        assert_eq!(grant.iter().count(), 3);
+        assert!(grant.iter().all(|uuid| grant.contains(&uuid)));
+        assert!(grant.iter().zip(grant.iter().skip(1)).all(|(a, b)| a < b));
+        assert_eq!(grant.get(3), None);
+        assert!(!grant.contains(&UUID::from_u128(grant.first().as_u128() - 1)));
+
+        assert_eq!(LeaseGrant::from_bytes(&grant.to_bytes()), Some(grant));
+        assert_eq!(LeaseGrant::from_bytes(&[0xff; LeaseGrant::LEN]), None);
+    }
+}
@@ -9,28 +9,147 @@
 //! ```
 //!
 //! ```rust
-//! use simple_uuid::v4;
-//! println!("{}", v4!());
+//! use simple_uuid::UUID;
+//! println!("{}", UUID::new_from_rand().unwrap());
 //! ```
 #![doc(html_root_url = "https://docs.rs/simple-uuid")]
 #![feature(doc_cfg)]
 
+extern crate self as simple_uuid;
+
+mod async_stream;
+mod bucket;
+pub mod calendar;
+pub mod cast;
+mod chaos;
+mod chrono;
+mod clock;
+pub mod compat;
+mod components;
+mod composite;
+pub mod config;
+mod context;
+pub mod crosscheck;
+mod deterministic;
+mod diff;
+mod entropy;
+mod error;
+pub mod fields;
+pub mod forensics;
+mod format;
+pub mod gc;
+mod generator;
+mod graphql;
+mod guid;
+pub mod integrations;
+pub mod lease;
+pub mod migrate;
+mod monotonic;
 mod name;
+mod node;
+mod openapi;
+mod page;
+mod par;
+mod parse;
+mod per_core;
+pub mod prelude;
+pub mod prometheus;
+mod public_key;
 mod rand;
+mod redact;
+mod reorder;
+pub mod retention;
+mod sample;
+pub mod serde;
+pub mod service;
+mod shared;
+mod spec;
+mod stamper;
+pub mod state;
+pub mod stats;
+mod stream;
+pub mod synth;
 mod time;
-
+mod time_source;
+mod timestamp;
+mod trie;
+pub mod typed;
+pub mod uefi;
+mod unix;
+mod versions;
+mod view;
+pub mod web;
+
+#[cfg(feature = "async")]
+pub use async_stream::UuidStream;
+pub use chaos::{ChaosConfig, ChaosGenerator, ChaosStats};
+pub use clock::{Clock, SystemClock};
+pub use components::{Components, FieldName};
+pub use composite::CompositeKey;
+#[cfg(feature = "mac_addr")]
+pub use context::V1Context;
+#[cfg(feature = "rand_num")]
+pub use deterministic::DeterministicGenerator;
+pub use diff::UuidDiff;
+pub use entropy::generation_is_lock_free;
+pub use error::UuidError;
+pub use format::Format;
+pub use generator::{Generator, GeneratorRouter};
+pub use guid::{parse_c_define_guid, Guid};
+#[cfg(feature = "rand_num")]
+pub use monotonic::{RolloverPolicy, V7Generator, V7Method};
+#[cfg(any(feature = "hash_md5", feature = "hash_sha1", feature = "hash_digest"))]
+pub use name::{Namespace, NamespaceError};
+#[cfg(feature = "mac_addr")]
+pub use node::MacAddressNode;
+pub use node::NodeSource;
+#[cfg(feature = "rand_num")]
+pub use node::RandomNode;
+#[cfg(feature = "page_token")]
+pub use page::{PageToken, PageTokenError};
+pub use parse::{ParseError, ParseOptions};
+pub use per_core::{PerCoreGenerators, PerCoreStats};
+#[cfg(feature = "public_key")]
+pub use public_key::PublicKeyError;
+/// The `rand_core` crate of the generators taken by [`UUID::v4_from_rng`].
+#[cfg(feature = "rand_num")]
+#[doc(cfg(feature = "rand_num"))]
+pub use rand_core;
+#[cfg(feature = "redact")]
+pub use redact::Redacted;
+pub use shared::{GeneratorMetrics, SharedGenerator};
+/// Parse a UUID literal at compile time, failing to compile if malformed.
+///
+/// ```
+/// use simple_uuid::{uuid, UUID};
+/// const ID: UUID = uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+/// assert_eq!(ID, UUID::NAMESPACE_DNS);
+/// ```
+///
+/// ```compile_fail
+/// use simple_uuid::{uuid, UUID};
+/// const ID: UUID = uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430cg");
+/// ```
+#[cfg(feature = "macros")]
+#[doc(cfg(feature = "macros"))]
+pub use simple_uuid_macros::uuid;
+pub use spec::{Case, FormatSpec};
+pub use stamper::{HeaderSink, IdStamper, REQUEST_ID_HEADER};
+pub use stream::{generate_stream, generate_stream_from};
+pub use time_source::{ClockEvent, TimeSource};
+pub use trie::{Resolution, UuidTrie};
+pub use versions::VersionSet;
+pub use view::{V6View, V7View, V8View};
+
+use core::convert::{TryFrom, TryInto};
 use core::fmt;
 use core::sync::atomic;
-use std::string::ToString;
-use std::time::SystemTime;
-
-use rand_core::{OsRng, RngCore};
 
 /// Is 100-ns ticks between UNIX and UTC epochs.
-pub const UTC_EPOCH: u64 = 0x1b21_dd21_3814_000;
+pub const UTC_EPOCH: u64 = 0x01b2_1dd2_1381_4000;
 
 /// The UUID format is 16 octets.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Copy, Clone)]
 pub struct Layout {
     /// The low field of the Timestamp.
     pub field_low: u32,
@@ -91,54 +210,70 @@ impl Layout {
         ])
     }
 
-    /// Return the memory representation of the UUID in little-endian order .
+    /// The UUID of these fields.
+    pub fn to_uuid(&self) -> UUID {
+        self.be_bytes()
+    }
+
+    /// The UUID of these fields.
+    #[deprecated(since = "0.6.0", note = "renamed to `to_uuid`")]
     pub fn as_bytes(&self) -> UUID {
-        UUID([
-            self.field_low.to_le_bytes()[3],
-            self.field_low.to_le_bytes()[2],
-            self.field_low.to_le_bytes()[1],
-            self.field_low.to_le_bytes()[0],
-            self.field_mid.to_le_bytes()[1],
-            self.field_mid.to_le_bytes()[0],
-            self.field_high_and_version.to_le_bytes()[1],
-            self.field_high_and_version.to_le_bytes()[0],
-            self.clock_seq_high_and_reserved,
-            self.clock_seq_low,
-            self.node.0[5],
-            self.node.0[4],
-            self.node.0[3],
-            self.node.0[2],
-            self.node.0[1],
-            self.node.0[0],
-        ])
+        self.to_uuid()
     }
 
     /// Version of the current generated UUID.
     pub const fn get_version(&self) -> Option<Version> {
-        match (self.field_high_and_version >> 12) & 0xf {
-            0x01 => Some(Version::TIME),
-            0x02 => Some(Version::DCE),
-            0x03 => Some(Version::MD5),
-            0x04 => Some(Version::RAND),
-            0x05 => Some(Version::SHA1),
-            _ => None,
-        }
+        Version::from_num(self.get_version_num())
+    }
+
+    /// Raw version number, including numbers this crate has no `Version` for.
+    pub const fn get_version_num(&self) -> u8 {
+        ((self.field_high_and_version >> 12) & 0xf) as u8
     }
 
     /// Variant field of the current generated UUID.
     pub const fn get_variant(&self) -> Option<Variant> {
-        match (self.clock_seq_high_and_reserved >> 4) & 0xf {
-            0x00 => Some(Variant::NCS),
-            0x01 => Some(Variant::RFC),
-            0x02 => Some(Variant::MS),
-            0x03 => Some(Variant::FUT),
-            _ => None,
+        match self.clock_seq_high_and_reserved >> 5 {
+            0b000..=0b011 => Some(Variant::NCS),
+            0b100 | 0b101 => Some(Variant::RFC),
+            0b110 => Some(Variant::MS),
+            _ => Some(Variant::FUT),
         }
     }
+
+    /// Node field, usually the MAC-address of a version-1 or 2 UUID.
+    pub const fn get_node(&self) -> Node {
+        self.node
+    }
+
+    /// The 14-bit clock sequence, without the variant bits.
+    pub const fn get_clock_seq(&self) -> u16 {
+        ((self.clock_seq_high_and_reserved & 0x3f) as u16) << 8 | self.clock_seq_low as u16
+    }
+
+    /// Split the 16 octets of `uuid` back into the layout fields.
+    pub fn from_bytes(uuid: &UUID) -> Self {
+        let b = uuid.0;
+        Self {
+            field_low: u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            field_mid: u16::from_be_bytes([b[4], b[5]]),
+            field_high_and_version: u16::from_be_bytes([b[6], b[7]]),
+            clock_seq_high_and_reserved: b[8],
+            clock_seq_low: b[9],
+            node: Node([b[10], b[11], b[12], b[13], b[14], b[15]]),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_uuid(), fmt)
+    }
 }
 
 /// Variant is a type field determines the layout of the UUID.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
 pub enum Variant {
     /// Reserved, NCS backward compatibility.
     NCS = 0,
@@ -151,7 +286,11 @@ pub enum Variant {
 }
 
 /// Version represents the type of UUID, and is in the most significant 4 bits of the Timestamp.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// New versions keep being standardized, so matches need a wildcard arm;
+/// `get_version_num` gives the raw number of versions not listed here.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
 pub enum Version {
     /// The time-based version specified in `rfc4122` document.
     TIME = 1,
@@ -163,31 +302,106 @@ pub enum Version {
     RAND,
     /// The name-based version specified in `rfc4122`document that uses SHA-1 hashing.
     SHA1,
+    /// The time-based version specified in `rfc9562` document, with the
+    /// timestamp of version-1 reordered to sort by time.
+    REORDERED,
+    /// The time-based version specified in `rfc9562` document, with a UNIX
+    /// millisecond timestamp followed by random bits.
+    UNIX,
+    /// The custom version specified in `rfc9562` document, for
+    /// vendor-specific layouts.
+    CUSTOM,
 }
 
-/// Represented by Coordinated Universal Time (UTC) as a count
-/// of 100-ns intervals from the system-time.
-#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
-pub struct TimeStamp(u64);
+impl Version {
+    /// Every known version, in numeric order.
+    pub const ALL: [Version; 8] = [
+        Version::TIME,
+        Version::DCE,
+        Version::MD5,
+        Version::RAND,
+        Version::SHA1,
+        Version::REORDERED,
+        Version::UNIX,
+        Version::CUSTOM,
+    ];
+
+    const fn from_num(n: u8) -> Option<Version> {
+        match n {
+            0x01 => Some(Version::TIME),
+            0x02 => Some(Version::DCE),
+            0x03 => Some(Version::MD5),
+            0x04 => Some(Version::RAND),
+            0x05 => Some(Version::SHA1),
+            0x06 => Some(Version::REORDERED),
+            0x07 => Some(Version::UNIX),
+            0x08 => Some(Version::CUSTOM),
+            _ => None,
+        }
+    }
+
+    /// Number of bits the specification requires to be random.
+    pub const fn random_bits(&self) -> u32 {
+        match self {
+            Version::RAND => 122,
+            Version::UNIX => 74,
+            _ => 0,
+        }
+    }
+
+    /// Whether UUIDs of this version sort in generation order.
+    pub const fn is_time_ordered(&self) -> bool {
+        matches!(self, Version::REORDERED | Version::UNIX)
+    }
 
-impl TimeStamp {
-    /// Generate new UTC timestamp.
-    pub fn new() -> u64 {
-        let utc = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("elapsed time ")
-            .checked_add(std::time::Duration::from_nanos(UTC_EPOCH))
-            .expect("duration addition error")
-            .as_nanos();
-        (utc & 0xffff_ffff_ffff_fff) as u64
+    /// Number of the RFC that first specified this version.
+    pub const fn rfc(&self) -> u16 {
+        match self {
+            Version::REORDERED | Version::UNIX | Version::CUSTOM => 9562,
+            _ => 4122,
+        }
+    }
+}
+
+impl TryFrom<u8> for Version {
+    /// The unknown version number.
+    type Error = u8;
+
+    fn try_from(n: u8) -> Result<Self, u8> {
+        Version::from_num(n).ok_or(n)
     }
 }
 
+impl From<Version> for u8 {
+    fn from(v: Version) -> Self {
+        v as u8
+    }
+}
+
+/// Time of version-1 and 6 UUIDs, as a 60-bit count of 100-ns intervals
+/// since 1582-10-15 00:00 UTC, the Gregorian epoch.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy, Default)]
+pub struct TimeStamp(u64);
+
 /// Is a 128-bit number used to identify information in computer systems.
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+///
+/// UUIDs are ordered as their 16 big-endian octets compared with `memcmp`,
+/// which is also the order of `as_u128`; this order is stable across releases.
+///
+/// A UUID has the layout of its 16 octets, `[u8; 16]`, which the
+/// [`cast`](crate::cast) helpers rely on.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[repr(transparent)]
 pub struct UUID([u8; 16]);
 
 impl UUID {
+    /// The Nil UUID, with all 128 bits set to zero.
+    pub const NIL: UUID = UUID([0; 16]);
+
+    /// The Max UUID, with all 128 bits set to one.
+    pub const MAX: UUID = UUID([0xff; 16]);
+
     /// UUID namespace for domain name system (DNS).
     pub const NAMESPACE_DNS: UUID = UUID([
         0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
@@ -211,6 +425,117 @@ impl UUID {
         0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
         0xc8,
     ]);
+
+    /// UUID from its 16 big-endian octets.
+    pub const fn from_bytes(bytes: [u8; 16]) -> UUID {
+        UUID(bytes)
+    }
+
+    /// UUID from a 128-bit big-endian value.
+    pub const fn from_u128(v: u128) -> UUID {
+        UUID(v.to_be_bytes())
+    }
+
+    /// The UUID as a 128-bit big-endian value.
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    /// Consume the UUID into its 16 big-endian octets.
+    pub const fn into_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Borrow the 16 big-endian octets of the UUID.
+    pub const fn as_array(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// UUID version-8 holding `custom`, except for the version and variant bits.
+    pub const fn v8(custom: [u8; 16]) -> UUID {
+        let mut bytes = custom;
+        bytes[fields::VERSION_BYTE] = bytes[fields::VERSION_BYTE] & !fields::VERSION_MASK
+            | (Version::CUSTOM as u8) << fields::VERSION_SHIFT;
+        bytes[fields::VARIANT_BYTE] =
+            bytes[fields::VARIANT_BYTE] & !fields::VARIANT_MASK | fields::VARIANT_RFC;
+        UUID(bytes)
+    }
+
+    /// The first `N` octets; `N` above 16 fails to compile.
+    pub fn prefix_bytes<const N: usize>(&self) -> [u8; N] {
+        const { assert!(N <= 16, "a UUID has 16 octets") };
+        let mut prefix = [0u8; N];
+        prefix.copy_from_slice(&self.0[..N]);
+        prefix
+    }
+
+    /// The last `N` octets; `N` above 16 fails to compile.
+    pub fn suffix_bytes<const N: usize>(&self) -> [u8; N] {
+        const { assert!(N <= 16, "a UUID has 16 octets") };
+        let mut suffix = [0u8; N];
+        suffix.copy_from_slice(&self.0[16 - N..]);
+        suffix
+    }
+
+    /// UUID from its field values, with `d4` holding the last 8 octets.
+    pub const fn from_fields(d1: u32, d2: u16, d3: u16, d4: &[u8; 8]) -> UUID {
+        let (a, b, c) = (d1.to_be_bytes(), d2.to_be_bytes(), d3.to_be_bytes());
+        UUID([
+            a[0], a[1], a[2], a[3], b[0], b[1], c[0], c[1], d4[0], d4[1], d4[2], d4[3], d4[4],
+            d4[5], d4[6], d4[7],
+        ])
+    }
+
+    /// UUID from field values whose first three fields are stored
+    /// little-endian, as in the Windows `GUID` structure.
+    pub const fn from_fields_le(d1: u32, d2: u16, d3: u16, d4: &[u8; 8]) -> UUID {
+        UUID::from_fields(d1.swap_bytes(), d2.swap_bytes(), d3.swap_bytes(), d4)
+    }
+
+    /// The field values of the UUID, with the last 8 octets as a reference.
+    pub fn as_fields(&self) -> (u32, u16, u16, &[u8; 8]) {
+        let b = &self.0;
+        (
+            u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            u16::from_be_bytes([b[4], b[5]]),
+            u16::from_be_bytes([b[6], b[7]]),
+            b[8..].try_into().unwrap(),
+        )
+    }
+
+    /// The field values of the UUID with the first three fields read
+    /// little-endian, the inverse of `from_fields_le`.
+    pub fn as_fields_le(&self) -> (u32, u16, u16, &[u8; 8]) {
+        let (d1, d2, d3, d4) = self.as_fields();
+        (d1.swap_bytes(), d2.swap_bytes(), d3.swap_bytes(), d4)
+    }
+
+    /// Key whose byte-wise (`memcmp`) order is the order of UUIDs, stable
+    /// across releases, for persisted B-tree or LSM keys.
+    pub const fn memcmp_key(&self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Whether this is the Nil UUID.
+    pub const fn is_nil(&self) -> bool {
+        u128::from_be_bytes(self.0) == 0
+    }
+
+    /// Whether this is the Max UUID.
+    pub const fn is_max(&self) -> bool {
+        u128::from_be_bytes(self.0) == u128::MAX
+    }
+
+    /// The layout fields of the UUID, to inspect a UUID not generated here.
+    pub fn decode(&self) -> Layout {
+        Layout::from_bytes(self)
+    }
+
+    /// Raw version number held in the most significant 4 bits of octet 6,
+    /// including numbers this crate has no `Version` for.
+    pub const fn get_version_num(&self) -> u8 {
+        (self.0[fields::VERSION_BYTE] & fields::VERSION_MASK) >> fields::VERSION_SHIFT
+    }
 }
 
 impl fmt::LowerHex for UUID {
@@ -263,53 +588,29 @@ impl fmt::UpperHex for UUID {
     }
 }
 
-impl ToString for UUID {
-    fn to_string(&self) -> String {
-        format!(
-            "{:02}{:02}{:02}{:02}-{:02}{:02}-{:02}{:02}-{:02}{:02}-{:02}{:02}{:02}{:02}{:02}{:02}",
-            self.0[0],
-            self.0[1],
-            self.0[2],
-            self.0[3],
-            self.0[4],
-            self.0[5],
-            self.0[6],
-            self.0[7],
-            self.0[8],
-            self.0[9],
-            self.0[10],
-            self.0[11],
-            self.0[12],
-            self.0[13],
-            self.0[14],
-            self.0[15],
-        )
+impl fmt::Display for UUID {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, fmt)
     }
 }
 
 /// Used to avoid duplicates that could arise when the clock is set backwards in time.
+#[allow(dead_code)]
+#[deprecated(since = "0.6.0", note = "the clock sequence is kept by `V1Context`")]
 pub struct ClockSeq(u16);
 
+#[allow(deprecated)]
 impl ClockSeq {
     /// New atomic random value.
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(r: u16) -> u16 {
         atomic::AtomicU16::new(r).fetch_add(1, atomic::Ordering::SeqCst)
     }
 }
 
-fn clock_seq_high_and_reserved(s: u8) -> (u8, u8) {
-    let mut key = [0u8; 2];
-    OsRng.fill_bytes(&mut key);
-    let random_u64 = (OsRng.next_u64() & 0xff) as u16;
-    let clock_seq = ClockSeq::new(random_u64);
-    (
-        ((clock_seq >> 8) & 0xf) as u8 | s << 4,
-        (clock_seq & 0xff) as u8,
-    )
-}
 /// Node field consists of an IEEE 802 MAC address,
 /// usually the host address
-#[derive(Debug, PartialEq, Default, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone)]
 pub struct Node(pub [u8; 6]);
 
 impl fmt::LowerHex for Node {
@@ -332,12 +633,9 @@ impl fmt::UpperHex for Node {
     }
 }
 
-impl ToString for Node {
-    fn to_string(&self) -> String {
-        format!(
-            "{:02}-{:02}-{:02}-{:02}-{:02}-{:02}",
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5],
-        )
+impl fmt::Display for Node {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(self, fmt)
     }
 }
 
@@ -352,11 +650,165 @@ mod tests {
 
         let uuid = UUID::default();
         assert_eq!(uuid, UUID([0; 16]));
+        assert_eq!(uuid, UUID::NIL);
+        assert!(uuid.is_nil() && !uuid.is_max());
+        assert!(UUID::MAX.is_max() && !UUID::MAX.is_nil());
+        assert_eq!(UUID::MAX.as_u128(), u128::MAX);
 
         let time: TimeStamp = TimeStamp::default();
         assert_eq!(time.0.leading_zeros(), 64)
     }
 
+    #[test]
+    fn ord_is_memcmp_order() {
+        let mut state = 0x853c_49e6_748f_ea9b_u128;
+        let mut uuids: Vec<UUID> = (0..256)
+            .map(|i| {
+                state = state.wrapping_mul(0x2360_ed05_1fc6_5da4_4385_df64_9fcc_f645) + 1;
+                UUID::from_u128(state >> (i % 128))
+            })
+            .collect();
+        uuids.extend(&[UUID::NIL, UUID::MAX]);
+        for a in uuids.iter() {
+            for b in uuids.iter().step_by(7) {
+                assert_eq!(a.cmp(b), a.memcmp_key().cmp(&b.memcmp_key()));
+                assert_eq!(a.cmp(b), a.as_u128().cmp(&b.as_u128()));
+            }
+        }
+    }
+
+    #[test]
+    fn hash_map_key() {
+        let mut seen = std::collections::HashMap::new();
+        seen.insert(UUID::NAMESPACE_DNS, 1);
+        seen.insert(UUID::default(), 2);
+        assert_eq!(seen[&UUID::from_u128(UUID::NAMESPACE_DNS.as_u128())], 1);
+        assert_eq!(seen[&UUID::from_bytes([0; 16])], 2);
+
+        let layout = UUID::NAMESPACE_DNS.decode();
+        let copy = layout;
+        assert_eq!(layout.to_uuid(), copy.to_uuid());
+    }
+
+    #[test]
+    fn bytes_and_integers() {
+        let uuid = UUID::from_bytes([
+            0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4,
+            0x30, 0xc8,
+        ]);
+        assert_eq!(uuid, UUID::NAMESPACE_DNS);
+        assert_eq!(uuid.as_u128(), 0x6ba7_b810_9dad_11d1_80b4_00c0_4fd4_30c8);
+        assert_eq!(UUID::from_u128(uuid.as_u128()), uuid);
+        assert_eq!(UUID::from_bytes(uuid.into_bytes()), uuid);
+    }
+
+    #[test]
+    fn custom_v8() {
+        let uuid = UUID::v8([0xff; 16]);
+        assert_eq!(uuid.as_u128(), 0xffff_ffff_ffff_8fff_bfff_ffff_ffff_ffff);
+        assert_eq!(uuid.decode().get_version(), Some(Version::CUSTOM));
+        assert_eq!(uuid.decode().get_variant(), Some(Variant::RFC));
+        assert_eq!(UUID::v8(uuid.into_bytes()), uuid);
+    }
+
+    #[test]
+    fn prefix_and_suffix() {
+        let uuid = UUID::NAMESPACE_DNS;
+        assert_eq!(uuid.prefix_bytes::<2>(), [0x6b, 0xa7]);
+        assert_eq!(
+            u64::from_be_bytes(uuid.prefix_bytes()),
+            0x6ba7_b810_9dad_11d1
+        );
+        assert_eq!(uuid.suffix_bytes::<3>(), [0xd4, 0x30, 0xc8]);
+        assert_eq!(uuid.suffix_bytes::<16>(), uuid.into_bytes());
+        assert_eq!(uuid.prefix_bytes::<0>(), [0u8; 0]);
+    }
+
+    #[test]
+    fn const_constructors() {
+        const DNS: UUID = UUID::from_u128(0x6ba7_b810_9dad_11d1_80b4_00c0_4fd4_30c8);
+        static URL: UUID = UUID::from_fields(
+            0x6ba7_b811,
+            0x9dad,
+            0x11d1,
+            &[0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8],
+        );
+        const RAW: u128 = UUID::NAMESPACE_DNS.as_u128();
+
+        assert_eq!(DNS, UUID::NAMESPACE_DNS);
+        assert_eq!(RAW, DNS.as_u128());
+        let name = match URL {
+            UUID::NAMESPACE_DNS => "dns",
+            UUID::NAMESPACE_URL => "url",
+            _ => "other",
+        };
+        assert_eq!(name, "url");
+    }
+
+    #[test]
+    fn byte_conversions() {
+        let uuid = UUID::NAMESPACE_DNS;
+        assert_eq!(uuid.as_array(), &uuid.into_bytes());
+        assert_eq!(UUID::from_bytes(*uuid.as_array()), uuid);
+        assert_eq!(uuid.decode().to_uuid(), uuid);
+    }
+
+    #[test]
+    fn fields_round_trip() {
+        let d4 = [0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+        let uuid = UUID::from_fields(0x6ba7_b810, 0x9dad, 0x11d1, &d4);
+        assert_eq!(uuid, UUID::NAMESPACE_DNS);
+        assert_eq!(uuid.as_fields(), (0x6ba7_b810, 0x9dad, 0x11d1, &d4));
+
+        let le = UUID::from_fields_le(0x10b8_a76b, 0xad9d, 0xd111, &d4);
+        assert_eq!(le, UUID::NAMESPACE_DNS);
+        assert_eq!(le.as_fields_le(), (0x10b8_a76b, 0xad9d, 0xd111, &d4));
+    }
+
+    #[test]
+    fn decode_foreign_uuid() {
+        let layout = UUID::NAMESPACE_DNS.decode();
+        assert_eq!(layout.get_version(), Some(Version::TIME));
+        assert_eq!(layout.get_variant(), Some(Variant::RFC));
+        assert_eq!(
+            layout.get_node(),
+            Node([0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8])
+        );
+        assert_eq!(layout.get_clock_seq(), 0x00b4);
+        assert_eq!(layout.to_uuid(), UUID::NAMESPACE_DNS);
+        assert_eq!(layout.be_bytes(), UUID::NAMESPACE_DNS);
+
+        let ms = UUID::from_u128(0x0000_0000_0000_0000_c000_0000_0000_0000);
+        assert_eq!(ms.decode().get_variant(), Some(Variant::MS));
+        assert_eq!(UUID::default().decode().get_variant(), Some(Variant::NCS));
+    }
+
+    #[test]
+    fn future_version_num() {
+        let uuid = UUID::from_u128(0x0000_0000_0000_9000_8000_0000_0000_0000);
+        assert_eq!(uuid.get_version_num(), 9);
+        assert_eq!(uuid.decode().get_version(), None);
+        assert_eq!(uuid.decode().get_version_num(), 9);
+        assert_eq!(UUID::parse_str(&format!("{:x}", uuid)), Ok(uuid));
+    }
+
+    #[test]
+    fn version_metadata() {
+        for v in Version::ALL.iter() {
+            assert_eq!(Version::try_from(u8::from(*v)), Ok(*v));
+        }
+        assert_eq!(Version::SHA1.rfc(), 4122);
+        assert_eq!(Version::REORDERED.rfc(), 9562);
+        assert!(!Version::TIME.is_time_ordered());
+        assert!(Version::REORDERED.is_time_ordered());
+        assert_eq!(Version::try_from(0), Err(0));
+        assert_eq!(Version::try_from(15), Err(15));
+        assert_eq!(Version::RAND.random_bits(), 122);
+        assert_eq!(Version::MD5.random_bits(), 0);
+        assert_eq!(Version::UNIX.random_bits(), 74);
+        assert!(Version::UNIX.is_time_ordered());
+    }
+
     #[test]
     fn to_string() {
         let node = Node::default();
@@ -364,5 +816,13 @@ mod tests {
 
         let uuid = UUID::default();
         assert_eq!(uuid.to_string(), "00000000-0000-0000-0000-000000000000");
+        assert_eq!(
+            UUID::NAMESPACE_DNS.to_string(),
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+        );
+        assert_eq!(
+            UUID::NAMESPACE_DNS.decode().to_string(),
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+        );
     }
 }
@@ -0,0 +1,85 @@
+//! Bulk conversion of stored UUIDs between byte orders.
+//!
+//! .NET (`Guid.ToByteArray`) and SQL Server store the first three fields of
+//! a UUID little-endian, while this crate, `rfc9562` and most databases
+//! store all 16 octets big-endian. The conversion is its own inverse, so
+//! the same call migrates a column either way.
+
+use std::convert::TryInto;
+
+/// Swap each packed 16-byte record of `buf` in place between big-endian
+/// and the mixed-endian layout of Microsoft `GUID`s.
+///
+/// Each record is rewritten with branch-free 64-bit arithmetic, which the
+/// compiler vectorizes over large buffers.
+///
+/// # Panics
+///
+/// Panics if the length of `buf` is not a multiple of 16.
+///
+/// ```rust
+/// use simple_uuid::{migrate, UUID};
+///
+/// let mut column = UUID::NAMESPACE_DNS.into_bytes();
+/// migrate::swap_guid_endianness(&mut column);
+/// assert_eq!(UUID::from_bytes(column).as_fields_le(), UUID::NAMESPACE_DNS.as_fields());
+/// ```
+pub fn swap_guid_endianness(buf: &mut [u8]) {
+    assert!(
+        buf.len().is_multiple_of(16),
+        "buffer of {} bytes is not made of 16-byte records",
+        buf.len()
+    );
+    for record in buf.chunks_exact_mut(16) {
+        let (head, _) = record.split_at_mut(8);
+        let fields = u64::from_be_bytes((&*head).try_into().unwrap());
+        // d1 d2 d3 reversed as a whole, then d1 moved back in front and
+        // d2, d3 put back in order.
+        let reversed = fields.swap_bytes().rotate_left(32);
+        let swapped =
+            reversed & 0xffff_ffff_0000_0000 | u64::from((reversed as u32).rotate_left(16));
+        head.copy_from_slice(&swapped.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UUID;
+
+    #[test]
+    fn swap_round_trip() {
+        let uuids = [
+            UUID::NAMESPACE_DNS,
+            UUID::NAMESPACE_OID,
+            UUID::MAX,
+            UUID::NIL,
+        ];
+        let mut buf: Vec<u8> = uuids.iter().flat_map(|u| u.into_bytes()).collect();
+        swap_guid_endianness(&mut buf);
+        assert_eq!(
+            &buf[..16],
+            [
+                0x10, 0xb8, 0xa7, 0x6b, 0xad, 0x9d, 0xd1, 0x11, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4,
+                0x30, 0xc8
+            ]
+        );
+        for (record, uuid) in buf.chunks_exact(16).zip(&uuids) {
+            let (d1, d2, d3, d4) = uuid.as_fields_le();
+            assert_eq!(
+                UUID::from_bytes(record.try_into().unwrap()),
+                UUID::from_fields(d1, d2, d3, d4)
+            );
+        }
+
+        swap_guid_endianness(&mut buf);
+        let back: Vec<u8> = uuids.iter().flat_map(|u| u.into_bytes()).collect();
+        assert_eq!(buf, back);
+    }
+
+    #[test]
+    #[should_panic]
+    fn partial_record() {
+        swap_guid_endianness(&mut [0; 20]);
+    }
+}
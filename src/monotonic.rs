@@ -0,0 +1,535 @@
+#![doc(cfg(feature = "rand_num"))]
+#![cfg(feature = "rand_num")]
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rand_core::RngCore;
+
+use crate::entropy;
+use crate::state::{GeneratorState, StateStore};
+use crate::{Clock, Generator, GeneratorMetrics, SystemClock, UuidError, Version, UUID};
+
+/// Bits of the counter, spread over `rand_a` and the top of `rand_b`.
+pub(crate) const COUNTER_BITS: u32 = 42;
+
+const COUNTER_MAX: u64 = (1 << COUNTER_BITS) - 1;
+
+/// Bits of the counter held in `rand_b`, below the variant.
+const COUNTER_LOW_BITS: u32 = 30;
+
+const RAND_A_MAX: u64 = 0xfff;
+
+const RAND_B_MAX: u64 = (1 << 62) - 1;
+
+/// How a [`V7Generator`] keeps UUIDs of the same millisecond increasing.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum V7Method {
+    /// Fixed-length dedicated counter (method 1 of `rfc9562`): a 42-bit
+    /// counter is incremented by one, and the timestamp is advanced by one
+    /// millisecond when it overflows.
+    #[default]
+    Counter,
+    /// Monotonic random (method 2 of `rfc9562`): `rand_a` is incremented by a
+    /// random step of 1 to 8 under fresh random `rand_b` bits, and the policy
+    /// says what happens when `rand_a` overflows.
+    RandomIncrement(RolloverPolicy),
+}
+
+/// What a [`V7Generator`] does when the increment of [`V7Method::RandomIncrement`]
+/// overflows `rand_a` within a millisecond.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum RolloverPolicy {
+    /// Wait for the clock to reach the next millisecond, failing with
+    /// `UuidError::Clock` after 100 ms.
+    Freeze,
+    /// Advance the timestamp by one millisecond ahead of the clock.
+    #[default]
+    AdvanceTimestamp,
+    /// Keep `rand_a` at its maximum and increment `rand_b` by a random step
+    /// of up to 2^32 instead, advancing the timestamp once `rand_b` also
+    /// overflows.
+    BorrowRandB,
+}
+
+/// Longest wait of [`RolloverPolicy::Freeze`] for the next millisecond.
+const MAX_FREEZE_WAIT: Duration = Duration::from_millis(100);
+
+/// How far ahead of the UUIDs issued the timestamp is saved, in milliseconds.
+const SAVE_AHEAD_MS: u64 = 10_000;
+
+// Last timestamp, counter or `rand_a`, `rand_b`, whether the increment
+// already overflowed in this millisecond, last reading of the clock, which
+// the timestamp may run ahead of, and the timestamp saved to the store.
+#[derive(Debug, Default)]
+struct State {
+    ms: u64,
+    counter: u64,
+    rand_b: u64,
+    rolled_over: bool,
+    clock_ms: u64,
+    saved_until: u64,
+}
+
+/// Thread-safe generator of strictly increasing version-7 UUIDs.
+///
+/// By default implements the fixed-length dedicated counter method of
+/// `rfc9562`: a 42-bit counter, seeded randomly with its top bit clear at
+/// each new millisecond, follows the timestamp and is incremented for every
+/// UUID of the same millisecond. When the clock goes backwards the last
+/// timestamp is kept; when the counter overflows the timestamp is advanced
+/// by one millisecond, so consecutive UUIDs always increase. See
+/// [`V7Method`] for the monotonic random method.
+///
+/// With a [`StateStore`] the ordering also survives restarts, see
+/// [`V7Generator::with_state`].
+pub struct V7Generator {
+    method: V7Method,
+    clock: Box<dyn Clock + Send + Sync>,
+    rng: Option<Mutex<Box<dyn RngCore + Send>>>,
+    last: Mutex<State>,
+    metrics: Arc<GeneratorMetrics>,
+    store: Option<Box<dyn StateStore + Send + Sync>>,
+}
+
+impl Default for V7Generator {
+    fn default() -> Self {
+        Self {
+            method: V7Method::default(),
+            clock: Box::new(SystemClock),
+            rng: None,
+            last: Mutex::default(),
+            metrics: Arc::default(),
+            store: None,
+        }
+    }
+}
+
+impl fmt::Debug for V7Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("V7Generator")
+            .field("method", &self.method)
+            .field("custom_rng", &self.rng.is_some())
+            .field("last", &self.last)
+            .field("metrics", &self.metrics)
+            .field("persistent", &self.store.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl V7Generator {
+    /// New generator using the dedicated counter method.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New generator using `method`.
+    pub fn with_method(method: V7Method) -> Self {
+        Self {
+            method,
+            ..Self::default()
+        }
+    }
+
+    /// Use `clock` instead of the system clock.
+    pub fn with_clock(self, clock: impl Clock + Send + Sync + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            ..self
+        }
+    }
+
+    /// Draw the random bits from `rng` instead of the generator of the
+    /// calling thread, see [`UUID::v4_from_rng`](crate::UUID::v4_from_rng).
+    pub fn with_rng(self, rng: impl RngCore + Send + 'static) -> Self {
+        self.with_boxed_rng(Box::new(rng))
+    }
+
+    pub(crate) fn with_boxed_rng(self, rng: Box<dyn RngCore + Send>) -> Self {
+        Self {
+            rng: Some(Mutex::new(rng)),
+            ..self
+        }
+    }
+
+    /// Persist the last timestamp and counter to `store`, resuming from what
+    /// it holds, so UUIDs issued after a restart sort above those before.
+    ///
+    /// The saved timestamp is kept ten seconds ahead of the UUIDs issued, so
+    /// the store is written about once per ten seconds, and a restarted
+    /// generator continues from it until the clock catches up.
+    pub fn with_state<S: StateStore + Send + Sync + 'static>(
+        mut self,
+        store: S,
+    ) -> Result<Self, UuidError> {
+        let last = self.last.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Some(saved) = store.load()? {
+            last.ms = saved.last_timestamp;
+            last.counter = saved.counter;
+        }
+        last.saved_until = 0;
+        self.store = Some(Box::new(store));
+        Ok(self)
+    }
+
+    /// Method used to order UUIDs of the same millisecond.
+    pub fn method(&self) -> V7Method {
+        self.method
+    }
+
+    /// Clock regressions and counter rollovers seen by the generator.
+    pub fn metrics(&self) -> &GeneratorMetrics {
+        &self.metrics
+    }
+
+    /// Endless new UUIDs of this generator, strictly increasing.
+    ///
+    /// ```rust
+    /// use simple_uuid::V7Generator;
+    ///
+    /// let generator = V7Generator::new();
+    /// let ids: Vec<_> = generator.iter().take(3).collect();
+    /// assert!(ids[0] < ids[1] && ids[1] < ids[2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics when the clock or the random number generator
+    /// fails; use [`Generator::generate`] to handle the failure.
+    pub fn iter(&self) -> impl Iterator<Item = UUID> + '_ {
+        std::iter::repeat_with(move || self.generate().expect("version-7 generation failed"))
+    }
+
+    // Next UUID at time `ms` with random bits `rand`, or `None` to wait for
+    // the next millisecond.
+    fn next(&self, ms: u64, rand: u128) -> Option<UUID> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        if ms < last.clock_ms {
+            self.metrics.record_clock_regression();
+        }
+        last.clock_ms = ms;
+        let uuid = match self.method {
+            V7Method::Counter => self.next_counter(&mut last, ms, rand),
+            V7Method::RandomIncrement(policy) => self.next_random(&mut last, ms, rand, policy)?,
+        };
+        Some(uuid)
+    }
+
+    fn next_counter(&self, last: &mut State, ms: u64, rand: u128) -> UUID {
+        if ms > last.ms {
+            last.ms = ms;
+            last.counter = counter_seed((rand >> 64) as u64);
+        } else if last.counter == COUNTER_MAX {
+            self.metrics.record_counter_rollover();
+            last.ms += 1;
+            last.counter = 0;
+        } else {
+            last.counter += 1;
+        }
+        counter_uuid(last.ms, last.counter, rand as u32)
+    }
+
+    fn next_random(
+        &self,
+        last: &mut State,
+        ms: u64,
+        rand: u128,
+        policy: RolloverPolicy,
+    ) -> Option<UUID> {
+        let rand_b = rand as u64 & RAND_B_MAX;
+        let seed_a = (rand >> 64) as u64 & (RAND_A_MAX >> 1);
+        let step_a = 1 + ((rand >> 76) as u64 & 0x7);
+        let step_b = 1 + (rand >> 96) as u64;
+
+        if ms > last.ms {
+            *last = State {
+                ms,
+                counter: seed_a,
+                rand_b,
+                rolled_over: false,
+                clock_ms: ms,
+                saved_until: last.saved_until,
+            };
+        } else if !last.rolled_over && last.counter + step_a <= RAND_A_MAX {
+            last.counter += step_a;
+            last.rand_b = rand_b;
+        } else {
+            if !last.rolled_over {
+                self.metrics.record_counter_rollover();
+                last.rolled_over = true;
+            }
+            match policy {
+                RolloverPolicy::Freeze => return None,
+                RolloverPolicy::BorrowRandB if last.rand_b + step_b <= RAND_B_MAX => {
+                    last.counter = RAND_A_MAX;
+                    last.rand_b += step_b;
+                }
+                _ => {
+                    *last = State {
+                        ms: last.ms + 1,
+                        counter: seed_a,
+                        rand_b,
+                        rolled_over: false,
+                        clock_ms: last.clock_ms,
+                        saved_until: last.saved_until,
+                    };
+                }
+            }
+        }
+
+        Some(UUID::from_u128(
+            u128::from(last.ms) << 80
+                | u128::from(Version::UNIX as u8) << 76
+                | u128::from(last.counter) << 64
+                | 0b10 << 62
+                | u128::from(last.rand_b),
+        ))
+    }
+
+    // Save a timestamp ahead of the last UUID once it reaches the saved one.
+    fn save_ahead(&self) -> Result<(), UuidError> {
+        let store = match &self.store {
+            Some(store) => store,
+            None => return Ok(()),
+        };
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        if last.ms >= last.saved_until {
+            let saved_until = last.ms + SAVE_AHEAD_MS;
+            store.save(&GeneratorState {
+                last_timestamp: saved_until,
+                counter: last.counter,
+                ..GeneratorState::default()
+            })?;
+            last.saved_until = saved_until;
+        }
+        Ok(())
+    }
+}
+
+/// Random start of a 42-bit counter, with its top bit clear.
+pub(crate) fn counter_seed(rand: u64) -> u64 {
+    rand & (COUNTER_MAX >> 1)
+}
+
+/// UUID of the dedicated counter method at time `ms`, with the 42-bit
+/// `counter` and 32 random bits.
+pub(crate) fn counter_uuid(ms: u64, counter: u64, rand: u32) -> UUID {
+    UUID::from_u128(
+        u128::from(ms) << 80
+            | u128::from(Version::UNIX as u8) << 76
+            | u128::from(counter >> COUNTER_LOW_BITS) << 64
+            | 0b10 << 62
+            | u128::from(counter & ((1 << COUNTER_LOW_BITS) - 1)) << 32
+            | u128::from(rand),
+    )
+}
+
+impl Generator for V7Generator {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        let mut rand = [0u8; 16];
+        match &self.rng {
+            Some(rng) => rng
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .try_fill_bytes(&mut rand)
+                .map_err(|_| UuidError::Entropy)?,
+            None => entropy::fill(&mut rand)?,
+        }
+        let rand = u128::from_be_bytes(rand);
+
+        let mut waited = Duration::ZERO;
+        loop {
+            let ticks = self.clock.try_now_100ns()?;
+            if let Some(uuid) = self.next(ticks / 10_000, rand) {
+                self.save_ahead()?;
+                return Ok(uuid);
+            }
+            if waited >= MAX_FREEZE_WAIT {
+                return Err(UuidError::Clock);
+            }
+            // Sleep until the clock reaches its next millisecond.
+            let wait = Duration::from_nanos((10_000 - ticks % 10_000) * 100);
+            thread::sleep(wait);
+            waited += wait;
+        }
+    }
+
+    fn shared_metrics(&self) -> Option<Arc<GeneratorMetrics>> {
+        Some(Arc::clone(&self.metrics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GeneratorRouter;
+
+    #[test]
+    fn strictly_increasing() {
+        let g = V7Generator::new();
+        let mut last = g.generate().unwrap();
+        assert_eq!(last.decode().get_version(), Some(Version::UNIX));
+        for _ in 0..10_000 {
+            let next = g.generate().unwrap();
+            assert!(next > last);
+            last = next;
+        }
+        for next in g.iter().take(10_000) {
+            assert!(next > last);
+            last = next;
+        }
+    }
+
+    #[test]
+    fn custom_clock() {
+        let g = V7Generator::new().with_clock(|| 16_455_577_420_000_000);
+        let a = g.generate().unwrap();
+        let b = g.generate().unwrap();
+        assert!(b > a);
+        assert_eq!(b.timestamp_ms(), Some(1_645_557_742_000));
+
+        let seeded = || {
+            use rand_core::SeedableRng;
+            V7Generator::new()
+                .with_clock(|| 16_455_577_420_000_000)
+                .with_rng(rand_chacha::ChaCha20Rng::seed_from_u64(7))
+        };
+        let (c, d) = (seeded(), seeded());
+        for _ in 0..10 {
+            assert_eq!(c.generate(), d.generate());
+        }
+    }
+
+    #[test]
+    fn clock_regression_and_rollover() {
+        let g = V7Generator::new();
+        let a = g.next(1_000, u128::MAX).unwrap();
+        assert_eq!(g.last.lock().unwrap().counter, COUNTER_MAX >> 1);
+        let b = g.next(999, 0).unwrap();
+        assert!(b > a);
+        assert_eq!(b.timestamp_ms(), Some(1_000));
+        assert_eq!(g.metrics().clock_regressions(), 1);
+
+        g.last.lock().unwrap().counter = COUNTER_MAX;
+        let c = g.next(1_000, 0).unwrap();
+        assert!(c > b);
+        assert_eq!(c.timestamp_ms(), Some(1_001));
+        assert_eq!(g.metrics().counter_rollovers(), 1);
+
+        // Running ahead of the clock is not a regression.
+        assert!(g.next(1_000, 0).unwrap() > c);
+        assert_eq!(g.metrics().clock_regressions(), 1);
+    }
+
+    #[test]
+    fn resume_from_state() {
+        use crate::state::MemoryStore;
+
+        let store = Arc::new(MemoryStore::new());
+        let start = || {
+            V7Generator::new()
+                .with_clock(|| 16_455_577_420_000_000)
+                .with_state(Arc::clone(&store))
+                .unwrap()
+        };
+        let g = start();
+        let before: Vec<_> = (0..100).map(|_| g.generate().unwrap()).collect();
+        let saved = store.load().unwrap().unwrap();
+        assert_eq!(saved.last_timestamp, 1_645_557_742_000 + SAVE_AHEAD_MS);
+
+        // Restarted with the clock where it was: continues above the saved
+        // timestamp.
+        let g = start();
+        let after = g.generate().unwrap();
+        assert!(before.iter().all(|uuid| *uuid < after));
+        assert_eq!(after.timestamp_ms(), Some(saved.last_timestamp));
+    }
+
+    #[test]
+    fn freeze_gives_up_on_a_stopped_clock() {
+        let g = V7Generator::with_method(V7Method::RandomIncrement(RolloverPolicy::Freeze))
+            .with_clock(|| 16_455_577_420_000_000);
+        let failure = (0..5_000).map(|_| g.generate()).find(Result::is_err);
+        assert_eq!(failure, Some(Err(UuidError::Clock)));
+    }
+
+    // Generates a burst of 1M UUIDs while the simulated clock reads 1000 ms,
+    // moving the clock only when the generator asks to wait.
+    fn burst(policy: RolloverPolicy) -> (V7Generator, Vec<UUID>) {
+        let g = V7Generator::with_method(V7Method::RandomIncrement(policy));
+        let (mut ms, mut x) = (1_000, 0x9e37_79b9_7f4a_7c15_u64);
+        let mut rand = || {
+            let mut word = || {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x
+            };
+            u128::from(word()) << 64 | u128::from(word())
+        };
+        let mut ids = Vec::with_capacity(1_000_000);
+        while ids.len() < 1_000_000 {
+            match g.next(ms, rand()) {
+                Some(uuid) => ids.push(uuid),
+                None => ms += 1,
+            }
+        }
+        (g, ids)
+    }
+
+    #[test]
+    fn random_increment_bursts_stay_ordered() {
+        for policy in [
+            RolloverPolicy::Freeze,
+            RolloverPolicy::AdvanceTimestamp,
+            RolloverPolicy::BorrowRandB,
+        ] {
+            let (g, ids) = burst(policy);
+            assert!(ids.windows(2).all(|w| w[0] < w[1]), "{:?}", policy);
+            assert!(ids
+                .iter()
+                .all(|id| id.decode().get_version() == Some(Version::UNIX)));
+            let last_ms = ids.last().unwrap().timestamp_ms().unwrap();
+            match policy {
+                RolloverPolicy::BorrowRandB => {
+                    assert_eq!(last_ms, 1_000);
+                    assert_eq!(g.metrics().counter_rollovers(), 1);
+                }
+                _ => {
+                    assert!(last_ms > 1_000);
+                    assert_eq!(g.metrics().counter_rollovers(), last_ms - 1_000);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn routed_and_shared_between_threads() {
+        let router = Arc::new(GeneratorRouter::new(|_: &u8| {
+            V7Generator::with_method(V7Method::RandomIncrement(RolloverPolicy::Freeze))
+        }));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let router = Arc::clone(&router);
+                thread::spawn(move || {
+                    (0..1_000)
+                        .map(|_| router.for_tenant(&1).generate().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut all = Vec::new();
+        for h in handles {
+            let ids = h.join().unwrap();
+            assert!(ids.windows(2).all(|w| w[0] < w[1]));
+            all.extend(ids);
+        }
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 4_000);
+    }
+}
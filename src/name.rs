@@ -1,11 +1,159 @@
-#![cfg(any(feature = "hash_md5", feauture = "hash_sha1"))]
+#![cfg(any(feature = "hash_md5", feature = "hash_sha1", feature = "hash_digest"))]
 
+use core::fmt;
+use std::convert::TryFrom;
+#[cfg(any(feature = "hash_sha1", feature = "hash_digest"))]
 use std::convert::TryInto;
+#[cfg(feature = "hash_sha1")]
+use std::io::{self, Read};
 
-use md5;
+#[cfg(feature = "hash_sha1")]
 use sha1::Sha1;
 
-use crate::{Layout, Node, Variant, Version, UUID};
+use crate::fields::{VARIANT_BYTE, VARIANT_MASK, VARIANT_RFC};
+use crate::{Layout, Node, Version, UUID};
+
+/// A namespace UUID checked to be of the `rfc4122` variant, for name-based UUIDs.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Namespace(UUID);
+
+/// The namespace UUID is not of the `rfc4122` variant.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct NamespaceError(pub UUID);
+
+impl fmt::Display for NamespaceError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "namespace {:x} is not an rfc4122 variant UUID", self.0)
+    }
+}
+
+impl std::error::Error for NamespaceError {}
+
+impl Namespace {
+    /// Namespace for domain name system (DNS).
+    pub const DNS: Namespace = Namespace(UUID::NAMESPACE_DNS);
+    /// Namespace for ISO object identifiers (OIDs).
+    pub const OID: Namespace = Namespace(UUID::NAMESPACE_OID);
+    /// Namespace for uniform resource locators (URLs).
+    pub const URL: Namespace = Namespace(UUID::NAMESPACE_URL);
+    /// Namespace for X.500 distinguished names (DNs).
+    pub const X500: Namespace = Namespace(UUID::NAMESPACE_X500);
+
+    /// The namespace UUID.
+    pub const fn as_uuid(&self) -> UUID {
+        self.0
+    }
+
+    /// New UUID version-3 of `data` in this namespace.
+    #[cfg(feature = "hash_md5")]
+    #[doc(cfg(feature = "hash_md5"))]
+    pub fn md5(&self, data: &str) -> Layout {
+        let hash = md5::compute(UUID::concat(data, self.0)).0;
+        Layout::hash_fields(hash, Version::MD5)
+    }
+
+    /// New UUID version-5 of `data` in this namespace.
+    #[cfg(feature = "hash_sha1")]
+    #[doc(cfg(feature = "hash_sha1"))]
+    pub fn sha1(&self, data: &str) -> Layout {
+        let hash = Sha1::from(UUID::concat(data, self.0)).digest().bytes()[..16]
+            .try_into()
+            .unwrap();
+        Layout::hash_fields(hash, Version::SHA1)
+    }
+
+    /// New UUID version-3 of the octets of this namespace followed by `name`,
+    /// as specified by `rfc4122`.
+    #[cfg(feature = "hash_md5")]
+    #[doc(cfg(feature = "hash_md5"))]
+    pub fn md5_bytes(&self, name: &[u8]) -> Layout {
+        let mut ctx = md5::Context::new();
+        ctx.consume(self.0 .0);
+        ctx.consume(name);
+        Layout::hash_fields(ctx.compute().0, Version::MD5)
+    }
+
+    /// New UUID version-5 of the octets of this namespace followed by `name`,
+    /// as specified by `rfc4122`.
+    #[cfg(feature = "hash_sha1")]
+    #[doc(cfg(feature = "hash_sha1"))]
+    pub fn sha1_bytes(&self, name: &[u8]) -> Layout {
+        let mut sha1 = Sha1::new();
+        sha1.update(&self.0 .0);
+        sha1.update(name);
+        let hash = sha1.digest().bytes()[..16].try_into().unwrap();
+        Layout::hash_fields(hash, Version::SHA1)
+    }
+
+    /// Same as [`Namespace::sha1_bytes`] with the name read from `reader`
+    /// in chunks, for names too large to hold in memory.
+    #[cfg(feature = "hash_sha1")]
+    #[doc(cfg(feature = "hash_sha1"))]
+    pub fn sha1_reader<R: Read>(&self, mut reader: R) -> io::Result<Layout> {
+        let mut sha1 = Sha1::new();
+        sha1.update(&self.0 .0);
+        let mut buf = [0u8; 8192];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => sha1.update(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        let hash = sha1.digest().bytes()[..16].try_into().unwrap();
+        Ok(Layout::hash_fields(hash, Version::SHA1))
+    }
+
+    /// New UUID version-8 of the first 128 bits of the SHA-256 of the octets
+    /// of this namespace followed by `name`, as in the appendix of `rfc9562`.
+    #[cfg(feature = "hash_sha256")]
+    #[doc(cfg(feature = "hash_sha256"))]
+    pub fn sha256(&self, name: &[u8]) -> Layout {
+        self.digest::<sha2::Sha256>(name)
+    }
+
+    /// New name-based UUID of the first 128 bits of the `D` digest of the
+    /// octets of this namespace followed by `name`.
+    ///
+    /// The version is 3 for 128-bit digests such as MD5, 5 for 160-bit
+    /// digests such as SHA-1, and 8 for other sizes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digest is shorter than 128 bits.
+    #[cfg(feature = "hash_digest")]
+    #[doc(cfg(feature = "hash_digest"))]
+    pub fn digest<D: digest::Digest>(&self, name: &[u8]) -> Layout {
+        let version = match <D as digest::Digest>::output_size() {
+            16 => Version::MD5,
+            20 => Version::SHA1,
+            _ => Version::CUSTOM,
+        };
+        let digest = D::new()
+            .chain_update(self.0 .0)
+            .chain_update(name)
+            .finalize();
+        Layout::hash_fields(digest[..16].try_into().unwrap(), version)
+    }
+}
+
+impl TryFrom<UUID> for Namespace {
+    type Error = NamespaceError;
+
+    fn try_from(uuid: UUID) -> Result<Self, NamespaceError> {
+        match uuid.0[VARIANT_BYTE] & VARIANT_MASK {
+            VARIANT_RFC => Ok(Namespace(uuid)),
+            _ => Err(NamespaceError(uuid)),
+        }
+    }
+}
+
+impl From<Namespace> for UUID {
+    fn from(ns: Namespace) -> Self {
+        ns.0
+    }
+}
 
 impl Layout {
     fn hash_fields(hash: [u8; 16], v: Version) -> Self {
@@ -17,62 +165,112 @@ impl Layout {
             field_mid: (hash[4] as u16) << 8 | (hash[5] as u16),
             field_high_and_version: ((hash[6] as u16) << 8 | (hash[7] as u16)) & 0xfff
                 | (v as u16) << 12,
-            clock_seq_high_and_reserved: (hash[8] & 0xf) | (Variant::RFC as u8) << 4,
-            clock_seq_low: hash[9] as u8,
+            clock_seq_high_and_reserved: (hash[8] & 0x3f) | VARIANT_RFC,
+            clock_seq_low: hash[9],
             node: Node([hash[10], hash[11], hash[12], hash[13], hash[14], hash[15]]),
         }
     }
 }
 
 impl UUID {
-    /// New UUID version-3 using md5 algorithme
+    /// New UUID version-3 using md5 algorithme, failing if `ns` is not
+    /// an `rfc4122` variant UUID
+    ///
+    /// The hyphen-less hex text of `ns` is hashed rather than its octets;
+    /// [`UUID::v3_bytes`] matches other UUID libraries.
+    #[cfg(feature = "hash_md5")]
     #[doc(cfg(feature = "hash_md5"))]
-    pub fn using_md5(data: &str, ns: UUID) -> Layout {
-        let hash = md5::compute(Self::concat(data, ns)).0;
-        Layout::hash_fields(hash, Version::MD5)
+    pub fn using_md5(data: &str, ns: UUID) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.md5(data))
     }
 
-    /// New UUID version-5 using sha1 algorithme
+    /// New UUID version-5 using sha1 algorithme, failing if `ns` is not
+    /// an `rfc4122` variant UUID
+    ///
+    /// The hyphen-less hex text of `ns` is hashed rather than its octets;
+    /// [`UUID::v5_bytes`] matches other UUID libraries.
+    #[cfg(feature = "hash_sha1")]
     #[doc(cfg(feature = "hash_sha1"))]
-    pub fn using_sha1(data: &str, ns: UUID) -> Layout {
-        let hash = Sha1::from(Self::concat(data, ns)).digest().bytes()[..16]
-            .try_into()
-            .unwrap();
-        Layout::hash_fields(hash, Version::SHA1)
+    pub fn using_sha1(data: &str, ns: UUID) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.sha1(data))
     }
 
-    fn concat(data: &str, ns: UUID) -> String {
-        format!("{:x}", ns) + data
+    /// New UUID version-3 of the octets of `ns` followed by the raw `name`,
+    /// failing if `ns` is not an `rfc4122` variant UUID
+    #[cfg(feature = "hash_md5")]
+    #[doc(cfg(feature = "hash_md5"))]
+    pub fn v3_bytes(name: &[u8], ns: UUID) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.md5_bytes(name))
+    }
+
+    /// New UUID version-5 of the octets of `ns` followed by the raw `name`,
+    /// failing if `ns` is not an `rfc4122` variant UUID
+    #[cfg(feature = "hash_sha1")]
+    #[doc(cfg(feature = "hash_sha1"))]
+    pub fn v5_bytes(name: &[u8], ns: UUID) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.sha1_bytes(name))
+    }
+
+    /// New UUID version-5 of the octets of `ns` followed by everything read
+    /// from `reader`, failing with [`io::ErrorKind::InvalidInput`] if `ns` is
+    /// not an `rfc4122` variant UUID
+    #[cfg(feature = "hash_sha1")]
+    #[doc(cfg(feature = "hash_sha1"))]
+    pub fn v5_from_reader<R: Read>(ns: UUID, reader: R) -> io::Result<Layout> {
+        Namespace::try_from(ns)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .sha1_reader(reader)
+    }
+
+    /// New UUID version-8 from the SHA-256 of the octets of `ns` followed by
+    /// `name`, failing if `ns` is not an `rfc4122` variant UUID
+    #[cfg(feature = "hash_sha256")]
+    #[doc(cfg(feature = "hash_sha256"))]
+    pub fn v8_sha256(name: &[u8], ns: UUID) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.sha256(name))
+    }
+
+    /// New name-based UUID hashing `ns` and `name` with the digest `D`, failing
+    /// if `ns` is not an `rfc4122` variant UUID; see [`Namespace::digest`]
+    #[cfg(feature = "hash_digest")]
+    #[doc(cfg(feature = "hash_digest"))]
+    pub fn new_name_based<D: digest::Digest>(
+        ns: UUID,
+        name: &[u8],
+    ) -> Result<Layout, NamespaceError> {
+        Namespace::try_from(ns).map(|ns| ns.digest::<D>(name))
     }
-}
 
-impl ToString for Layout {
-    fn to_string(&self) -> String {
-        format!("{:02x}", self.as_bytes(),)
+    #[cfg(any(feature = "hash_md5", feature = "hash_sha1"))]
+    fn concat(data: &str, ns: UUID) -> String {
+        format!("{:x}", ns) + data
     }
 }
 
 /// `UUID` version-3
+#[cfg(feature = "hash_md5")]
 #[doc(cfg(feature = "hash_md5"))]
 #[macro_export]
 macro_rules! v3 {
     ($data:expr, $ns:expr) => {
-        format!("{:x}", $crate::UUID::using_md5($data, $ns).as_bytes())
+        $crate::UUID::using_md5($data, $ns).map(|uuid| format!("{:x}", uuid.to_uuid()))
     };
 }
 
 /// `UUID` version-5
+#[cfg(feature = "hash_sha1")]
 #[doc(cfg(feature = "hash_sha1"))]
 #[macro_export]
 macro_rules! v5 {
     ($data:expr, $ns:expr) => {
-        format!("{:x}", $crate::UUID::using_sha1($data, $ns).as_bytes())
+        $crate::UUID::using_sha1($data, $ns).map(|uuid| format!("{:x}", uuid.to_uuid()))
     };
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "hash_md5", feature = "hash_sha1"))]
 mod tests {
     use super::*;
+    use crate::Variant;
 
     #[test]
     fn new_uuid_using_md5() {
@@ -85,11 +283,11 @@ mod tests {
 
         for s in ns.iter() {
             assert_eq!(
-                UUID::using_md5("test_data", *s).get_version(),
+                UUID::using_md5("test_data", *s).unwrap().get_version(),
                 Some(Version::MD5)
             );
             assert_eq!(
-                UUID::using_md5("test_data", *s).get_variant(),
+                UUID::using_md5("test_data", *s).unwrap().get_variant(),
                 Some(Variant::RFC)
             );
         }
@@ -106,13 +304,106 @@ mod tests {
 
         for s in ns.iter() {
             assert_eq!(
-                UUID::using_sha1("test_data", *s).get_version(),
+                UUID::using_sha1("test_data", *s).unwrap().get_version(),
                 Some(Version::SHA1)
             );
             assert_eq!(
-                UUID::using_sha1("test_data", *s).get_variant(),
+                UUID::using_sha1("test_data", *s).unwrap().get_variant(),
                 Some(Variant::RFC)
             );
         }
     }
+
+    #[test]
+    fn reject_non_rfc_namespace() {
+        let nil = UUID::default();
+        assert_eq!(Namespace::try_from(nil), Err(NamespaceError(nil)));
+        assert_eq!(
+            UUID::using_md5("test_data", nil).err(),
+            Some(NamespaceError(nil))
+        );
+        assert_eq!(
+            UUID::using_sha1("test_data", nil).err(),
+            Some(NamespaceError(nil))
+        );
+        assert!(v3!("test_data", nil).is_err());
+    }
+
+    #[test]
+    fn namespace_validated_once() {
+        let ns = Namespace::try_from(UUID::NAMESPACE_DNS).unwrap();
+        assert_eq!(ns, Namespace::DNS);
+        assert_eq!(
+            ns.sha1("test_data").to_uuid(),
+            UUID::using_sha1("test_data", UUID::NAMESPACE_DNS)
+                .unwrap()
+                .to_uuid()
+        );
+        assert_eq!(
+            v5!("test_data", UUID::NAMESPACE_DNS).unwrap(),
+            format!("{:x}", ns.sha1("test_data").to_uuid())
+        );
+    }
+
+    #[test]
+    fn bytes_match_rfc4122() {
+        assert_eq!(
+            UUID::v3_bytes(b"python.org", UUID::NAMESPACE_DNS)
+                .unwrap()
+                .to_string(),
+            "6fa459ea-ee8a-3ca4-894e-db77e160355e"
+        );
+        assert_eq!(
+            UUID::v5_bytes(b"python.org", UUID::NAMESPACE_DNS)
+                .unwrap()
+                .to_string(),
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d"
+        );
+        assert_eq!(
+            Namespace::URL.sha1_bytes(&[0xff, 0x00]).get_version(),
+            Some(Version::SHA1)
+        );
+        assert!(UUID::v5_bytes(b"python.org", UUID::default()).is_err());
+    }
+
+    #[test]
+    fn v5_from_reader_matches_bytes() {
+        let name = vec![0x5a; 20_000];
+        assert_eq!(
+            UUID::v5_from_reader(UUID::NAMESPACE_URL, &name[..])
+                .unwrap()
+                .to_uuid(),
+            UUID::v5_bytes(&name, UUID::NAMESPACE_URL)
+                .unwrap()
+                .to_uuid()
+        );
+        let err = UUID::v5_from_reader(UUID::default(), io::empty()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(feature = "hash_sha256")]
+    #[test]
+    fn v8_sha256_matches_rfc9562() {
+        let uuid = UUID::v8_sha256(b"www.example.com", UUID::NAMESPACE_DNS).unwrap();
+        assert_eq!(uuid.to_string(), "5c146b14-3c52-8afd-938a-375d0df1fbf6");
+        assert_eq!(uuid.get_version(), Some(Version::CUSTOM));
+        assert!(UUID::v8_sha256(b"www.example.com", UUID::default()).is_err());
+    }
+
+    #[cfg(feature = "hash_digest")]
+    #[test]
+    fn digest_matches_bundled_hashes() {
+        let name = b"python.org";
+        assert_eq!(
+            UUID::new_name_based::<md5_digest::Md5>(UUID::NAMESPACE_DNS, name)
+                .unwrap()
+                .to_uuid(),
+            UUID::v3_bytes(name, UUID::NAMESPACE_DNS).unwrap().to_uuid()
+        );
+        assert_eq!(
+            Namespace::DNS.digest::<sha1_digest::Sha1>(name).to_uuid(),
+            Namespace::DNS.sha1_bytes(name).to_uuid()
+        );
+        assert!(UUID::new_name_based::<md5_digest::Md5>(UUID::default(), name).is_err());
+    }
 }
@@ -0,0 +1,95 @@
+#[cfg(feature = "rand_num")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "rand_num")]
+use rand_core::{OsRng, RngCore};
+
+use crate::{Node, UuidError};
+
+/// Where version-1 and 6 generators take their node from.
+///
+/// The MAC-address of the device identifies the host in every UUID; use
+/// [`RandomNode`] or a fixed [`Node`] to keep hardware addresses private.
+pub trait NodeSource {
+    /// The node of the next UUIDs.
+    fn node(&self) -> Result<Node, UuidError>;
+}
+
+/// The node given by the user.
+impl NodeSource for Node {
+    fn node(&self) -> Result<Node, UuidError> {
+        Ok(*self)
+    }
+}
+
+/// The MAC-address of this device, failing with `UuidError::Node` when it
+/// has none.
+#[cfg(feature = "mac_addr")]
+#[doc(cfg(feature = "mac_addr"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MacAddressNode;
+
+#[cfg(feature = "mac_addr")]
+impl NodeSource for MacAddressNode {
+    fn node(&self) -> Result<Node, UuidError> {
+        crate::time::device_mac_addr()
+    }
+}
+
+/// A random node drawn from the operating system once per process, with
+/// the multicast bit set so it never collides with a MAC-address.
+#[cfg(feature = "rand_num")]
+#[doc(cfg(feature = "rand_num"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomNode;
+
+#[cfg(feature = "rand_num")]
+static RANDOM_NODE: OnceLock<Node> = OnceLock::new();
+
+#[cfg(feature = "rand_num")]
+impl NodeSource for RandomNode {
+    fn node(&self) -> Result<Node, UuidError> {
+        if let Some(node) = RANDOM_NODE.get() {
+            return Ok(*node);
+        }
+        let node = Node::random()?;
+        Ok(*RANDOM_NODE.get_or_init(|| node))
+    }
+}
+
+#[cfg(feature = "rand_num")]
+impl Node {
+    /// New random node with the multicast bit, the least significant bit of
+    /// the first octet, set as `rfc4122` 4.5 asks of nodes not taken from a
+    /// network card.
+    pub fn random() -> Result<Node, UuidError> {
+        let mut node = [0u8; 6];
+        OsRng
+            .try_fill_bytes(&mut node)
+            .map_err(|_| UuidError::Entropy)?;
+        node[0] |= 0x01;
+        Ok(Node(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_node() {
+        let node = Node([1, 2, 3, 4, 5, 6]);
+        assert_eq!(node.node(), Ok(node));
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn random_node_is_stable_multicast() {
+        assert_eq!(Node::random().unwrap().0[0] & 0x01, 0x01);
+        assert_ne!(Node::random(), Node::random());
+
+        let node = RandomNode.node().unwrap();
+        assert_eq!(node.0[0] & 0x01, 0x01);
+        assert_eq!(RandomNode.node(), Ok(node));
+    }
+}
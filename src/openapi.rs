@@ -0,0 +1,122 @@
+//! OpenAPI schema of `UUID` for `utoipa` and `poem-openapi`.
+//!
+//! Both describe a `string` of `format: uuid`; `poem-openapi` inputs are
+//! parsed in the simple, hyphenated, braced or urn form.
+#![cfg(any(feature = "utoipa", feature = "poem-openapi"))]
+
+#[cfg(feature = "poem-openapi")]
+use std::borrow::Cow;
+
+#[cfg(feature = "poem-openapi")]
+use crate::ParseOptions;
+use crate::UUID;
+
+#[cfg(feature = "utoipa")]
+#[doc(cfg(feature = "utoipa"))]
+impl utoipa::PartialSchema for UUID {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, SchemaFormat, Type};
+
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::Custom("uuid".into())))
+            .into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+#[doc(cfg(feature = "utoipa"))]
+impl utoipa::ToSchema for UUID {}
+
+#[cfg(feature = "poem-openapi")]
+#[doc(cfg(feature = "poem-openapi"))]
+impl poem_openapi::types::Type for UUID {
+    const IS_REQUIRED: bool = true;
+
+    type RawValueType = Self;
+
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        "string_uuid".into()
+    }
+
+    fn schema_ref() -> poem_openapi::registry::MetaSchemaRef {
+        use poem_openapi::registry::{MetaSchema, MetaSchemaRef};
+
+        MetaSchemaRef::Inline(Box::new(MetaSchema::new_with_format("string", "uuid")))
+    }
+
+    fn as_raw_value(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self> + 'a> {
+        Box::new(self.as_raw_value().into_iter())
+    }
+}
+
+#[cfg(feature = "poem-openapi")]
+#[doc(cfg(feature = "poem-openapi"))]
+impl poem_openapi::types::ParseFromJSON for UUID {
+    fn parse_from_json(value: Option<serde_json::Value>) -> poem_openapi::types::ParseResult<Self> {
+        match value.unwrap_or_default() {
+            serde_json::Value::String(s) => {
+                <UUID as poem_openapi::types::ParseFromParameter>::parse_from_parameter(&s)
+            }
+            value => Err(poem_openapi::types::ParseError::expected_type(value)),
+        }
+    }
+}
+
+#[cfg(feature = "poem-openapi")]
+#[doc(cfg(feature = "poem-openapi"))]
+impl poem_openapi::types::ParseFromParameter for UUID {
+    fn parse_from_parameter(value: &str) -> poem_openapi::types::ParseResult<Self> {
+        UUID::parse_with(value, &ParseOptions::new())
+            .map_err(poem_openapi::types::ParseError::custom)
+    }
+}
+
+#[cfg(feature = "poem-openapi")]
+#[doc(cfg(feature = "poem-openapi"))]
+impl poem_openapi::types::ToJSON for UUID {
+    fn to_json(&self) -> Option<serde_json::Value> {
+        Some(serde_json::Value::String(self.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn utoipa_schema_is_uuid_string() {
+        use utoipa::PartialSchema;
+
+        let schema = serde_json::to_value(UUID::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["format"], "uuid");
+        assert_eq!(<UUID as utoipa::ToSchema>::name(), "UUID");
+    }
+
+    #[cfg(feature = "poem-openapi")]
+    #[test]
+    fn poem_openapi_round_trip() {
+        use poem_openapi::types::{ParseFromJSON, ParseFromParameter, ToJSON, Type};
+
+        let json = UUID::NAMESPACE_DNS.to_json().unwrap();
+        assert_eq!(json, "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+        assert_eq!(
+            UUID::parse_from_json(Some(json)).ok(),
+            Some(UUID::NAMESPACE_DNS)
+        );
+        assert_eq!(
+            UUID::parse_from_parameter("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8").ok(),
+            Some(UUID::NAMESPACE_DNS)
+        );
+        assert!(UUID::parse_from_json(Some(serde_json::Value::Bool(true))).is_err());
+        assert_eq!(UUID::name(), "string_uuid");
+    }
+}
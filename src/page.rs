@@ -0,0 +1,157 @@
+#![doc(cfg(feature = "page_token"))]
+#![cfg(feature = "page_token")]
+
+use core::fmt;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::UUID;
+
+// Format of the token, leading the signed bytes.
+const TOKEN_VERSION: u8 = 1;
+
+const TAG_LEN: usize = 32;
+
+/// Keyset-pagination cursor: the last UUID of a page and opaque state of
+/// the caller, such as the sort order or a filter.
+///
+/// The token handed to clients is base64url text signed with HMAC-SHA256,
+/// so they can pass it back but not forge or alter it. It is not encrypted:
+/// clients can read the UUID and the extra bytes.
+///
+/// ```rust
+/// use simple_uuid::{PageToken, UUID};
+///
+/// let key = b"server secret";
+/// let token = PageToken::new(UUID::NAMESPACE_DNS, b"asc").sign(key);
+/// let page = PageToken::parse(&token, key).unwrap();
+/// assert_eq!(page.last_seen(), UUID::NAMESPACE_DNS);
+/// assert_eq!(page.extra(), b"asc");
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct PageToken {
+    last_seen: UUID,
+    extra: Vec<u8>,
+}
+
+/// Error parsing a `PageToken`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum PageTokenError {
+    /// Not base64url text of a token.
+    Malformed,
+    /// The signature does not match the key.
+    Signature,
+}
+
+impl fmt::Display for PageTokenError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageTokenError::Malformed => write!(fmt, "malformed page token"),
+            PageTokenError::Signature => write!(fmt, "invalid page token signature"),
+        }
+    }
+}
+
+impl std::error::Error for PageTokenError {}
+
+impl PageToken {
+    /// New cursor after `last_seen`, carrying `extra`.
+    pub fn new(last_seen: UUID, extra: &[u8]) -> Self {
+        Self {
+            last_seen,
+            extra: extra.to_vec(),
+        }
+    }
+
+    /// Last UUID of the page.
+    pub fn last_seen(&self) -> UUID {
+        self.last_seen
+    }
+
+    /// State given to `PageToken::new`.
+    pub fn extra(&self) -> &[u8] {
+        &self.extra
+    }
+
+    /// Token text signed with `key`.
+    pub fn sign(&self, key: &[u8]) -> String {
+        let mut bytes = Vec::with_capacity(1 + 16 + self.extra.len() + TAG_LEN);
+        bytes.push(TOKEN_VERSION);
+        bytes.extend_from_slice(self.last_seen.as_array());
+        bytes.extend_from_slice(&self.extra);
+        let tag = mac(key, &bytes).finalize().into_bytes();
+        bytes.extend_from_slice(&tag);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Cursor of `token`, checking it was signed with `key`.
+    pub fn parse(token: &str, key: &[u8]) -> Result<PageToken, PageTokenError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| PageTokenError::Malformed)?;
+        if bytes.len() < 1 + 16 + TAG_LEN || bytes[0] != TOKEN_VERSION {
+            return Err(PageTokenError::Malformed);
+        }
+        let (signed, tag) = bytes.split_at(bytes.len() - TAG_LEN);
+        mac(key, signed)
+            .verify_slice(tag)
+            .map_err(|_| PageTokenError::Signature)?;
+        let mut last_seen = [0u8; 16];
+        last_seen.copy_from_slice(&signed[1..17]);
+        Ok(PageToken::new(UUID(last_seen), &signed[17..]))
+    }
+}
+
+fn mac(key: &[u8], bytes: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(bytes);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let uuid = UUID::from_u128(0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+        let token = PageToken::new(uuid, b"").sign(b"key");
+        assert!(token
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+        assert_eq!(
+            PageToken::parse(&token, b"key"),
+            Ok(PageToken::new(uuid, b""))
+        );
+    }
+
+    #[test]
+    fn rejects_tampering() {
+        let token = PageToken::new(UUID::NAMESPACE_URL, b"desc").sign(b"key");
+        assert_eq!(
+            PageToken::parse(&token, b"other key"),
+            Err(PageTokenError::Signature)
+        );
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+        bytes[5] ^= 1;
+        let forged = URL_SAFE_NO_PAD.encode(bytes);
+        assert_eq!(
+            PageToken::parse(&forged, b"key"),
+            Err(PageTokenError::Signature)
+        );
+
+        assert_eq!(
+            PageToken::parse("not a token", b"key"),
+            Err(PageTokenError::Malformed)
+        );
+        assert_eq!(
+            PageToken::parse(&token[..20], b"key"),
+            Err(PageTokenError::Malformed)
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#![doc(cfg(feature = "rayon"))]
+#![cfg(feature = "rayon")]
+
+use rayon::prelude::*;
+
+use crate::entropy;
+use crate::monotonic::{counter_seed, counter_uuid, COUNTER_BITS};
+use crate::{Clock, SystemClock, UuidError, UUID};
+
+/// UUIDs generated by one task of a batch, each drawing its random bits from
+/// the generator of its worker thread.
+const CHUNK: usize = 16 * 1024;
+
+impl UUID {
+    /// `n` new version-4 UUIDs, generated in parallel on the `rayon` thread
+    /// pool.
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let uuids = UUID::par_batch_v4(100_000).unwrap();
+    /// assert_eq!(uuids.len(), 100_000);
+    /// ```
+    pub fn par_batch_v4(n: usize) -> Result<Vec<UUID>, UuidError> {
+        let mut uuids = vec![UUID::default(); n];
+        uuids.par_chunks_mut(CHUNK).try_for_each(UUID::fill_v4)?;
+        Ok(uuids)
+    }
+
+    /// `n` new version-7 UUIDs of the current millisecond, generated in
+    /// parallel on the `rayon` thread pool, strictly increasing.
+    ///
+    /// The batch takes a range of the 42-bit counter of the dedicated counter
+    /// method from a random start, and each worker fills its own part of the
+    /// range, so the workers share no state. As with a
+    /// [`V7Generator`](crate::V7Generator), the timestamp is advanced by one
+    /// millisecond whenever the counter overflows.
+    pub fn par_batch_v7(n: usize) -> Result<Vec<UUID>, UuidError> {
+        let ms = SystemClock.try_now_100ns()? / 10_000;
+        let mut seed = [0u8; 8];
+        entropy::fill(&mut seed)?;
+        let start =
+            u128::from(ms) << COUNTER_BITS | u128::from(counter_seed(u64::from_be_bytes(seed)));
+
+        let mut uuids = vec![UUID::default(); n];
+        uuids.par_chunks_mut(CHUNK).enumerate().try_for_each(
+            |(index, chunk)| -> Result<(), UuidError> {
+                entropy::fill_uuids(chunk)?;
+                let first = start + (index * CHUNK) as u128;
+                for (tick, uuid) in (first..).zip(chunk.iter_mut()) {
+                    let ms = (tick >> COUNTER_BITS) as u64;
+                    let counter = tick as u64 & ((1 << COUNTER_BITS) - 1);
+                    *uuid = counter_uuid(ms, counter, uuid.as_u128() as u32);
+                }
+                Ok(())
+            },
+        )?;
+        Ok(uuids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn par_batches() {
+        let uuids = UUID::par_batch_v4(100_000).unwrap();
+        assert!(uuids.iter().all(|uuid| uuid.get_version_num() == 4));
+        assert_eq!(uuids.iter().collect::<HashSet<_>>().len(), 100_000);
+
+        let before = UUID::v7().unwrap().to_uuid().timestamp_ms();
+        let uuids = UUID::par_batch_v7(100_000).unwrap();
+        assert_eq!(uuids.len(), 100_000);
+        assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+        assert!(uuids.iter().all(|uuid| uuid.get_version_num() == 7));
+        assert!(uuids[0].timestamp_ms() >= before);
+        assert!(UUID::par_batch_v7(0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use crate::format::{decode_formats, decode_groups};
+use crate::{VersionSet, UUID};
+
+/// Error parsing a textual UUID.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The input does not have the expected number of bytes.
+    InvalidLength {
+        /// Expected number of bytes.
+        expected: usize,
+        /// Number of bytes of the input.
+        found: usize,
+    },
+    /// A character that is not expected at its position.
+    InvalidCharacter {
+        /// Byte index of the character.
+        index: usize,
+        /// The offending character.
+        found: char,
+    },
+    /// A missing group separator.
+    InvalidSeparator {
+        /// Byte index where the separator is expected.
+        index: usize,
+        /// The character found instead.
+        found: char,
+    },
+    /// A runtime value that is not a string, such as a JSON number.
+    InvalidType {
+        /// Kind of the value.
+        found: &'static str,
+    },
+    /// A field of a C GUID definition that is missing, extra or out of range.
+    InvalidField {
+        /// Byte index of the field.
+        index: usize,
+    },
+    /// A UUID whose version is not one of `ParseOptions::version_mask`.
+    InvalidVersion {
+        /// Version number of the UUID.
+        found: u8,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidLength { expected, found } => {
+                write!(
+                    fmt,
+                    "invalid length: expected {}, found {}",
+                    expected, found
+                )
+            }
+            ParseError::InvalidCharacter { index, found } => {
+                write!(fmt, "invalid character {:?} at {}", found, index)
+            }
+            ParseError::InvalidSeparator { index, found } => write!(
+                fmt,
+                "invalid group separator at {}, found {:?}",
+                index, found
+            ),
+            ParseError::InvalidType { found } => {
+                write!(fmt, "invalid type: expected a string, found {}", found)
+            }
+            ParseError::InvalidField { index } => write!(fmt, "invalid GUID field at {}", index),
+            ParseError::InvalidVersion { found } => write!(fmt, "unexpected version {}", found),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Options of `UUID::parse_with`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ParseOptions {
+    strict: bool,
+    versions: VersionSet,
+}
+
+impl ParseOptions {
+    /// Accept the simple, hyphenated, braced and urn forms, in any case,
+    /// of any version.
+    pub const fn new() -> Self {
+        Self {
+            strict: false,
+            versions: VersionSet::ANY,
+        }
+    }
+
+    /// Accept only the canonical hyphenated form when `strict` is set.
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept only UUIDs whose version number is in `versions`.
+    pub const fn version_mask(mut self, versions: VersionSet) -> Self {
+        self.versions = versions;
+        self
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UUID {
+    /// Parse the canonical `8-4-4-4-12` form, in any case.
+    pub fn parse_str(s: &str) -> Result<UUID, ParseError> {
+        decode_groups(s, 0, s.len(), Some(b'-'))
+    }
+
+    /// Parse according to `options`; lenient options also accept
+    /// `936da01f9abd4d9d80c702af85c822a8`, `{936da01f-9abd-4d9d-80c7-02af85c822a8}`
+    /// and `urn:uuid:936da01f-9abd-4d9d-80c7-02af85c822a8`, in any case.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<UUID, ParseError> {
+        let uuid = if options.strict {
+            UUID::parse_str(s)?
+        } else {
+            decode_formats(s)?
+        };
+        if !uuid.version_in(options.versions) {
+            return Err(ParseError::InvalidVersion {
+                found: uuid.get_version_num(),
+            });
+        }
+        Ok(uuid)
+    }
+}
+
+impl FromStr for UUID {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<UUID, ParseError> {
+        UUID::parse_str(s)
+    }
+}
+
+// Conversions from the string types found at application boundaries, in the
+// simple, hyphenated, braced or urn form.
+macro_rules! try_from_str {
+    ($($ty:ty => |$s:ident| $as_str:expr;)*) => {
+        $(
+            impl TryFrom<$ty> for UUID {
+                type Error = ParseError;
+
+                fn try_from($s: $ty) -> Result<UUID, ParseError> {
+                    UUID::parse_with($as_str, &ParseOptions::new())
+                }
+            }
+        )*
+    };
+}
+
+try_from_str! {
+    &str => |s| s;
+    String => |s| &s;
+    &String => |s| s;
+    Cow<'_, str> => |s| &s;
+    &OsStr => |s| os_str(s)?;
+    OsString => |s| os_str(&s)?;
+    &Path => |s| os_str(s.as_os_str())?;
+    PathBuf => |s| os_str(s.as_os_str())?;
+}
+
+// The OS string as UTF-8, failing at its first invalid byte.
+fn os_str(s: &OsStr) -> Result<&str, ParseError> {
+    core::str::from_utf8(s.as_encoded_bytes()).map_err(|e| ParseError::InvalidCharacter {
+        index: e.valid_up_to(),
+        found: char::REPLACEMENT_CHARACTER,
+    })
+}
+
+#[cfg(feature = "serde_json")]
+#[doc(cfg(feature = "serde_json"))]
+impl TryFrom<&serde_json::Value> for UUID {
+    type Error = ParseError;
+
+    fn try_from(value: &serde_json::Value) -> Result<UUID, ParseError> {
+        use serde_json::Value;
+
+        let found = match value {
+            Value::String(s) => return UUID::try_from(s),
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        Err(ParseError::InvalidType { found })
+    }
+}
+
+#[cfg(feature = "serde_json")]
+#[doc(cfg(feature = "serde_json"))]
+impl TryFrom<serde_json::Value> for UUID {
+    type Error = ParseError;
+
+    fn try_from(value: serde_json::Value) -> Result<UUID, ParseError> {
+        UUID::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_canonical() {
+        let s = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+        assert_eq!(UUID::parse_str(s), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(s.to_uppercase().parse(), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(
+            format!("{:x}", UUID::NAMESPACE_X500).parse(),
+            Ok(UUID::NAMESPACE_X500)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            UUID::parse_str("6ba7b810"),
+            Err(ParseError::InvalidLength {
+                expected: 36,
+                found: 8
+            })
+        );
+        assert_eq!(
+            UUID::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430cg"),
+            Err(ParseError::InvalidCharacter {
+                index: 35,
+                found: 'g'
+            })
+        );
+        assert_eq!(
+            UUID::parse_str("6ba7b810-9dad-11d1-80b4_00c04fd430c8"),
+            Err(ParseError::InvalidSeparator {
+                index: 23,
+                found: '_'
+            })
+        );
+        assert_eq!(
+            UUID::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430\u{e9}"),
+            Err(ParseError::InvalidCharacter {
+                index: 34,
+                found: '\u{e9}'
+            })
+        );
+    }
+
+    #[test]
+    fn parse_lenient_formats() {
+        let options = ParseOptions::new();
+        let inputs = [
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "6BA7B8109DAD11D180B400C04FD430C8",
+            "{6ba7b810-9dad-11d1-80b4-00c04fd430c8}",
+            "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "URN:UUID:6BA7B810-9DAD-11D1-80B4-00C04FD430C8",
+        ];
+        for s in inputs.iter() {
+            assert_eq!(
+                UUID::parse_with(s, &options),
+                Ok(UUID::NAMESPACE_DNS),
+                "{}",
+                s
+            );
+            assert!(UUID::NAMESPACE_DNS.eq_str(s), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_strict_and_lenient_errors() {
+        let strict = ParseOptions::new().strict(true);
+        assert_eq!(
+            UUID::parse_with("6ba7b8109dad11d180b400c04fd430c8", &strict),
+            Err(ParseError::InvalidLength {
+                expected: 36,
+                found: 32
+            })
+        );
+
+        let lenient = ParseOptions::new();
+        assert_eq!(
+            UUID::parse_with("{6ba7b810-9dad-11d1-80b4-00c04fd430c8", &lenient),
+            Err(ParseError::InvalidCharacter {
+                index: 36,
+                found: '8'
+            })
+        );
+        assert_eq!(
+            UUID::parse_with("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430cx", &lenient),
+            Err(ParseError::InvalidCharacter {
+                index: 44,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            UUID::parse_with("{6ba7b810-9dad-11d1-80b4_00c04fd430c8}", &lenient),
+            Err(ParseError::InvalidSeparator {
+                index: 24,
+                found: '_'
+            })
+        );
+        assert_eq!(
+            UUID::parse_with("6ba7b8109dad11d180b400c04fd430c", &lenient),
+            Err(ParseError::InvalidLength {
+                expected: 36,
+                found: 31
+            })
+        );
+
+        let v4_or_v7 = ParseOptions::new().version_mask(VersionSet::V4 | VersionSet::V7);
+        assert_eq!(
+            UUID::parse_with("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8", &v4_or_v7),
+            Err(ParseError::InvalidVersion { found: 1 })
+        );
+        let s = "017f22e2-79b0-7cc3-98c4-dc0c0c07398f";
+        assert!(UUID::parse_with(s, &v4_or_v7.strict(true)).is_ok());
+    }
+
+    #[test]
+    fn try_from_boundary_types() {
+        let s = "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+        assert_eq!(UUID::try_from(s), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(UUID::try_from(Cow::from(s)), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(UUID::try_from(OsString::from(s)), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(UUID::try_from(PathBuf::from(s)), Ok(UUID::NAMESPACE_DNS));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let bytes = b"6ba7b810-9dad-11d1-80b4-00c04fd430\xff8";
+            assert_eq!(
+                UUID::try_from(OsStr::from_bytes(bytes)),
+                Err(ParseError::InvalidCharacter {
+                    index: 34,
+                    found: '\u{fffd}'
+                })
+            );
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn try_from_json() {
+        let json = serde_json::json!({"id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8", "n": 1});
+        assert_eq!(UUID::try_from(&json["id"]), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(
+            UUID::try_from(json["n"].clone()),
+            Err(ParseError::InvalidType { found: "number" })
+        );
+        assert_eq!(
+            UUID::try_from(&json["missing"]).unwrap_err().to_string(),
+            "invalid type: expected a string, found null"
+        );
+    }
+}
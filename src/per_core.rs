@@ -0,0 +1,221 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::{Generator, GeneratorMetrics, UuidError, Version, UUID};
+
+// Shard of the next thread asking for one.
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Shard hint of the calling thread, handed out round-robin on first use.
+    static SHARD: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+// Generator and counters of one CPU, alone on their cache lines.
+#[repr(align(128))]
+struct Shard {
+    generator: Box<dyn Generator + Send + Sync>,
+    metrics: GeneratorMetrics,
+}
+
+/// One generator per CPU, so threads generating UUIDs at high rates do not
+/// contend on the cache line of a shared generator.
+///
+/// Each thread is assigned a shard once, round-robin, and keeps using it;
+/// with about one busy thread per CPU every shard has a single writer.
+/// UUIDs of different shards are unique but not ordered with each other.
+pub struct PerCoreGenerators {
+    version: Version,
+    shards: Box<[Shard]>,
+}
+
+/// Counters of a [`PerCoreGenerators`], summed over its shards.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct PerCoreStats {
+    /// Number of UUIDs issued.
+    pub issued: u64,
+    /// Number of clock failures.
+    pub clock_regressions: u64,
+    /// Number of random number generator failures.
+    pub rng_failures: u64,
+    /// Number of UUIDs issued by each shard.
+    pub issued_per_shard: Vec<u64>,
+}
+
+impl PerCoreGenerators {
+    /// New generators of `version`, one per CPU available to the process.
+    ///
+    /// Versions 1 and 6 take a [`V1Context`](crate::V1Context) per shard for
+    /// the node of [`UUID::new_from_sys_time`], each with its own clock
+    /// sequence; version 7 takes a [`V7Generator`](crate::V7Generator) per
+    /// shard, and version 4 needs no state. Other versions fail with
+    /// `UuidError::UnsupportedVersion`.
+    pub fn new(version: Version) -> Result<Self, UuidError> {
+        let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(version, cpus)
+    }
+
+    /// New generators of `version` with `shards` shards, at least one.
+    ///
+    /// Versions 1 and 6 fail with `UuidError::TooManyShards` above 16384
+    /// shards, the number of clock sequences.
+    pub fn with_shards(version: Version, shards: usize) -> Result<Self, UuidError> {
+        // Version-1 and 6 shards share the node, so their clock sequences
+        // are spread evenly over the 14 bits from one random start.
+        let shards = shards.max(1);
+        let step = (0x4000 / shards.min(0x4000)) as u16;
+        let start: u16 = match version {
+            #[cfg(feature = "mac_addr")]
+            Version::TIME | Version::REORDERED if shards > 0x4000 => {
+                return Err(UuidError::TooManyShards)
+            }
+            #[cfg(feature = "mac_addr")]
+            Version::TIME | Version::REORDERED => {
+                crate::V1Context::new(crate::Node::default())?.clock_seq()
+            }
+            _ => 0,
+        };
+        let shards = (0..shards)
+            .map(|index| {
+                let clock_seq = start.wrapping_add(step.wrapping_mul(index as u16));
+                Ok(Shard {
+                    generator: shard_generator(version, clock_seq)?,
+                    metrics: GeneratorMetrics::default(),
+                })
+            })
+            .collect::<Result<_, UuidError>>()?;
+        Ok(Self { version, shards })
+    }
+
+    /// Version of the UUIDs.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Number of shards.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Counters of all shards.
+    pub fn stats(&self) -> PerCoreStats {
+        let mut stats = PerCoreStats::default();
+        for shard in self.shards.iter() {
+            stats.issued += shard.metrics.issued();
+            stats.clock_regressions += shard.metrics.clock_regressions();
+            stats.rng_failures += shard.metrics.rng_failures();
+            stats.issued_per_shard.push(shard.metrics.issued());
+        }
+        stats
+    }
+
+    fn shard(&self) -> &Shard {
+        let hint = SHARD.with(|shard| match shard.get() {
+            Some(hint) => hint,
+            None => {
+                let hint = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+                shard.set(Some(hint));
+                hint
+            }
+        });
+        &self.shards[hint % self.shards.len()]
+    }
+}
+
+impl Generator for PerCoreGenerators {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        let shard = self.shard();
+        let result = shard.generator.generate();
+        shard.metrics.record(&result);
+        result
+    }
+}
+
+impl std::fmt::Debug for PerCoreGenerators {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PerCoreGenerators")
+            .field("version", &self.version)
+            .field("shards", &self.shards.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// Generator of one shard, starting version-1 and 6 UUIDs at `clock_seq`.
+#[allow(unused_variables)]
+fn shard_generator(
+    version: Version,
+    clock_seq: u16,
+) -> Result<Box<dyn Generator + Send + Sync>, UuidError> {
+    #[cfg(feature = "mac_addr")]
+    let context = || -> Result<_, UuidError> {
+        Ok(crate::V1Context::with_clock_seq(
+            crate::time::default_node()?,
+            clock_seq,
+        ))
+    };
+    match version {
+        #[cfg(feature = "mac_addr")]
+        Version::TIME => Ok(Box::new(context()?)),
+        #[cfg(feature = "mac_addr")]
+        Version::REORDERED => {
+            let ctx = context()?;
+            Ok(Box::new(move || ctx.v6().map(|uuid| uuid.to_uuid())))
+        }
+        #[cfg(any(feature = "rand_num", feature = "getrandom"))]
+        Version::RAND => Ok(Box::new(UUID::new_v4_forever)),
+        #[cfg(feature = "rand_num")]
+        Version::UNIX => Ok(Box::new(crate::V7Generator::new())),
+        _ => Err(UuidError::UnsupportedVersion(version as u8)),
+    }
+}
+
+#[cfg(all(test, feature = "rand_num"))]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn threads_spread_over_shards() {
+        let generators = Arc::new(PerCoreGenerators::with_shards(Version::UNIX, 4).unwrap());
+        assert_eq!(generators.shards(), 4);
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let generators = Arc::clone(&generators);
+                thread::spawn(move || {
+                    let uuids: Vec<_> =
+                        (0..1_000).map(|_| generators.generate().unwrap()).collect();
+                    assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+                    uuids
+                })
+            })
+            .collect();
+        let mut all = HashSet::new();
+        for handle in handles {
+            all.extend(handle.join().unwrap());
+        }
+        assert_eq!(all.len(), 8_000);
+
+        let stats = generators.stats();
+        assert_eq!(stats.issued, 8_000);
+        assert_eq!(stats.issued_per_shard.iter().sum::<u64>(), 8_000);
+        assert!(stats.issued_per_shard.iter().all(|n| *n >= 1_000));
+    }
+
+    #[test]
+    fn unsupported_versions() {
+        assert_eq!(
+            PerCoreGenerators::new(Version::MD5).unwrap_err(),
+            UuidError::UnsupportedVersion(3)
+        );
+        #[cfg(feature = "mac_addr")]
+        assert_eq!(
+            PerCoreGenerators::with_shards(Version::TIME, 0x4001).unwrap_err(),
+            UuidError::TooManyShards
+        );
+        let v4 = PerCoreGenerators::new(Version::RAND).unwrap();
+        assert!(v4.shards() >= 1);
+        assert_eq!(v4.generate().unwrap().get_version_num(), 4);
+    }
+}
@@ -0,0 +1,39 @@
+//! The items most applications need, in one import.
+//!
+//! ```rust
+//! use simple_uuid::prelude::*;
+//!
+//! let uuid: UUID = "6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse().unwrap();
+//! assert_eq!(uuid.decode().get_version(), Some(Version::TIME));
+//! # #[cfg(any(feature = "rand_num", feature = "getrandom"))]
+//! assert!(v4!().is_ok());
+//! ```
+
+pub use crate::{
+    Clock, Format, Generator, Layout, Node, NodeSource, ParseError, ParseOptions, UuidError,
+    Variant, Version, VersionSet, UUID,
+};
+
+#[cfg(feature = "mac_addr")]
+#[doc(cfg(feature = "mac_addr"))]
+pub use crate::{uuid_v6, v1, V1Context};
+
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+#[doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+pub use crate::{uuid_new, uuid_v7, v4};
+
+#[cfg(feature = "rand_num")]
+#[doc(cfg(feature = "rand_num"))]
+pub use crate::{DeterministicGenerator, V7Generator};
+
+#[cfg(feature = "hash_md5")]
+#[doc(cfg(feature = "hash_md5"))]
+pub use crate::v3;
+
+#[cfg(feature = "hash_sha1")]
+#[doc(cfg(feature = "hash_sha1"))]
+pub use crate::v5;
+
+#[cfg(feature = "macros")]
+#[doc(cfg(feature = "macros"))]
+pub use crate::uuid;
@@ -0,0 +1,108 @@
+//! Generator health in the Prometheus text exposition format.
+//!
+//! Register each `SharedGenerator` once, then serve `gather()` from the
+//! metrics endpoint.
+#![doc(cfg(feature = "prometheus"))]
+#![cfg(feature = "prometheus")]
+
+use core::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::{GeneratorMetrics, SharedGenerator};
+
+static REGISTRY: Mutex<Vec<(Arc<str>, Arc<GeneratorMetrics>)>> = Mutex::new(Vec::new());
+
+/// Name, help text and counter of an exposed metric.
+type Metric = (&'static str, &'static str, fn(&GeneratorMetrics) -> u64);
+
+const METRICS: [Metric; 4] = [
+    (
+        "uuid_ids_issued_total",
+        "UUIDs issued by the generator.",
+        GeneratorMetrics::issued,
+    ),
+    (
+        "uuid_clock_regressions_total",
+        "Clock regressions or failures seen by the generator.",
+        GeneratorMetrics::clock_regressions,
+    ),
+    (
+        "uuid_counter_rollovers_total",
+        "Overflows of the intra-timestamp counter.",
+        GeneratorMetrics::counter_rollovers,
+    ),
+    (
+        "uuid_rng_failures_total",
+        "Failures of the random number generator.",
+        GeneratorMetrics::rng_failures,
+    ),
+];
+
+/// Expose the metrics of `generator`, replacing a generator registered under the same name.
+pub fn register_generator<G>(generator: &SharedGenerator<G>) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let name: Arc<str> = generator.name().into();
+    registry.retain(|(n, _)| *n != name);
+    registry.push((name, Arc::clone(generator.metrics())));
+}
+
+/// Stop exposing the generator registered under `name`.
+pub fn unregister_generator(name: &str) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|(n, _)| &**n != name);
+}
+
+/// Metrics of every registered generator, labelled by generator name.
+pub fn gather() -> String {
+    let registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    let mut out = String::new();
+    for (metric, help, value) in METRICS.iter() {
+        let _ = writeln!(out, "# HELP {} {}", metric, help);
+        let _ = writeln!(out, "# TYPE {} counter", metric);
+        for (name, metrics) in registry.iter() {
+            let _ = writeln!(
+                out,
+                "{}{{generator=\"{}\"}} {}",
+                metric,
+                name.replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n"),
+                value(metrics)
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Generator, UUID};
+
+    #[test]
+    fn gather_registered_generators() {
+        let shared = SharedGenerator::new("orders", || Ok(UUID::NIL));
+        register_generator(&shared);
+        shared.generate().unwrap();
+        shared.generate().unwrap();
+
+        let text = gather();
+        assert!(text.contains("# TYPE uuid_ids_issued_total counter\n"));
+        assert!(text.contains("uuid_ids_issued_total{generator=\"orders\"} 2\n"));
+        assert!(text.contains("uuid_rng_failures_total{generator=\"orders\"} 0\n"));
+
+        unregister_generator("orders");
+        assert!(!gather().contains("orders"));
+    }
+
+    #[test]
+    fn gather_escapes_label_values() {
+        let shared = SharedGenerator::new("a\\b\"c\nd", || Ok(UUID::NIL));
+        register_generator(&shared);
+
+        let text = gather();
+        assert!(text.contains("uuid_ids_issued_total{generator=\"a\\\\b\\\"c\\nd\"} 0\n"));
+
+        unregister_generator("a\\b\"c\nd");
+    }
+}
@@ -0,0 +1,270 @@
+#![doc(cfg(feature = "hash_sha1"))]
+#![cfg(feature = "hash_sha1")]
+
+#[cfg(feature = "public_key")]
+use core::convert::TryInto;
+#[cfg(feature = "public_key")]
+use core::fmt;
+
+#[cfg(feature = "public_key")]
+use base64::engine::general_purpose::STANDARD;
+#[cfg(feature = "public_key")]
+use base64::Engine;
+
+use crate::{Layout, NamespaceError, UUID};
+
+/// `SubjectPublicKeyInfo` of an Ed25519 key, up to the 32 octets of the key.
+#[cfg(feature = "public_key")]
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// `SubjectPublicKeyInfo` of a P-256 key, up to its 65-octet uncompressed
+/// point.
+#[cfg(feature = "public_key")]
+const P256_SPKI_PREFIX: [u8; 26] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+/// Error deriving a UUID from an encoded public key.
+#[cfg(feature = "public_key")]
+#[doc(cfg(feature = "public_key"))]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum PublicKeyError {
+    /// The namespace UUID is not of the `rfc4122` variant.
+    Namespace(NamespaceError),
+    /// Not text or octets of the expected encoding.
+    Malformed,
+    /// A key type or point format that cannot be converted to its
+    /// `SubjectPublicKeyInfo`.
+    Unsupported,
+}
+
+#[cfg(feature = "public_key")]
+impl fmt::Display for PublicKeyError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicKeyError::Namespace(e) => fmt::Display::fmt(e, fmt),
+            PublicKeyError::Malformed => write!(fmt, "malformed public key"),
+            PublicKeyError::Unsupported => write!(fmt, "unsupported public key type"),
+        }
+    }
+}
+
+#[cfg(feature = "public_key")]
+impl std::error::Error for PublicKeyError {}
+
+#[cfg(feature = "public_key")]
+impl From<NamespaceError> for PublicKeyError {
+    fn from(e: NamespaceError) -> Self {
+        PublicKeyError::Namespace(e)
+    }
+}
+
+impl UUID {
+    /// New UUID version-5 identifying the public key of `der`, its
+    /// DER-encoded `SubjectPublicKeyInfo`, failing if `ns` is not an
+    /// `rfc4122` variant UUID.
+    ///
+    /// The `SubjectPublicKeyInfo` is the one encoding of a key that every
+    /// toolkit can produce, so devices and servers derive the same UUID from
+    /// it; the wrappers of the `public_key` feature convert the other
+    /// encodings of a key to it and give the same UUID.
+    pub fn v5_of_public_key(ns: UUID, der: &[u8]) -> Result<Layout, NamespaceError> {
+        UUID::v5_bytes(der, ns)
+    }
+
+    /// New UUID version-5 of the key of a PEM `PUBLIC KEY` block, see
+    /// [`UUID::v5_of_public_key`].
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let pem = "-----BEGIN PUBLIC KEY-----
+    /// MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=
+    /// -----END PUBLIC KEY-----";
+    /// let uuid = UUID::v5_of_pem_public_key(UUID::NAMESPACE_OID, pem).unwrap();
+    /// assert_eq!(uuid.to_string(), "73380521-b2fe-57b0-8d9b-db4827410f11");
+    /// ```
+    #[cfg(feature = "public_key")]
+    #[doc(cfg(feature = "public_key"))]
+    pub fn v5_of_pem_public_key(ns: UUID, pem: &str) -> Result<Layout, PublicKeyError> {
+        let (_, rest) = pem
+            .split_once("-----BEGIN PUBLIC KEY-----")
+            .ok_or(PublicKeyError::Malformed)?;
+        let (body, _) = rest
+            .split_once("-----END PUBLIC KEY-----")
+            .ok_or(PublicKeyError::Malformed)?;
+        let text: String = body.split_whitespace().collect();
+        let der = STANDARD
+            .decode(text)
+            .map_err(|_| PublicKeyError::Malformed)?;
+        Ok(UUID::v5_of_public_key(ns, &der)?)
+    }
+
+    /// New UUID version-5 of the 32 octets of an Ed25519 key, see
+    /// [`UUID::v5_of_public_key`].
+    #[cfg(feature = "public_key")]
+    #[doc(cfg(feature = "public_key"))]
+    pub fn v5_of_ed25519_key(ns: UUID, key: &[u8; 32]) -> Result<Layout, NamespaceError> {
+        let mut der = [0u8; 44];
+        der[..12].copy_from_slice(&ED25519_SPKI_PREFIX);
+        der[12..].copy_from_slice(key);
+        UUID::v5_of_public_key(ns, &der)
+    }
+
+    /// New UUID version-5 of the SEC1 point of a P-256 key, see
+    /// [`UUID::v5_of_public_key`]; only the 65-octet uncompressed form,
+    /// starting with `0x04`, is supported.
+    #[cfg(feature = "public_key")]
+    #[doc(cfg(feature = "public_key"))]
+    pub fn v5_of_p256_key(ns: UUID, point: &[u8]) -> Result<Layout, PublicKeyError> {
+        match point {
+            [0x04, ..] if point.len() == 65 => {
+                let mut der = [0u8; 91];
+                der[..26].copy_from_slice(&P256_SPKI_PREFIX);
+                der[26..].copy_from_slice(point);
+                Ok(UUID::v5_of_public_key(ns, &der)?)
+            }
+            [0x02 | 0x03, ..] if point.len() == 33 => Err(PublicKeyError::Unsupported),
+            _ => Err(PublicKeyError::Malformed),
+        }
+    }
+
+    /// New UUID version-5 of an OpenSSH public key line, such as a line of
+    /// `authorized_keys` without options, see [`UUID::v5_of_public_key`];
+    /// `ssh-ed25519` and `ecdsa-sha2-nistp256` keys are supported.
+    #[cfg(feature = "public_key")]
+    #[doc(cfg(feature = "public_key"))]
+    pub fn v5_of_openssh_key(ns: UUID, line: &str) -> Result<Layout, PublicKeyError> {
+        let mut words = line.split_whitespace();
+        let (kind, blob) = match (words.next(), words.next()) {
+            (Some(kind), Some(blob)) => (kind, blob),
+            _ => return Err(PublicKeyError::Malformed),
+        };
+        let blob = STANDARD
+            .decode(blob)
+            .map_err(|_| PublicKeyError::Malformed)?;
+
+        let mut fields = SshFields(&blob);
+        if fields.next() != Some(kind.as_bytes()) {
+            return Err(PublicKeyError::Malformed);
+        }
+        let key = match kind {
+            "ssh-ed25519" => fields.next(),
+            "ecdsa-sha2-nistp256" if fields.next() == Some(b"nistp256") => fields.next(),
+            "ecdsa-sha2-nistp256" => None,
+            _ => return Err(PublicKeyError::Unsupported),
+        };
+        match (kind, key, fields.0) {
+            ("ssh-ed25519", Some(key), []) => {
+                let key = key.try_into().map_err(|_| PublicKeyError::Malformed)?;
+                Ok(UUID::v5_of_ed25519_key(ns, key)?)
+            }
+            (_, Some(point), []) => UUID::v5_of_p256_key(ns, point),
+            _ => Err(PublicKeyError::Malformed),
+        }
+    }
+}
+
+// Length-prefixed strings of an SSH wire-format blob.
+#[cfg(feature = "public_key")]
+struct SshFields<'a>(&'a [u8]);
+
+#[cfg(feature = "public_key")]
+impl<'a> Iterator for SshFields<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let len = u32::from_be_bytes(self.0.get(..4)?.try_into().unwrap()) as usize;
+        let field = self.0.get(4..4usize.checked_add(len)?)?;
+        self.0 = &self.0[4 + len..];
+        Some(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ed25519 key of `rfc8410` section 10.1.
+    const ED25519_DER: &str =
+        "302a300506032b657003210019bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1";
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn v5_of_der_public_key() {
+        let uuid = UUID::v5_of_public_key(UUID::NAMESPACE_OID, &hex(ED25519_DER)).unwrap();
+        assert_eq!(uuid.to_string(), "73380521-b2fe-57b0-8d9b-db4827410f11");
+        assert!(UUID::v5_of_public_key(UUID::default(), &hex(ED25519_DER)).is_err());
+    }
+
+    #[cfg(feature = "public_key")]
+    #[test]
+    fn encodings_of_a_key_agree() {
+        let ns = UUID::NAMESPACE_OID;
+        let der = UUID::v5_of_public_key(ns, &hex(ED25519_DER))
+            .unwrap()
+            .to_uuid();
+        let raw = hex(&ED25519_DER[24..]);
+        let raw = UUID::v5_of_ed25519_key(ns, raw[..].try_into().unwrap()).unwrap();
+        assert_eq!(raw.to_uuid(), der);
+
+        let pem = "-----BEGIN PUBLIC KEY-----\n\
+                   MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEaCBQM37XlVDct2rlQ5yrStsVuVUV\n\
+                   NU46TKO+4Bjy52Y3c7TYMeM7ic6ZORslRDxgyCGSu/vgA3b7Y/zWpxjYXg==\n\
+                   -----END PUBLIC KEY-----\n";
+        let ssh = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBB\
+                   GggUDN+15VQ3Ldq5UOcq0rbFblVFTVOOkyjvuAY8udmN3O02DHjO4nOmTkbJUQ8YMghkrv74A\
+                   N2+2P81qcY2F4= dev";
+        assert_eq!(
+            UUID::v5_of_pem_public_key(ns, pem).unwrap().to_uuid(),
+            UUID::v5_of_openssh_key(ns, ssh).unwrap().to_uuid()
+        );
+
+        let ssh = "ssh-ed25519 \
+                   AAAAC3NzaC1lZDI1NTE5AAAAIBbk6FgVBO74/mxL9XE26JMoXuewqoOKZsdmpRgSZou+";
+        let raw = hex("16e4e8581504eef8fe6c4bf57136e893285ee7b0aa838a66c766a51812668bbe");
+        assert_eq!(
+            UUID::v5_of_openssh_key(ns, ssh).unwrap().to_uuid(),
+            UUID::v5_of_ed25519_key(ns, raw[..].try_into().unwrap())
+                .unwrap()
+                .to_uuid()
+        );
+    }
+
+    #[cfg(feature = "public_key")]
+    #[test]
+    fn rejects_other_keys() {
+        let ns = UUID::NAMESPACE_OID;
+        assert_eq!(
+            UUID::v5_of_pem_public_key(ns, "-----BEGIN RSA PUBLIC KEY-----").err(),
+            Some(PublicKeyError::Malformed)
+        );
+        assert_eq!(
+            UUID::v5_of_p256_key(ns, &[0x02; 33]).err(),
+            Some(PublicKeyError::Unsupported)
+        );
+        assert_eq!(
+            UUID::v5_of_openssh_key(ns, "ssh-rsa AAAAB3NzaC1yc2E=").err(),
+            Some(PublicKeyError::Unsupported)
+        );
+        assert_eq!(
+            UUID::v5_of_openssh_key(ns, "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAAQA=").err(),
+            Some(PublicKeyError::Malformed)
+        );
+        let nil = UUID::default();
+        assert_eq!(
+            UUID::v5_of_p256_key(nil, &[0x04; 65]).err(),
+            Some(PublicKeyError::Namespace(NamespaceError(nil)))
+        );
+    }
+}
@@ -1,20 +1,123 @@
-#![doc(cfg(feature = "rand_num"))]
-#![cfg(feature = "rand_num")]
+#![doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+#![cfg(any(feature = "rand_num", feature = "getrandom"))]
 
-use crate::{Layout, Node, Variant, Version, UUID};
-use rand_core::{OsRng, RngCore};
+#[cfg(feature = "rand_num")]
+use rand_core::RngCore;
 
+use crate::entropy;
+use crate::fields::VARIANT_RFC;
+use crate::{Layout, Node, UuidError, Version, UUID};
 impl UUID {
+    /// Version generated by [`UUID::new`]: currently version-4, or
+    /// version-7 with the `v7_default` feature.
+    pub const DEFAULT_VERSION: Version = if cfg!(feature = "v7_default") {
+        Version::UNIX
+    } else {
+        Version::RAND
+    };
+
+    /// New UUID of the recommended version.
+    ///
+    /// This is currently version-4, and will become version-7 in 1.0; the
+    /// `v7_default` feature opts into version-7 now. Use
+    /// [`UUID::new_v4_forever`] where the version must stay random.
+    pub fn new() -> Result<UUID, UuidError> {
+        #[cfg(feature = "v7_default")]
+        let layout = UUID::v7()?;
+        #[cfg(not(feature = "v7_default"))]
+        let layout = UUID::new_from_rand()?;
+        Ok(layout.to_uuid())
+    }
+
+    /// New UUID version-4, unaffected by the version [`UUID::new`] defaults to.
+    pub fn new_v4_forever() -> Result<UUID, UuidError> {
+        UUID::new_from_rand().map(|uuid| uuid.to_uuid())
+    }
+
+    /// Overwrite every UUID of `uuids` with a new version-4 UUID, drawing
+    /// the random bits in bulk.
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let mut uuids = vec![UUID::default(); 1_000];
+    /// UUID::fill_v4(&mut uuids).unwrap();
+    /// assert!(uuids.iter().all(|uuid| uuid.get_version_num() == 4));
+    /// ```
+    pub fn fill_v4(uuids: &mut [UUID]) -> Result<(), UuidError> {
+        entropy::fill_uuids(uuids)?;
+        for uuid in uuids {
+            uuid.0[6] = uuid.0[6] & 0x0f | (Version::RAND as u8) << 4;
+            uuid.0[8] = uuid.0[8] & 0x3f | VARIANT_RFC;
+        }
+        Ok(())
+    }
+
+    /// Endless new version-4 UUIDs.
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let ids: Vec<UUID> = UUID::v4_iter().take(3).collect();
+    /// assert_eq!(ids.len(), 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics when the random number generator fails; use
+    /// [`UUID::new_v4_forever`] to handle the failure.
+    pub fn v4_iter() -> impl Iterator<Item = UUID> {
+        std::iter::repeat_with(|| UUID::new_v4_forever().expect("random number generator failed"))
+    }
+
     /// New UUID version-4 from truly-random number
-    pub fn new_from_rand() -> Layout {
-        let mut key = [0u8; 128];
-        OsRng.fill_bytes(&mut key);
+    pub fn new_from_rand() -> Result<Layout, UuidError> {
+        let mut key = [0u8; 16];
+        entropy::fill(&mut key)?;
+        Ok(Layout::rand_fields(key))
+    }
 
-        let random_u64_round_1 = OsRng.next_u64();
-        let round_1 = random_u64_round_1.to_le_bytes();
+    /// New UUID version-4 with the random bits of `rng`, such as a ChaCha20
+    /// generator, a hardware generator or a seeded one in tests.
+    ///
+    /// ```rust
+    /// use simple_uuid::rand_core::{impls, Error, RngCore};
+    /// use simple_uuid::UUID;
+    ///
+    /// struct Fixed;
+    ///
+    /// impl RngCore for Fixed {
+    ///     fn next_u32(&mut self) -> u32 {
+    ///         0
+    ///     }
+    ///     fn next_u64(&mut self) -> u64 {
+    ///         0
+    ///     }
+    ///     fn fill_bytes(&mut self, dest: &mut [u8]) {
+    ///         dest.fill(0xff)
+    ///     }
+    ///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+    ///         self.fill_bytes(dest);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let uuid = UUID::v4_from_rng(&mut Fixed).unwrap();
+    /// assert_eq!(uuid.to_string(), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+    /// ```
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn v4_from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Result<Layout, UuidError> {
+        let mut key = [0u8; 16];
+        rng.try_fill_bytes(&mut key)
+            .map_err(|_| UuidError::Entropy)?;
+        Ok(Layout::rand_fields(key))
+    }
+}
 
-        let random_u64_round_2 = OsRng.next_u64();
-        let round_2 = random_u64_round_2.to_le_bytes();
+impl Layout {
+    fn rand_fields(key: [u8; 16]) -> Self {
+        let (round_1, round_2) = key.split_at(8);
 
         Layout {
             field_low: ((round_1[0] as u32) << 24)
@@ -24,8 +127,8 @@ impl UUID {
             field_mid: (round_1[4] as u16) << 8 | (round_1[5] as u16),
             field_high_and_version: ((round_1[6] as u16) << 8 | (round_1[7] as u16)) & 0xfff
                 | (Version::RAND as u16) << 12,
-            clock_seq_high_and_reserved: (round_2[0] & 0xf) | (Variant::RFC as u8) << 4,
-            clock_seq_low: round_2[1] as u8,
+            clock_seq_high_and_reserved: (round_2[0] & 0x3f) | VARIANT_RFC,
+            clock_seq_low: round_2[1],
             node: Node([
                 round_2[2], round_2[3], round_2[4], round_2[5], round_2[6], round_2[7],
             ]),
@@ -34,22 +137,95 @@ impl UUID {
 }
 
 /// `UUID` version-4
-#[doc(cfg(feature = "rand_num"))]
+#[doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
 #[macro_export]
 macro_rules! v4 {
     () => {
-        format!("{:x}", $crate::UUID::new_from_rand().as_bytes())
+        $crate::UUID::new_from_rand().map(|uuid| format!("{:x}", uuid.to_uuid()))
+    };
+}
+
+/// `UUID` of the version [`UUID::new`] defaults to
+#[doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+#[macro_export]
+macro_rules! uuid_new {
+    () => {
+        $crate::UUID::new().map(|uuid| format!("{:x}", uuid))
     };
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Variant;
 
     #[test]
     fn new_from_rand() {
-        let uuid = UUID::new_from_rand();
+        let uuid = UUID::new_from_rand().unwrap();
         assert_eq!(uuid.get_version(), Some(Version::RAND));
         assert_eq!(uuid.get_variant(), Some(Variant::RFC));
     }
+
+    #[test]
+    fn fill_v4() {
+        let mut uuids = vec![UUID::default(); 1_000];
+        UUID::fill_v4(&mut uuids).unwrap();
+        for uuid in &uuids {
+            assert_eq!(uuid.decode().get_version(), Some(Version::RAND));
+            assert_eq!(uuid.decode().get_variant(), Some(Variant::RFC));
+        }
+        let unique: std::collections::HashSet<_> = uuids.iter().collect();
+        assert_eq!(unique.len(), 1_000);
+        UUID::fill_v4(&mut []).unwrap();
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn from_seeded_rng() {
+        use rand_chacha::ChaCha20Rng;
+        use rand_core::SeedableRng;
+
+        let mut a = ChaCha20Rng::seed_from_u64(7);
+        let mut b = ChaCha20Rng::seed_from_u64(7);
+        let uuid = UUID::v4_from_rng(&mut a).unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::RAND));
+        let uuid = uuid.to_uuid();
+        assert_eq!(uuid, UUID::v4_from_rng(&mut b).unwrap().to_uuid());
+        assert_ne!(uuid, UUID::v4_from_rng(&mut a).unwrap().to_uuid());
+
+        let ms = 1_645_557_742_000;
+        let uuid = UUID::v7_from_millis_and_rng(ms, &mut a).unwrap().to_uuid();
+        assert_eq!(uuid.timestamp_ms(), Some(ms));
+        let rng: &mut dyn rand_core::RngCore = &mut b;
+        UUID::v4_from_rng(rng).unwrap();
+        assert_eq!(
+            UUID::v7_from_millis_and_rng(ms, rng).unwrap().to_uuid(),
+            uuid
+        );
+        assert_eq!(
+            UUID::v7_from_rng(rng).unwrap().get_version(),
+            Some(Version::UNIX)
+        );
+    }
+
+    #[test]
+    fn v4_iter() {
+        let uuids: Vec<_> = UUID::v4_iter().take(100).collect();
+        assert!(uuids.iter().all(|uuid| uuid.get_version_num() == 4));
+        assert_ne!(uuids[0], uuids[1]);
+    }
+
+    #[test]
+    fn new_uses_default_version() {
+        let uuid = UUID::new().unwrap();
+        assert_eq!(uuid.get_version_num(), UUID::DEFAULT_VERSION as u8);
+        assert_eq!(UUID::new_v4_forever().unwrap().get_version_num(), 4);
+        let s = uuid_new!().unwrap();
+        assert_eq!(
+            s.as_bytes()[14],
+            b'0' + UUID::DEFAULT_VERSION as u8,
+            "{}",
+            s
+        );
+    }
 }
@@ -0,0 +1,60 @@
+#![doc(cfg(feature = "redact"))]
+#![cfg(feature = "redact")]
+
+use core::fmt;
+
+use crate::UUID;
+
+/// A UUID that only prints its first and last 4 hex digits, e.g. `6ba7…30c8`.
+#[derive(Eq, PartialEq, Copy, Clone, Default)]
+pub struct Redacted(UUID);
+
+impl Redacted {
+    /// The full UUID behind the redaction.
+    pub const fn reveal(&self) -> UUID {
+        self.0
+    }
+}
+
+impl UUID {
+    /// Wrap the UUID so that it is redacted when formatted.
+    pub const fn redacted(self) -> Redacted {
+        Redacted(self)
+    }
+}
+
+impl From<UUID> for Redacted {
+    fn from(uuid: UUID) -> Self {
+        Redacted(uuid)
+    }
+}
+
+impl fmt::Display for Redacted {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = (self.0).0;
+        write!(
+            fmt,
+            "{:02x}{:02x}\u{2026}{:02x}{:02x}",
+            b[0], b[1], b[14], b[15]
+        )
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Redacted({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_format() {
+        let r = UUID::NAMESPACE_DNS.redacted();
+        assert_eq!(format!("{}", r), "6ba7\u{2026}30c8");
+        assert_eq!(format!("{:?}", r), "Redacted(6ba7\u{2026}30c8)");
+        assert_eq!(r.reveal(), UUID::NAMESPACE_DNS);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{Layout, Version};
+
+impl Layout {
+    /// The version-6 UUID with the timestamp, clock sequence and node of
+    /// this version-1 UUID, or `None` for other versions.
+    pub fn to_v6(&self) -> Option<Layout> {
+        let ticks = match self.get_version()? {
+            Version::TIME => self.ticks_v1(),
+            Version::REORDERED => return Some(*self),
+            _ => return None,
+        };
+        Some(Layout {
+            field_low: (ticks >> 28) as u32,
+            field_mid: (ticks >> 12 & 0xffff) as u16,
+            field_high_and_version: (ticks & 0xfff) as u16 | (Version::REORDERED as u16) << 12,
+            ..*self
+        })
+    }
+
+    /// The version-1 UUID with the timestamp, clock sequence and node of
+    /// this version-6 UUID, or `None` for other versions.
+    pub fn to_v1(&self) -> Option<Layout> {
+        let ticks = match self.get_version()? {
+            Version::REORDERED => {
+                u64::from(self.field_low) << 28
+                    | u64::from(self.field_mid) << 12
+                    | u64::from(self.field_high_and_version & 0xfff)
+            }
+            Version::TIME => return Some(*self),
+            _ => return None,
+        };
+        Some(Layout {
+            field_low: ticks as u32,
+            field_mid: (ticks >> 32) as u16,
+            field_high_and_version: (ticks >> 48 & 0xfff) as u16 | (Version::TIME as u16) << 12,
+            ..*self
+        })
+    }
+
+    fn ticks_v1(&self) -> u64 {
+        u64::from(self.field_high_and_version & 0xfff) << 48
+            | u64::from(self.field_mid) << 32
+            | u64::from(self.field_low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UUID;
+
+    #[test]
+    fn v1_and_v6_round_trip() {
+        // Both from the `rfc9562` examples, sharing timestamp, clock sequence and node.
+        let v1 = UUID::from_u128(0xc232_ab00_9414_11ec_b3c8_9f6b_dece_d846).decode();
+        let v6 = UUID::from_u128(0x1ec9_414c_232a_6b00_b3c8_9f6b_dece_d846).decode();
+        assert_eq!(v1.to_v6().unwrap().to_uuid(), v6.to_uuid());
+        assert_eq!(v6.to_v1().unwrap().to_uuid(), v1.to_uuid());
+        assert_eq!(v6.to_v6().unwrap().to_uuid(), v6.to_uuid());
+        assert!(UUID::NIL.decode().to_v6().is_none());
+        assert!(UUID::v8([0; 16]).decode().to_v1().is_none());
+    }
+}
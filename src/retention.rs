@@ -0,0 +1,37 @@
+//! Partitioning of UUID-keyed stores into retention periods.
+
+use std::time::Duration;
+
+use crate::UUID;
+
+/// Index of the retention period holding the timestamp of `uuid`, counted
+/// from the UNIX epoch in `period` steps.
+///
+/// Entries sharing a key can be dropped together by a TTL job; `None` is
+/// returned for UUIDs without a timestamp. Periods shorter than a millisecond
+/// count as one millisecond.
+pub fn partition_key(uuid: &UUID, period: Duration) -> Option<u64> {
+    let period = period.as_millis().clamp(1, u128::from(u64::MAX)) as u64;
+    uuid.timestamp_ms().map(|ms| ms / period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_by_day() {
+        // 2022-02-22T19:22:22Z, from the `rfc9562` v7 example.
+        let uuid = UUID([
+            0x01, 0x7f, 0x22, 0xe2, 0x79, 0xb0, 0x7c, 0xc3, 0x98, 0xc4, 0xdc, 0x0c, 0x0c, 0x07,
+            0x39, 0x8f,
+        ]);
+        let day = Duration::from_secs(86_400);
+        assert_eq!(partition_key(&uuid, day), Some(19_045));
+        assert_eq!(
+            partition_key(&uuid, Duration::ZERO),
+            Some(1_645_557_742_000)
+        );
+        assert_eq!(partition_key(&UUID::default(), day), None);
+    }
+}
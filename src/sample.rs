@@ -0,0 +1,54 @@
+use core::convert::TryInto;
+
+use crate::UUID;
+
+/// Number of random bits in octets 8 to 15 once the variant bits are dropped.
+const SAMPLE_BITS: u32 = 62;
+
+impl UUID {
+    /// Whether the UUID falls in a sample of the given `rate`, from 0 to 1.
+    ///
+    /// The low 62 bits of octets 8 to 15, read big-endian, are the random
+    /// bits of versions 4 and 7; the UUID is included when they are below
+    /// `rate * 2^62`. The same UUID and rate give the same answer in any
+    /// language, and a UUID sampled at some rate is sampled at every higher one.
+    pub fn sample(&self, rate: f64) -> bool {
+        if rate.is_nan() || rate <= 0.0 {
+            return false;
+        }
+        if rate >= 1.0 {
+            return true;
+        }
+        let bits = u64::from_be_bytes(self.0[8..].try_into().unwrap()) & ((1 << SAMPLE_BITS) - 1);
+        (bits as f64) < rate * (1u64 << SAMPLE_BITS) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_bounds() {
+        let uuid = UUID::from_u128(0x0000_0000_0000_4000_bfff_ffff_ffff_ffff);
+        assert!(!uuid.sample(0.0));
+        assert!(!uuid.sample(f64::NAN));
+        assert!(!uuid.sample(0.999));
+        assert!(uuid.sample(1.0));
+        assert!(UUID::from_u128(0x0000_0000_0000_4000_8000_0000_0000_0000).sample(1e-9));
+    }
+
+    #[test]
+    fn sample_rate_and_stability() {
+        let step = (1u64 << SAMPLE_BITS) / 1000 + 1;
+        let sampled = (0..1000u64)
+            .map(|i| UUID::from_u128(0x4000_8000_0000_0000_0000 | u128::from(i * step)))
+            .filter(|uuid| uuid.sample(0.25))
+            .count();
+        assert_eq!(sampled, 250);
+
+        let uuid = UUID::NAMESPACE_DNS;
+        assert_eq!(uuid.sample(0.3), uuid.sample(0.3));
+        assert!(!uuid.sample(0.3) || uuid.sample(0.6));
+    }
+}
@@ -0,0 +1,240 @@
+//! Serde support for `UUID`.
+//!
+//! Human-readable formats use the hyphenated form, and also accept the
+//! simple, braced and urn forms when deserializing; binary formats use the
+//! 16 octets.
+//!
+//! The [`bytes`], [`u128`] and [`simple`] modules select another
+//! representation for a single field, whatever the format:
+//!
+//! ```
+//! # use simple_uuid::UUID;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Event {
+//!     #[serde(with = "simple_uuid::serde::bytes")]
+//!     id: UUID,
+//!     #[serde(with = "simple_uuid::serde::simple")]
+//!     trace: UUID,
+//! }
+//! ```
+#![doc(cfg(feature = "serde"))]
+#![cfg(feature = "serde")]
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use ::serde::de::{self, Deserialize, Deserializer, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+
+use crate::format::MAX_ENCODED_LEN;
+use crate::{Format, Guid, ParseOptions, UUID};
+
+impl Serialize for UUID {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_str(self, Format::Hyphenated, serializer)
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UUID {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(UuidVisitor)
+        } else {
+            deserializer.deserialize_bytes(UuidVisitor)
+        }
+    }
+}
+
+/// The hyphenated form in human-readable formats, and the 16 octets stored
+/// as in Windows and UEFI, with the first three fields little-endian,
+/// in binary formats.
+impl Serialize for Guid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serialize_str(&self.to_uuid(), Format::Hyphenated, serializer)
+        } else {
+            serializer.serialize_bytes(&self.to_bytes_le())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Guid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(UuidVisitor).map(Guid::from)
+        } else {
+            let uuid = deserializer.deserialize_bytes(UuidVisitor)?;
+            Ok(Guid::from_bytes_le(uuid.0))
+        }
+    }
+}
+
+fn serialize_str<S: Serializer>(
+    uuid: &UUID,
+    format: Format,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut buf = [0u8; MAX_ENCODED_LEN];
+    let len = uuid.encode_into(format, &mut buf).unwrap();
+    serializer.serialize_str(core::str::from_utf8(&buf[..len]).unwrap())
+}
+
+/// Serialize as the 16 octets, also in human-readable formats.
+pub mod bytes {
+    use super::*;
+
+    /// Serialize `uuid` as its 16 octets.
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&uuid.0)
+    }
+
+    /// Deserialize 16 octets, given as bytes or as a sequence.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        deserializer.deserialize_bytes(UuidVisitor)
+    }
+}
+
+/// Serialize as the big-endian `u128` value.
+pub mod u128 {
+    use super::*;
+
+    /// Serialize `uuid` as [`UUID::as_u128`].
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u128(uuid.as_u128())
+    }
+
+    /// Deserialize a `u128` with [`UUID::from_u128`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        core::primitive::u128::deserialize(deserializer).map(UUID::from_u128)
+    }
+}
+
+/// Serialize as 32 hex digits without separators.
+pub mod simple {
+    use super::*;
+
+    /// Serialize `uuid` in the simple form.
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_str(uuid, Format::Simple, serializer)
+    }
+
+    /// Deserialize a string in the simple, hyphenated, braced or urn form.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        deserializer.deserialize_str(UuidVisitor)
+    }
+}
+
+struct UuidVisitor;
+
+impl<'de> Visitor<'de> for UuidVisitor {
+    type Value = UUID;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("a UUID string or 16 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<UUID, E> {
+        UUID::parse_with(s, &ParseOptions::new()).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<UUID, E> {
+        match <[u8; 16]>::try_from(b) {
+            Ok(bytes) => Ok(UUID(bytes)),
+            Err(_) => Err(E::invalid_length(b.len(), &self)),
+        }
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<UUID, A::Error> {
+        let mut bytes = [0u8; 16];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(UUID(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn readable_as_hyphenated() {
+        assert_tokens(
+            &UUID::NAMESPACE_DNS.readable(),
+            &[Token::Str("6ba7b810-9dad-11d1-80b4-00c04fd430c8")],
+        );
+        serde_test::assert_de_tokens(
+            &UUID::NAMESPACE_DNS.readable(),
+            &[Token::Str("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8")],
+        );
+        assert_de_tokens_error::<serde_test::Readable<UUID>>(
+            &[Token::Str("6ba7b810")],
+            "invalid length: expected 36, found 8",
+        );
+    }
+
+    #[test]
+    fn compact_as_bytes() {
+        assert_tokens(
+            &UUID::NAMESPACE_DNS.compact(),
+            &[Token::Bytes(&UUID::NAMESPACE_DNS.0)],
+        );
+        assert_de_tokens_error::<serde_test::Compact<UUID>>(
+            &[Token::Bytes(&[0; 4])],
+            "invalid length 4, expected a UUID string or 16 bytes",
+        );
+    }
+
+    #[test]
+    fn guid_compact_as_little_endian() {
+        let guid = Guid::from(UUID::NAMESPACE_DNS);
+        assert_tokens(
+            &guid.compact(),
+            &[Token::Bytes(&[
+                0x10, 0xb8, 0xa7, 0x6b, 0xad, 0x9d, 0xd1, 0x11, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4,
+                0x30, 0xc8,
+            ])],
+        );
+        assert_tokens(
+            &guid.readable(),
+            &[Token::Str("6ba7b810-9dad-11d1-80b4-00c04fd430c8")],
+        );
+    }
+
+    #[test]
+    fn field_representations() {
+        #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        struct Ids {
+            #[serde(with = "bytes")]
+            bytes: UUID,
+            #[serde(with = "super::u128")]
+            int: UUID,
+            #[serde(with = "simple")]
+            simple: UUID,
+        }
+
+        let ids = Ids {
+            bytes: UUID::NAMESPACE_DNS,
+            int: UUID::NAMESPACE_DNS,
+            simple: UUID::NAMESPACE_DNS,
+        };
+        let json = serde_json::to_string(&ids).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                "{{\"bytes\":{:?},\"int\":{},\"simple\":\"6ba7b8109dad11d180b400c04fd430c8\"}}",
+                UUID::NAMESPACE_DNS.0,
+                UUID::NAMESPACE_DNS.as_u128()
+            )
+            .replace(' ', "")
+        );
+        assert_eq!(serde_json::from_str::<Ids>(&json).unwrap(), ids);
+    }
+}
@@ -0,0 +1,383 @@
+//! A minimal ID-issuing server and its client, over TCP.
+//!
+//! Every frame is a big-endian `u32` length followed by that many bytes.
+//! Requests start with a command byte:
+//!
+//! - `L` and a big-endian `u32` count leases that many UUIDs, answered by
+//!   status `0` and 16 octets per UUID;
+//! - `R` and a big-endian `u32` count reserves a block of that many UUIDs
+//!   to mint locally, answered by status `0` and a `LeaseGrant`;
+//! - `H` asks for health, answered by status `0`, then the number of UUIDs
+//!   issued and the last issued timestamp in milliseconds, as big-endian `u64`s.
+//!
+//! Failures are answered by status `1` and a UTF-8 message.
+#![doc(cfg(feature = "service"))]
+#![cfg(feature = "service")]
+
+use std::convert::TryInto;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::lease::{LeaseGrant, LeaseRequest};
+use crate::state::{GeneratorState, StateStore};
+use crate::{Generator, UUID};
+
+/// Largest batch leased by default.
+pub const DEFAULT_MAX_BATCH: u32 = 10_000;
+
+/// Largest request frame accepted.
+const MAX_REQUEST_LEN: usize = 64;
+
+/// UUIDs drawn from the generator before a reserve gives up.
+const MAX_RESERVE_DRAWS: u32 = 1024;
+
+const STATUS_OK: u8 = 0;
+const STATUS_ERR: u8 = 1;
+
+/// Health reported by the `H` command.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub struct Health {
+    /// Number of UUIDs issued, including before restarts when state is persisted.
+    pub issued: u64,
+    /// Timestamp in milliseconds of the latest time-based UUID issued.
+    pub last_timestamp: u64,
+}
+
+/// Server leasing batches of UUIDs from a generator.
+pub struct IdService<G> {
+    generator: G,
+    store: Option<Box<dyn StateStore + Send + Sync>>,
+    max_batch: u32,
+    health: Mutex<Health>,
+    // Highest UUID issued, held while issuing so blocks never overlap it.
+    highest: Mutex<Option<UUID>>,
+}
+
+impl<G: Generator + Send + Sync + 'static> IdService<G> {
+    /// New service issuing the UUIDs of `generator`.
+    pub fn new(generator: G) -> Self {
+        Self {
+            generator,
+            store: None,
+            max_batch: DEFAULT_MAX_BATCH,
+            health: Mutex::new(Health::default()),
+            highest: Mutex::new(None),
+        }
+    }
+
+    /// Persist the issued count and last timestamp to `store`, resuming from
+    /// what it holds.
+    pub fn with_state<S: StateStore + Send + Sync + 'static>(
+        mut self,
+        store: S,
+    ) -> Result<Self, crate::UuidError> {
+        if let Some(state) = store.load()? {
+            *self.health.get_mut().unwrap_or_else(|e| e.into_inner()) = Health {
+                issued: state.counter,
+                last_timestamp: state.last_timestamp,
+            };
+        }
+        self.store = Some(Box::new(store));
+        Ok(self)
+    }
+
+    /// Refuse leases of more than `max_batch` UUIDs.
+    pub fn max_batch(mut self, max_batch: u32) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Current health of the service.
+    pub fn health(&self) -> Health {
+        *self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Serve connections of `listener`, one thread each, until accepting fails.
+    pub fn serve(self, listener: TcpListener) -> io::Result<()> {
+        let service = Arc::new(self);
+        for stream in listener.incoming() {
+            let service = Arc::clone(&service);
+            let stream = stream?;
+            thread::spawn(move || service.handle(stream));
+        }
+        Ok(())
+    }
+
+    /// Answer the requests of one connection until it is closed.
+    pub fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = BufWriter::new(stream);
+        while let Some(request) = read_frame(&mut reader, MAX_REQUEST_LEN)? {
+            let response = match self.answer(&request) {
+                Ok(body) => body,
+                Err(message) => {
+                    let mut body = vec![STATUS_ERR];
+                    body.extend_from_slice(message.as_bytes());
+                    body
+                }
+            };
+            write_frame(&mut writer, &response)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn answer(&self, request: &[u8]) -> Result<Vec<u8>, String> {
+        match request {
+            [b'L', count @ ..] => {
+                let count = u32::from_be_bytes(
+                    count
+                        .try_into()
+                        .map_err(|_| "malformed lease request".to_string())?,
+                );
+                if count > self.max_batch {
+                    return Err(format!("batch larger than {}", self.max_batch));
+                }
+                let mut body = Vec::with_capacity(1 + 16 * count as usize);
+                body.push(STATUS_OK);
+                for uuid in self.lease(count)? {
+                    body.extend_from_slice(&uuid.0);
+                }
+                Ok(body)
+            }
+            [crate::lease::LEASE_COMMAND, ..] => {
+                let request = LeaseRequest::from_bytes(request)
+                    .ok_or_else(|| "malformed reserve request".to_string())?;
+                let grant = self.reserve(request.count)?;
+                let mut body = vec![STATUS_OK];
+                body.extend_from_slice(&grant.to_bytes());
+                Ok(body)
+            }
+            [b'H'] => {
+                let health = self.health();
+                let mut body = vec![STATUS_OK];
+                body.extend_from_slice(&health.issued.to_be_bytes());
+                body.extend_from_slice(&health.last_timestamp.to_be_bytes());
+                Ok(body)
+            }
+            _ => Err("unknown command".to_string()),
+        }
+    }
+
+    fn lease(&self, count: u32) -> Result<Vec<UUID>, String> {
+        let mut highest = self.highest.lock().unwrap_or_else(|e| e.into_inner());
+        let batch = (0..count)
+            .map(|_| self.generator.generate())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        *highest = batch.iter().copied().chain(*highest).max();
+        self.record(&batch, count)?;
+        Ok(batch)
+    }
+
+    fn reserve(&self, count: u32) -> Result<LeaseGrant, String> {
+        // The block holds every UUID sharing the top 96 bits of its base, so
+        // the base must be above those of every UUID issued so far, and the
+        // generator must be advanced past them: generators incrementing the
+        // final bits, such as the `BorrowRandB` rollover of version 7, would
+        // otherwise issue UUIDs inside the block.
+        let mut highest = self.highest.lock().unwrap_or_else(|e| e.into_inner());
+        let above = |floor: Option<UUID>| {
+            move |uuid: UUID| floor.is_none_or(|f| uuid.as_u128() >> 32 > f.as_u128() >> 32)
+        };
+        let base = self.draw(above(*highest))?;
+        let next = self.draw(above(Some(base)))?;
+        *highest = Some(next);
+        self.record(&[base, next], count)?;
+        Ok(LeaseGrant::new(base, count))
+    }
+
+    // First UUID of the generator accepted by `accept`, failing once the
+    // generator has not advanced after `MAX_RESERVE_DRAWS` UUIDs.
+    fn draw(&self, accept: impl Fn(UUID) -> bool) -> Result<UUID, String> {
+        for _ in 0..MAX_RESERVE_DRAWS {
+            let uuid = self.generator.generate().map_err(|e| e.to_string())?;
+            if accept(uuid) {
+                return Ok(uuid);
+            }
+        }
+        Err("generator does not advance past the reserved block".to_string())
+    }
+
+    fn record(&self, batch: &[UUID], count: u32) -> Result<(), String> {
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        health.issued += u64::from(count);
+        if let Some(ms) = batch.iter().filter_map(UUID::timestamp_ms).max() {
+            health.last_timestamp = health.last_timestamp.max(ms);
+        }
+        if let Some(store) = &self.store {
+            let state = GeneratorState {
+                counter: health.issued,
+                last_timestamp: health.last_timestamp,
+                ..GeneratorState::default()
+            };
+            store.save(&state).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Client of an `IdService`.
+pub struct ServiceClient {
+    reader: BufReader<TcpStream>,
+    writer: BufWriter<TcpStream>,
+}
+
+impl ServiceClient {
+    /// Connect to the service at `addr`.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: BufWriter::new(stream),
+        })
+    }
+
+    /// Lease a batch of `count` UUIDs.
+    pub fn lease(&mut self, count: u32) -> io::Result<Vec<UUID>> {
+        let mut request = vec![b'L'];
+        request.extend_from_slice(&count.to_be_bytes());
+        let body = self.call(&request, 16 * count as usize)?;
+        Ok(body
+            .chunks_exact(16)
+            .map(|b| UUID(b.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Health of the service.
+    pub fn health(&mut self) -> io::Result<Health> {
+        let body = self.call(b"H", 16)?;
+        Ok(Health {
+            issued: u64::from_be_bytes(body[..8].try_into().unwrap()),
+            last_timestamp: u64::from_be_bytes(body[8..].try_into().unwrap()),
+        })
+    }
+
+    pub(crate) fn call(&mut self, request: &[u8], expected: usize) -> io::Result<Vec<u8>> {
+        write_frame(&mut self.writer, request)?;
+        self.writer.flush()?;
+        let response = read_frame(&mut self.reader, expected + 1)?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        match response.split_first() {
+            Some((&STATUS_OK, body)) if body.len() == expected => Ok(body.to_vec()),
+            Some((&STATUS_ERR, message)) => Err(io::Error::other(
+                String::from_utf8_lossy(message).into_owned(),
+            )),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "malformed response",
+            )),
+        }
+    }
+}
+
+// Reads one frame, `None` at a clean end of stream.
+fn read_frame<R: Read>(r: &mut R, max_len: usize) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    match r.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max_len {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame too long"));
+    }
+    let mut frame = vec![0u8; len];
+    r.read_exact(&mut frame)?;
+    Ok(Some(frame))
+}
+
+fn write_frame<W: Write>(w: &mut W, frame: &[u8]) -> io::Result<()> {
+    w.write_all(&(frame.len() as u32).to_be_bytes())?;
+    w.write_all(frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoryStore;
+
+    fn start<G: Generator + Send + Sync + 'static>(service: IdService<G>) -> ServiceClient {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || service.serve(listener));
+        ServiceClient::connect(addr).unwrap()
+    }
+
+    #[test]
+    fn lease_and_health() {
+        let mut client = start(IdService::new(|| Ok(UUID::MAX)).max_batch(3));
+        assert_eq!(client.lease(2).unwrap(), vec![UUID::MAX; 2]);
+        assert!(client.lease(4).is_err());
+        assert_eq!(
+            client.health().unwrap(),
+            Health {
+                issued: 2,
+                last_timestamp: 0,
+            }
+        );
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn reserve_blocks() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || IdService::new(crate::V7Generator::new()).serve(listener));
+
+        let mut client = crate::lease::LeaseClient::connect(addr).unwrap();
+        let a = client.reserve(1_000).unwrap();
+        let b = client.reserve(10).unwrap();
+        assert_eq!(a.count(), 1_000);
+        assert!(a.iter().last().unwrap() < b.first());
+    }
+
+    #[test]
+    fn reserve_needs_advancing_generator() {
+        let service = IdService::new(|| Ok(UUID::NIL));
+        assert!(service.reserve(10).is_err());
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn reserve_past_borrowed_rand_b() {
+        use crate::{RolloverPolicy, V7Generator, V7Method};
+
+        let generator =
+            V7Generator::with_method(V7Method::RandomIncrement(RolloverPolicy::BorrowRandB))
+                .with_clock(|| 16_455_577_420_000_000);
+        let service = IdService::new(generator);
+        // Exhaust `rand_a` so that the generator borrows from `rand_b`.
+        service.lease(DEFAULT_MAX_BATCH).unwrap();
+
+        for _ in 0..20 {
+            let before = service.lease(10).unwrap();
+            let grant = service.reserve(u32::MAX).unwrap();
+            let last = grant.get(u32::MAX - 1).unwrap();
+            let after = service.lease(10).unwrap();
+            assert!(!before.iter().any(|uuid| grant.contains(uuid)));
+            assert!(after.iter().all(|uuid| *uuid > last));
+        }
+    }
+
+    #[test]
+    fn resume_from_state() {
+        let store = MemoryStore::new();
+        store
+            .save(&GeneratorState {
+                counter: 40,
+                last_timestamp: 7,
+                ..GeneratorState::default()
+            })
+            .unwrap();
+        let service = IdService::new(|| Ok(UUID::NIL)).with_state(store).unwrap();
+        assert_eq!(service.health().issued, 40);
+
+        let mut client = start(service);
+        client.lease(2).unwrap();
+        assert_eq!(client.health().unwrap().issued, 42);
+    }
+}
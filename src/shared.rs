@@ -0,0 +1,159 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::{Generator, UuidError, UUID};
+
+/// Health counters of a generator, updated without locking.
+#[derive(Debug, Default)]
+pub struct GeneratorMetrics {
+    issued: AtomicU64,
+    clock_regressions: AtomicU64,
+    counter_rollovers: AtomicU64,
+    rng_failures: AtomicU64,
+}
+
+impl GeneratorMetrics {
+    /// Number of UUIDs issued.
+    pub fn issued(&self) -> u64 {
+        self.issued.load(Ordering::Relaxed)
+    }
+
+    /// Number of times the clock was found to go backwards or fail.
+    pub fn clock_regressions(&self) -> u64 {
+        self.clock_regressions.load(Ordering::Relaxed)
+    }
+
+    /// Number of times the intra-timestamp counter overflowed.
+    pub fn counter_rollovers(&self) -> u64 {
+        self.counter_rollovers.load(Ordering::Relaxed)
+    }
+
+    /// Number of failures of the random number generator.
+    pub fn rng_failures(&self) -> u64 {
+        self.rng_failures.load(Ordering::Relaxed)
+    }
+
+    /// Count a clock regression noticed by the generator itself.
+    pub fn record_clock_regression(&self) {
+        self.clock_regressions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count an overflow of the intra-timestamp counter.
+    pub fn record_counter_rollover(&self) {
+        self.counter_rollovers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record(&self, result: &Result<UUID, UuidError>) {
+        let counter = match result {
+            Ok(_) => &self.issued,
+            Err(UuidError::Clock) => &self.clock_regressions,
+            Err(UuidError::Entropy) => &self.rng_failures,
+            Err(_) => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A named generator shared between threads, counting what it issues.
+pub struct SharedGenerator<G> {
+    name: Arc<str>,
+    inner: Arc<G>,
+    metrics: Arc<GeneratorMetrics>,
+}
+
+impl<G: Generator> SharedGenerator<G> {
+    /// Wrap `inner` under `name`, which labels its metrics; the counters
+    /// `inner` updates itself are shared rather than duplicated.
+    pub fn new<N: Into<String>>(name: N, inner: G) -> Self {
+        Self {
+            name: name.into().into(),
+            metrics: inner.shared_metrics().unwrap_or_default(),
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+impl<G> SharedGenerator<G> {
+    /// Name of the generator.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The wrapped generator.
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+
+    /// Health counters of the generator.
+    pub fn metrics(&self) -> &Arc<GeneratorMetrics> {
+        &self.metrics
+    }
+}
+
+impl<G> Clone for SharedGenerator<G> {
+    fn clone(&self) -> Self {
+        Self {
+            name: Arc::clone(&self.name),
+            inner: Arc::clone(&self.inner),
+            metrics: Arc::clone(&self.metrics),
+        }
+    }
+}
+
+impl<G: Generator> Generator for SharedGenerator<G> {
+    fn generate(&self) -> Result<UUID, UuidError> {
+        let result = self.inner.generate();
+        self.metrics.record(&result);
+        result
+    }
+
+    fn shared_metrics(&self) -> Option<Arc<GeneratorMetrics>> {
+        Some(Arc::clone(&self.metrics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_outcomes() {
+        let results = std::sync::Mutex::new(vec![
+            Ok(UUID::NIL),
+            Err(UuidError::Entropy),
+            Err(UuidError::Clock),
+            Ok(UUID::MAX),
+        ]);
+        let shared = SharedGenerator::new("test", move || results.lock().unwrap().remove(0));
+        let clone = shared.clone();
+        for _ in 0..4 {
+            let _ = clone.generate();
+        }
+        shared.metrics().record_counter_rollover();
+
+        let metrics = shared.metrics();
+        assert_eq!(metrics.issued(), 2);
+        assert_eq!(metrics.rng_failures(), 1);
+        assert_eq!(metrics.clock_regressions(), 1);
+        assert_eq!(metrics.counter_rollovers(), 1);
+        assert_eq!(clone.name(), "test");
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn shares_inner_metrics() {
+        use crate::{RolloverPolicy, V7Generator, V7Method};
+
+        let inner =
+            V7Generator::with_method(V7Method::RandomIncrement(RolloverPolicy::AdvanceTimestamp))
+                .with_clock(|| 16_455_577_420_000_000);
+        let shared = SharedGenerator::new("v7", inner);
+        for _ in 0..5_000 {
+            shared.generate().unwrap();
+        }
+        let rollovers = shared.inner().metrics().counter_rollovers();
+        assert!(rollovers > 0);
+        assert_eq!(shared.metrics().counter_rollovers(), rollovers);
+        assert_eq!(shared.metrics().issued(), 5_000);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{ParseError, UUID};
+
+/// Case of the hex digits written by [`UUID::format_with`].
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+pub enum Case {
+    /// `0-9a-f`.
+    #[default]
+    Lower,
+    /// `0-9A-F`.
+    Upper,
+}
+
+/// Layout of a textual UUID: hex case, group separator and group widths in
+/// hex digits, for the legacy forms of mainframe exports and the like.
+///
+/// ```
+/// use simple_uuid::{Case, FormatSpec, UUID};
+///
+/// const QUADS: FormatSpec = FormatSpec::new(Case::Upper, Some(' '), &[4; 8]);
+/// let s = UUID::NAMESPACE_DNS.format_with(&QUADS);
+/// assert_eq!(s, "6BA7 B810 9DAD 11D1 80B4 00C0 4FD4 30C8");
+/// assert_eq!(UUID::parse_spec(&s, &QUADS), Ok(UUID::NAMESPACE_DNS));
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct FormatSpec {
+    case: Case,
+    separator: Option<char>,
+    groups: &'static [usize],
+}
+
+impl FormatSpec {
+    /// The canonical `8-4-4-4-12` form.
+    pub const HYPHENATED: FormatSpec = FormatSpec::new(Case::Lower, Some('-'), &[8, 4, 4, 4, 12]);
+
+    /// 32 hex digits without separators.
+    pub const SIMPLE: FormatSpec = FormatSpec::new(Case::Lower, None, &[32]);
+
+    /// New spec, failing to compile in const context if `groups` do not add
+    /// up to 32 hex digits.
+    pub const fn new(case: Case, separator: Option<char>, groups: &'static [usize]) -> Self {
+        let (mut i, mut digits) = (0, 0);
+        while i < groups.len() {
+            digits += groups[i];
+            i += 1;
+        }
+        assert!(digits == 32, "groups must add up to 32 hex digits");
+        FormatSpec {
+            case,
+            separator,
+            groups,
+        }
+    }
+
+    /// Case of the hex digits; parsing accepts both.
+    pub const fn case(&self) -> Case {
+        self.case
+    }
+
+    /// Character between groups, if any.
+    pub const fn separator(&self) -> Option<char> {
+        self.separator
+    }
+
+    /// Width of each group in hex digits, adding up to 32.
+    pub const fn groups(&self) -> &'static [usize] {
+        self.groups
+    }
+
+    // Number of bytes of a UUID written in this spec.
+    fn len(&self) -> usize {
+        let separators = self.groups.len().saturating_sub(1);
+        32 + separators * self.separator.map_or(0, char::len_utf8)
+    }
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        FormatSpec::HYPHENATED
+    }
+}
+
+impl UUID {
+    /// The UUID written according to `spec`.
+    pub fn format_with(&self, spec: &FormatSpec) -> String {
+        let hex = match spec.case {
+            Case::Lower => b"0123456789abcdef",
+            Case::Upper => b"0123456789ABCDEF",
+        };
+        let mut s = String::with_capacity(spec.len());
+        let mut digits = (0..32).map(|i| hex[(self.0[i / 2] >> (4 * (1 - i % 2)) & 0xf) as usize]);
+        for (i, width) in spec.groups.iter().enumerate() {
+            if let (Some(sep), true) = (spec.separator, i > 0) {
+                s.push(sep);
+            }
+            s.extend(digits.by_ref().take(*width).map(char::from));
+        }
+        s
+    }
+
+    /// Parse a UUID written according to `spec`, in any case.
+    pub fn parse_spec(s: &str, spec: &FormatSpec) -> Result<UUID, ParseError> {
+        if s.len() != spec.len() {
+            return Err(ParseError::InvalidLength {
+                expected: spec.len(),
+                found: s.len(),
+            });
+        }
+
+        let mut bytes = [0u8; 16];
+        let (mut digit, mut chars) = (0, s.char_indices());
+        for (i, width) in spec.groups.iter().enumerate() {
+            if let (Some(sep), true) = (spec.separator, i > 0) {
+                match chars.next() {
+                    Some((_, c)) if c == sep => {}
+                    Some((index, found)) => {
+                        return Err(ParseError::InvalidSeparator { index, found })
+                    }
+                    None => unreachable!("the length was checked"),
+                }
+            }
+            for (index, c) in chars.by_ref().take(*width) {
+                let v = c
+                    .to_digit(16)
+                    .ok_or(ParseError::InvalidCharacter { index, found: c })?;
+                bytes[digit / 2] |= (v as u8) << (4 * (1 - digit % 2));
+                digit += 1;
+            }
+        }
+        Ok(UUID(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_forms() {
+        let uuid = UUID::NAMESPACE_DNS;
+        assert_eq!(FormatSpec::HYPHENATED.case(), Case::Lower);
+        assert_eq!(FormatSpec::HYPHENATED.separator(), Some('-'));
+        assert_eq!(FormatSpec::HYPHENATED.groups(), &[8, 4, 4, 4, 12]);
+        assert_eq!(uuid.format_with(&FormatSpec::HYPHENATED), uuid.to_string());
+        assert_eq!(
+            uuid.format_with(&FormatSpec::SIMPLE),
+            "6ba7b8109dad11d180b400c04fd430c8"
+        );
+        assert_eq!(
+            UUID::parse_spec(
+                "6BA7B810-9dad-11D1-80b4-00c04fd430c8",
+                &FormatSpec::default()
+            ),
+            Ok(uuid)
+        );
+    }
+
+    #[test]
+    fn spec_errors() {
+        let spec = FormatSpec::new(Case::Lower, Some('·'), &[16, 16]);
+        let s = UUID::NAMESPACE_DNS.format_with(&spec);
+        assert_eq!(s, "6ba7b8109dad11d1·80b400c04fd430c8");
+        assert_eq!(UUID::parse_spec(&s, &spec), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(
+            UUID::parse_spec("6ba7b8109dad11d1-80b400c04fd430c8", &spec),
+            Err(ParseError::InvalidLength {
+                expected: 34,
+                found: 33
+            })
+        );
+        assert_eq!(
+            UUID::parse_spec(
+                "6ba7b8109dad11d1 80b400c04fd430c",
+                &FormatSpec::new(Case::Lower, Some(' '), &[16, 16])
+            ),
+            Err(ParseError::InvalidLength {
+                expected: 33,
+                found: 32
+            })
+        );
+        assert_eq!(
+            UUID::parse_spec(
+                "6ba7b8109dad11d1_80b400c04fd430cg",
+                &FormatSpec::new(Case::Lower, Some(' '), &[16, 16])
+            ),
+            Err(ParseError::InvalidSeparator {
+                index: 16,
+                found: '_'
+            })
+        );
+        assert_eq!(
+            UUID::parse_spec(
+                "6ba7b8109dad11d1 80b400c04fd430cg",
+                &FormatSpec::new(Case::Lower, Some(' '), &[16, 16])
+            ),
+            Err(ParseError::InvalidCharacter {
+                index: 32,
+                found: 'g'
+            })
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::format::MAX_ENCODED_LEN;
+use crate::{Format, Generator, UuidError, UUID};
+
+/// Header name used by `IdStamper` unless told otherwise.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Destination of the header written by `IdStamper::stamp`.
+pub trait HeaderSink {
+    /// Set header `name` to `value`.
+    fn insert_header(&mut self, name: &'static str, value: &[u8]);
+}
+
+/// Appends a raw `name: value\r\n` header line.
+impl HeaderSink for Vec<u8> {
+    fn insert_header(&mut self, name: &'static str, value: &[u8]) {
+        self.extend_from_slice(name.as_bytes());
+        self.extend_from_slice(b": ");
+        self.extend_from_slice(value);
+        self.extend_from_slice(b"\r\n");
+    }
+}
+
+/// Stamps responses with fresh IDs, reusing one formatting buffer.
+///
+/// Meant to live for a whole connection, so no allocation happens per stamp.
+pub struct IdStamper<G> {
+    generator: G,
+    header: &'static str,
+    buf: [u8; MAX_ENCODED_LEN],
+    len: usize,
+}
+
+impl<G: Generator> IdStamper<G> {
+    /// New stamper writing IDs of `generator` under `x-request-id`.
+    pub fn new(generator: G) -> Self {
+        Self {
+            generator,
+            header: REQUEST_ID_HEADER,
+            buf: [0; MAX_ENCODED_LEN],
+            len: 0,
+        }
+    }
+
+    /// Write IDs under header `name` instead.
+    pub fn with_header(mut self, name: &'static str) -> Self {
+        self.header = name;
+        self
+    }
+
+    /// Generate an ID and write its hyphenated form into `headers`.
+    ///
+    /// `headers` are left untouched if the generator fails.
+    pub fn stamp<H: HeaderSink + ?Sized>(&mut self, headers: &mut H) -> Result<UUID, UuidError> {
+        let uuid = self.generator.generate()?;
+        self.len = uuid.encode_into(Format::Hyphenated, &mut self.buf).unwrap();
+        headers.insert_header(self.header, &self.buf[..self.len]);
+        Ok(uuid)
+    }
+
+    /// Hyphenated form of the last stamped ID, empty before the first stamp.
+    pub fn last(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamp_into_header_lines() {
+        let mut stamper = IdStamper::new(|| Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(stamper.last(), "");
+
+        let mut headers = Vec::with_capacity(128);
+        assert_eq!(stamper.stamp(&mut headers), Ok(UUID::NAMESPACE_DNS));
+        assert_eq!(
+            headers,
+            b"x-request-id: 6ba7b810-9dad-11d1-80b4-00c04fd430c8\r\n".to_vec()
+        );
+        assert_eq!(stamper.last(), "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+    }
+
+    #[test]
+    fn stamp_custom_sink() {
+        struct Map(Vec<(&'static str, String)>);
+
+        impl HeaderSink for Map {
+            fn insert_header(&mut self, name: &'static str, value: &[u8]) {
+                self.0
+                    .push((name, String::from_utf8_lossy(value).into_owned()));
+            }
+        }
+
+        let mut stamper = IdStamper::new(|| Ok(UUID::NAMESPACE_URL)).with_header("x-trace-id");
+        let mut map = Map(Vec::new());
+        stamper.stamp(&mut map).unwrap();
+        assert_eq!(
+            map.0,
+            vec![(
+                "x-trace-id",
+                "6ba7b811-9dad-11d1-80b4-00c04fd430c8".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn failed_stamp_leaves_headers() {
+        let mut stamper = IdStamper::new(|| Err(UuidError::Clock));
+        let mut headers = Vec::new();
+        assert_eq!(stamper.stamp(&mut headers), Err(UuidError::Clock));
+        assert!(headers.is_empty());
+        assert_eq!(stamper.last(), "");
+    }
+}
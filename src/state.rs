@@ -0,0 +1,350 @@
+//! Persistence of generator state across restarts.
+//!
+//! A `StateStore` keeps the last clock sequence, timestamp and counter of
+//! a time-based generator, so a restarted process never reissues an ID.
+
+use std::convert::TryInto;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::{Node, UuidError};
+
+/// Length of `GeneratorState::to_bytes`.
+pub const STATE_LEN: usize = 25;
+
+/// State of a time-based generator.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct GeneratorState {
+    /// Node the UUIDs were generated with, if fixed.
+    pub node: Option<Node>,
+    /// Last clock sequence.
+    pub clock_seq: u16,
+    /// Last timestamp, in the generator's own unit.
+    pub last_timestamp: u64,
+    /// Last value of the intra-timestamp counter.
+    pub counter: u64,
+}
+
+impl GeneratorState {
+    /// Compact binary form, for key-value stores.
+    pub fn to_bytes(&self) -> [u8; STATE_LEN] {
+        let mut b = [0u8; STATE_LEN];
+        if let Some(node) = self.node {
+            b[0] = 1;
+            b[1..7].copy_from_slice(&node.0);
+        }
+        b[7..9].copy_from_slice(&self.clock_seq.to_be_bytes());
+        b[9..17].copy_from_slice(&self.last_timestamp.to_be_bytes());
+        b[17..25].copy_from_slice(&self.counter.to_be_bytes());
+        b
+    }
+
+    /// State from the binary form written by `to_bytes`.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        if b.len() != STATE_LEN || b[0] > 1 {
+            return None;
+        }
+        Some(Self {
+            node: match b[0] {
+                1 => Some(Node(b[1..7].try_into().ok()?)),
+                _ => None,
+            },
+            clock_seq: u16::from_be_bytes(b[7..9].try_into().ok()?),
+            last_timestamp: u64::from_be_bytes(b[9..17].try_into().ok()?),
+            counter: u64::from_be_bytes(b[17..25].try_into().ok()?),
+        })
+    }
+}
+
+/// Storage of a generator's state.
+pub trait StateStore {
+    /// The saved state, `None` if nothing was saved yet.
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError>;
+
+    /// Replace the saved state.
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError>;
+}
+
+/// A store shared with other owners.
+impl<S: StateStore + ?Sized> StateStore for Arc<S> {
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError> {
+        (**self).load()
+    }
+
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError> {
+        (**self).save(state)
+    }
+}
+
+/// State kept in memory only, mostly for tests.
+#[derive(Debug, Default)]
+pub struct MemoryStore(Mutex<Option<GeneratorState>>);
+
+impl MemoryStore {
+    /// New empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError> {
+        Ok(*self.0.lock().map_err(|_| UuidError::State)?)
+    }
+
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError> {
+        *self.0.lock().map_err(|_| UuidError::State)? = Some(*state);
+        Ok(())
+    }
+}
+
+/// State kept in a small text file, replaced atomically on save.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// New store at `path`; the file is created on first save.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Path of the state file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl StateStore for FileStore {
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(_) => return Err(UuidError::State),
+        };
+
+        let mut state = GeneratorState::default();
+        for line in text.lines() {
+            let (key, value) = line.split_once('=').ok_or(UuidError::State)?;
+            match key {
+                "node" => {
+                    if value.len() != 12 || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return Err(UuidError::State);
+                    }
+                    let mut node = [0u8; 6];
+                    let hex = u64::from_str_radix(value, 16).map_err(|_| UuidError::State)?;
+                    node.copy_from_slice(&hex.to_be_bytes()[2..]);
+                    state.node = Some(Node(node));
+                }
+                "clock_seq" => state.clock_seq = value.parse().map_err(|_| UuidError::State)?,
+                "last_timestamp" => {
+                    state.last_timestamp = value.parse().map_err(|_| UuidError::State)?
+                }
+                "counter" => state.counter = value.parse().map_err(|_| UuidError::State)?,
+                _ => return Err(UuidError::State),
+            }
+        }
+        Ok(Some(state))
+    }
+
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError> {
+        let mut text = String::new();
+        if let Some(node) = state.node {
+            text += &format!(
+                "node={:012x}\n",
+                node.0.iter().fold(0u64, |v, b| v << 8 | u64::from(*b))
+            );
+        }
+        text += &format!(
+            "clock_seq={}\nlast_timestamp={}\ncounter={}\n",
+            state.clock_seq, state.last_timestamp, state.counter
+        );
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        write_synced(Path::new(&tmp), text.as_bytes())
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .and_then(|_| sync_parent(&self.path))
+            .map_err(|_| UuidError::State)
+    }
+}
+
+// Write `bytes` to `path` and flush them to the disk, so a rename over the
+// old file never leaves an empty one after a crash.
+fn write_synced(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+// Flush the rename of a file in its directory; not possible on Windows.
+fn sync_parent(path: &Path) -> io::Result<()> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::File::open(dir)?.sync_all()
+}
+
+/// State kept under a key of a `sled` tree.
+#[cfg(feature = "sled")]
+#[doc(cfg(feature = "sled"))]
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    tree: sled::Tree,
+    key: Vec<u8>,
+}
+
+#[cfg(feature = "sled")]
+impl SledStore {
+    /// New store saving under `key` of `tree`.
+    pub fn new<K: AsRef<[u8]>>(tree: sled::Tree, key: K) -> Self {
+        Self {
+            tree,
+            key: key.as_ref().to_vec(),
+        }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl StateStore for SledStore {
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError> {
+        match self.tree.get(&self.key).map_err(|_| UuidError::State)? {
+            Some(b) => GeneratorState::from_bytes(&b)
+                .map(Some)
+                .ok_or(UuidError::State),
+            None => Ok(None),
+        }
+    }
+
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError> {
+        self.tree
+            .insert(&self.key, &state.to_bytes()[..])
+            .and_then(|_| self.tree.flush())
+            .map(|_| ())
+            .map_err(|_| UuidError::State)
+    }
+}
+
+/// State kept under a key of a Redis server.
+#[cfg(feature = "redis")]
+#[doc(cfg(feature = "redis"))]
+#[derive(Debug, Clone)]
+pub struct RedisStore {
+    client: redis::Client,
+    key: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisStore {
+    /// New store saving under `key` of the server behind `client`.
+    pub fn new<K: Into<String>>(client: redis::Client, key: K) -> Self {
+        Self {
+            client,
+            key: key.into(),
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+impl StateStore for RedisStore {
+    fn load(&self) -> Result<Option<GeneratorState>, UuidError> {
+        let mut conn = self.client.get_connection().map_err(|_| UuidError::State)?;
+        let bytes: Option<Vec<u8>> = redis::cmd("GET")
+            .arg(&self.key)
+            .query(&mut conn)
+            .map_err(|_| UuidError::State)?;
+        match bytes {
+            Some(b) => GeneratorState::from_bytes(&b)
+                .map(Some)
+                .ok_or(UuidError::State),
+            None => Ok(None),
+        }
+    }
+
+    fn save(&self, state: &GeneratorState) -> Result<(), UuidError> {
+        let mut conn = self.client.get_connection().map_err(|_| UuidError::State)?;
+        redis::cmd("SET")
+            .arg(&self.key)
+            .arg(&state.to_bytes()[..])
+            .query::<()>(&mut conn)
+            .map_err(|_| UuidError::State)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> GeneratorState {
+        GeneratorState {
+            node: Some(Node([0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80])),
+            clock_seq: 0x1234,
+            last_timestamp: 0x01ec_9414_c232_ab00,
+            counter: 7,
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        assert_eq!(
+            GeneratorState::from_bytes(&state().to_bytes()),
+            Some(state())
+        );
+        let no_node = GeneratorState::default();
+        assert_eq!(
+            GeneratorState::from_bytes(&no_node.to_bytes()),
+            Some(no_node)
+        );
+        assert_eq!(GeneratorState::from_bytes(&[2; STATE_LEN]), None);
+    }
+
+    #[test]
+    fn file_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("simple-uuid-state-{}", std::process::id()));
+        let store = FileStore::new(&path);
+        assert_eq!(store.load(), Ok(None));
+
+        store.save(&state()).unwrap();
+        assert_eq!(store.load(), Ok(Some(state())));
+
+        fs::write(&path, "clock_seq=x\n").unwrap();
+        assert_eq!(store.load(), Err(UuidError::State));
+        for node in [
+            "+32a350d1380",
+            "1032a350d1380",
+            "32a350d1380",
+            "032a350d138",
+        ] {
+            fs::write(&path, format!("node={}\n", node)).unwrap();
+            assert_eq!(store.load(), Err(UuidError::State), "{}", node);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn memory_store_round_trip() {
+        let store = MemoryStore::new();
+        assert_eq!(store.load(), Ok(None));
+        store.save(&state()).unwrap();
+        assert_eq!(store.load(), Ok(Some(state())));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn sled_store_round_trip() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStore::new(db.open_tree("uuid").unwrap(), "state");
+        assert_eq!(store.load(), Ok(None));
+        store.save(&state()).unwrap();
+        assert_eq!(store.load(), Ok(Some(state())));
+    }
+}
@@ -0,0 +1,115 @@
+//! Distribution of the bits of UUIDs, to check a source for bias.
+//!
+//! A source of random UUIDs sets every bit outside the version and variant
+//! about half of the time. [`bit_histogram`] counts how often each of the
+//! 128 bits is set and [`heatmap`] renders those counts as text, where a
+//! fixed bit of a misconfigured generator stands out:
+//!
+//! ```rust
+//! # #[cfg(any(feature = "rand_num", feature = "getrandom"))] {
+//! use simple_uuid::{stats, UUID};
+//!
+//! let uuids: Vec<UUID> = (0..1_000).map(|_| UUID::new_v4_forever().unwrap()).collect();
+//! let bits = stats::bit_histogram(&uuids);
+//! assert_eq!(bits[48..52], [0, 1_000, 0, 0]);
+//! println!("{}", stats::heatmap(&bits, 1_000));
+//! # }
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt::Write;
+
+use crate::UUID;
+
+/// Number of UUIDs of `uuids` with each bit set, the most significant bit
+/// of the first octet first.
+pub fn bit_histogram<I>(uuids: I) -> [u64; 128]
+where
+    I: IntoIterator,
+    I::Item: Borrow<UUID>,
+{
+    let mut counts = [0u64; 128];
+    for uuid in uuids {
+        let bits = uuid.borrow().as_u128();
+        for (i, count) in counts.iter_mut().enumerate() {
+            *count += (bits >> (127 - i) & 1) as u64;
+        }
+    }
+    counts
+}
+
+/// Number of UUIDs of `uuids` with each value of each octet, indexed by the
+/// position of the octet then by its value.
+pub fn byte_histogram<I>(uuids: I) -> [[u64; 256]; 16]
+where
+    I: IntoIterator,
+    I::Item: Borrow<UUID>,
+{
+    let mut counts = [[0u64; 256]; 16];
+    for uuid in uuids {
+        for (count, byte) in counts.iter_mut().zip(&uuid.borrow().0) {
+            count[usize::from(*byte)] += 1;
+        }
+    }
+    counts
+}
+
+/// Text map of a [`bit_histogram`] of `total` UUIDs, one line of four
+/// octets per 32 bits.
+///
+/// Each bit is `-` when never set, `#` when always set, and otherwise the
+/// tenth of the UUIDs setting it, from `0` to `9`: a uniform bit shows `4`
+/// or `5`.
+pub fn heatmap(bits: &[u64; 128], total: u64) -> String {
+    let mut map = String::new();
+    for (row, words) in bits.chunks(32).enumerate() {
+        let _ = write!(map, "{:2} ", row * 4);
+        for (i, count) in words.iter().enumerate() {
+            if i % 8 == 0 {
+                map.push(' ');
+            }
+            map.push(match *count {
+                0 => '-',
+                n if n >= total => '#',
+                n => char::from(b'0' + (n * 10 / total) as u8),
+            });
+        }
+        map.push('\n');
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histograms() {
+        let uuids = [UUID::NAMESPACE_DNS, UUID::default()];
+        let bits = bit_histogram(uuids);
+        // 6b: 0110_1011
+        assert_eq!(bits[..8], [0, 1, 1, 0, 1, 0, 1, 1]);
+        assert_eq!(
+            bits.iter().sum::<u64>(),
+            u64::from(UUID::NAMESPACE_DNS.as_u128().count_ones())
+        );
+
+        let bytes = byte_histogram(uuids.iter().copied());
+        assert_eq!(bytes[0][0x6b], 1);
+        assert_eq!(bytes[0][0], 1);
+        assert_eq!(bytes[15][0xc8], 1);
+    }
+
+    #[test]
+    fn heatmap_marks_fixed_bits() {
+        let mut bits = [50; 128];
+        bits[0] = 0;
+        bits[1] = 100;
+        bits[2] = 99;
+        let map = heatmap(&bits, 100);
+        let lines: Vec<_> = map.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], " 0  -#955555 55555555 55555555 55555555");
+        assert_eq!(lines[3], "12  55555555 55555555 55555555 55555555");
+    }
+}
@@ -0,0 +1,104 @@
+use std::io::{self, BufWriter, Write};
+
+use crate::format::MAX_ENCODED_LEN;
+#[cfg(any(feature = "mac_addr", feature = "rand_num", feature = "getrandom"))]
+use crate::UUID;
+use crate::{Format, Generator, Layout, UuidError, Version};
+
+/// Write `count` newly generated UUIDs of version `v` to `w` in `format`,
+/// one per line.
+///
+/// Every UUID is encoded into the same stack buffer, so no allocation
+/// happens per item, and a slow sink simply blocks generation.
+pub fn generate_stream<W: Write>(w: W, v: Version, count: usize, format: Format) -> io::Result<()> {
+    let generate = generator(v).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "version cannot be generated without input",
+        )
+    })?;
+    generate_stream_from(
+        w,
+        &|| generate().map(|layout| layout.to_uuid()),
+        count,
+        format,
+    )
+}
+
+/// Write `count` UUIDs of `generator` to `w` in `format`, one per line.
+///
+/// A failing generator stops the stream with an `io::Error` wrapping its
+/// `UuidError`.
+pub fn generate_stream_from<W: Write, G: Generator + ?Sized>(
+    w: W,
+    generator: &G,
+    count: usize,
+    format: Format,
+) -> io::Result<()> {
+    let mut buf = [0u8; MAX_ENCODED_LEN + 1];
+    let mut w = BufWriter::new(w);
+    for _ in 0..count {
+        let n = generator
+            .generate()
+            .map_err(io::Error::other)?
+            .encode_into(format, &mut buf)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unsupported format"))?;
+        buf[n] = b'\n';
+        w.write_all(&buf[..=n])?;
+    }
+    w.flush()
+}
+
+// Generator of version `v` with the enabled features, if any.
+fn generator(v: Version) -> Option<fn() -> Result<Layout, UuidError>> {
+    match v {
+        #[cfg(feature = "mac_addr")]
+        Version::TIME => Some(UUID::new_from_sys_time),
+        #[cfg(any(feature = "rand_num", feature = "getrandom"))]
+        Version::RAND => Some(UUID::new_from_rand),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "rand_num", feature = "getrandom"))]
+    #[test]
+    fn stream_into_sink() {
+        use crate::UUID;
+
+        let mut sink = Vec::new();
+        generate_stream(&mut sink, Version::RAND, 3, Format::Hyphenated).unwrap();
+
+        let out = String::from_utf8(sink).unwrap();
+        assert_eq!(out.lines().count(), 3);
+        for line in out.lines() {
+            assert_eq!(UUID::detect_format(line), Format::Hyphenated);
+        }
+    }
+
+    #[test]
+    fn stream_rejects_unsupported_input() {
+        let mut sink = Vec::new();
+        assert!(generate_stream(&mut sink, Version::MD5, 1, Format::Simple).is_err());
+        assert!(generate_stream(&mut sink, Version::RAND, 1, Format::Proquint).is_err());
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn stream_from_generator() {
+        let mut sink = Vec::new();
+        let generator = || Ok(crate::UUID::NAMESPACE_DNS);
+        generate_stream_from(&mut sink, &generator, 2, Format::Simple).unwrap();
+        assert_eq!(sink, b"6ba7b8109dad11d180b400c04fd430c8\n".repeat(2));
+
+        let failing = || Err(UuidError::Clock);
+        let err = generate_stream_from(&mut sink, &failing, 1, Format::Simple).unwrap_err();
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref(),
+            Some(&UuidError::Clock)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Synthetic UUID strings for test datasets.
+//!
+//! A `Mix` draws versions with configurable proportions and can inject
+//! duplicates and malformed strings, to exercise validation paths.
+
+use crate::fields::{VARIANT_BYTE, VARIANT_MASK, VARIANT_RFC, VERSION_BYTE, VERSION_MASK};
+use crate::{Version, UUID};
+
+/// Seed used unless `Mix::seed` is called.
+const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Endless, reproducible stream of UUID strings mixing versions by weight.
+///
+/// The UUIDs only carry the version and variant of their version; the other
+/// bits are pseudo-random, not derived from a clock or a name.
+#[derive(Debug, Clone)]
+pub struct Mix {
+    weights: Vec<(Version, f64)>,
+    total: f64,
+    duplicate: f64,
+    malformed: f64,
+    state: u64,
+    last: Option<String>,
+}
+
+impl Mix {
+    /// Mix of `weights`, pairs of a version and its relative weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no weight is positive.
+    pub fn new(weights: &[(Version, f64)]) -> Self {
+        let weights: Vec<_> = weights.iter().copied().filter(|(_, w)| *w > 0.0).collect();
+        let total = weights.iter().map(|(_, w)| w).sum();
+        assert!(!weights.is_empty(), "a mix needs a positive weight");
+        Self {
+            weights,
+            total,
+            duplicate: 0.0,
+            malformed: 0.0,
+            state: DEFAULT_SEED,
+            last: None,
+        }
+    }
+
+    /// Chance, from 0 to 1, of repeating the previous valid string.
+    pub fn duplicates(mut self, rate: f64) -> Self {
+        self.duplicate = rate;
+        self
+    }
+
+    /// Chance, from 0 to 1, of emitting a string that is not a valid UUID.
+    pub fn malformed(mut self, rate: f64) -> Self {
+        self.malformed = rate;
+        self
+    }
+
+    /// Seed of the stream, the same seed giving the same strings.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.state = seed | 1;
+        self
+    }
+
+    /// Next UUID of the mix, before duplicate or malformed injection.
+    pub fn next_uuid(&mut self) -> UUID {
+        let mut pick = (self.roll() >> 11) as f64 / (1u64 << 53) as f64 * self.total;
+        let mut version = self.weights[self.weights.len() - 1].0;
+        for (v, w) in self.weights.iter() {
+            if pick < *w {
+                version = *v;
+                break;
+            }
+            pick -= w;
+        }
+
+        let bits = u128::from(self.roll()) << 64 | u128::from(self.roll());
+        let mut bytes = bits.to_be_bytes();
+        bytes[VERSION_BYTE] = bytes[VERSION_BYTE] & !VERSION_MASK | (version as u8) << 4;
+        bytes[VARIANT_BYTE] = bytes[VARIANT_BYTE] & !VARIANT_MASK | VARIANT_RFC;
+        UUID(bytes)
+    }
+
+    fn malform(&mut self, s: String) -> String {
+        let mut b = s.into_bytes();
+        let i = self.roll() as usize % b.len();
+        match self.roll() % 3 {
+            // A character outside the hex alphabet.
+            0 => b[i] = b'g',
+            // A missing character.
+            1 => {
+                b.remove(i);
+            }
+            // A misplaced group separator.
+            _ => b.swap(8, 9),
+        }
+        String::from_utf8(b).unwrap()
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        p > 0.0 && ((self.roll() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    // xorshift64*, good enough for test data.
+    fn roll(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Iterator for Mix {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.last.is_some() && self.chance(self.duplicate) {
+            return self.last.clone();
+        }
+        let s = self.next_uuid().to_string();
+        if self.chance(self.malformed) {
+            return Some(self.malform(s));
+        }
+        self.last = Some(s.clone());
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proportions_follow_weights() {
+        let mix = Mix::new(&[(Version::RAND, 0.7), (Version::SHA1, 0.3)]);
+        let versions: Vec<_> = mix
+            .take(10_000)
+            .map(|s| UUID::parse_str(&s).unwrap().decode().get_version().unwrap())
+            .collect();
+        let rand = versions.iter().filter(|v| **v == Version::RAND).count();
+        assert!((6_700..7_300).contains(&rand), "{}", rand);
+        assert_eq!(
+            versions.iter().filter(|v| **v == Version::SHA1).count(),
+            10_000 - rand
+        );
+    }
+
+    #[test]
+    fn injects_duplicates_and_malformed() {
+        let mix = Mix::new(&[(Version::UNIX, 1.0)])
+            .duplicates(0.1)
+            .malformed(0.1)
+            .seed(7);
+        let strings: Vec<_> = mix.clone().take(1_000).collect();
+        assert_eq!(strings, mix.take(1_000).collect::<Vec<_>>());
+
+        let malformed = strings
+            .iter()
+            .filter(|s| UUID::parse_str(s).is_err())
+            .count();
+        let duplicates = strings.windows(2).filter(|w| w[0] == w[1]).count();
+        assert!((50..150).contains(&malformed), "{}", malformed);
+        assert!((50..150).contains(&duplicates), "{}", duplicates);
+    }
+}
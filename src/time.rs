@@ -1,14 +1,16 @@
 #![doc(cfg(feature = "mac_addr"))]
 #![cfg(feature = "mac_addr")]
 
-use mac_address;
-
-use crate::{Layout, Node, TimeStamp, Variant, Version, UUID};
+#[cfg(feature = "rand_num")]
+use crate::RandomNode;
+use crate::{Layout, Node, NodeSource, TimeStamp, UuidError, V1Context, Version, UUID};
 
 impl Layout {
     /// Get timestamp where the UUID generated in
     pub const fn get_timestamp(&self) -> u64 {
-        self.field_low as u64
+        ((self.field_high_and_version & 0xfff) as u64) << 48
+            | (self.field_mid as u64) << 32
+            | self.field_low as u64
     }
 
     /// Get the MAC-address where the UUID generated with
@@ -16,42 +18,101 @@ impl Layout {
         self.node
     }
 
-    fn time_fields(utc: u64, clock_seq: (u8, u8), node: Node) -> Self {
+    pub(crate) fn time_fields(utc: u64, clock_seq: (u8, u8), node: Node) -> Self {
         Self {
             field_low: (utc & 0xffff_ffff) as u32,
             field_mid: ((utc >> 32 & 0xffff) as u16),
             field_high_and_version: (utc >> 48 & 0xfff) as u16 | (Version::TIME as u16) << 12,
             clock_seq_high_and_reserved: clock_seq.0,
             clock_seq_low: clock_seq.1,
-            node: node,
+            node,
+        }
+    }
+
+    pub(crate) fn ordered_time_fields(utc: u64, clock_seq: (u8, u8), node: Node) -> Self {
+        Self {
+            field_low: (utc >> 28 & 0xffff_ffff) as u32,
+            field_mid: (utc >> 12 & 0xffff) as u16,
+            field_high_and_version: (utc & 0xfff) as u16 | (Version::REORDERED as u16) << 12,
+            clock_seq_high_and_reserved: clock_seq.0,
+            clock_seq_low: clock_seq.1,
+            node,
         }
     }
 }
 
+/// Version-1 and 6 UUIDs of this process share one clock sequence and last
+/// timestamp, see [`V1Context`](crate::V1Context).
 impl UUID {
-    /// New UUID version-1
-    pub fn new_from_sys_time() -> Layout {
-        let clock_seq: (u8, u8) = crate::clock_seq_high_and_reserved(Variant::RFC as u8);
-        let utc = TimeStamp::new();
-        Layout::time_fields(utc, clock_seq, device_mac_addr())
+    /// New UUID version-1, with the MAC-address of this device or, when it
+    /// has none, the random node of the process
+    pub fn new_from_sys_time() -> Result<Layout, UuidError> {
+        UUID::new_from_node(default_node()?)
+    }
+
+    /// New UUID version-1 with the node of `source`
+    pub fn new_from_source<S: NodeSource + ?Sized>(source: &S) -> Result<Layout, UuidError> {
+        UUID::new_from_node(source.node()?)
     }
 
     /// New UUID with a user defined MAC-address
-    pub fn new_from_node(node: Node) -> Layout {
-        let utc = TimeStamp::new();
-        let clock_seq = crate::clock_seq_high_and_reserved(Variant::RFC as u8);
-        Layout::time_fields(utc, clock_seq, node)
+    pub fn new_from_node(node: Node) -> Result<Layout, UuidError> {
+        let (utc, clock_seq) = V1Context::process()?.next(TimeStamp::new()?.ticks())?;
+        Ok(Layout::time_fields(utc, clock_seq, node))
+    }
+
+    /// New UUID version-1 with a fresh random multicast node, as
+    /// `uuid_generate_v1mc()` of the PostgreSQL `uuid-ossp` extension, so the
+    /// UUIDs do not reveal the device they were made on
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let uuid = UUID::v1mc().unwrap();
+    /// assert_eq!(uuid.get_node().0[0] & 0x01, 0x01);
+    /// ```
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn v1mc() -> Result<Layout, UuidError> {
+        UUID::new_from_node(Node::random()?)
+    }
+
+    /// New UUID with specific timestamp; every call advances the clock
+    /// sequence of the process, so UUIDs of the same timestamp differ
+    pub fn new_from_utc(utc: u64) -> Result<Layout, UuidError> {
+        let clock_seq = V1Context::process()?.bump_clock_seq()?;
+        Ok(Layout::time_fields(utc, clock_seq, default_node()?))
+    }
+}
+
+impl UUID {
+    /// New UUID version-6, sorting by creation time, with the same node as
+    /// [`UUID::new_from_sys_time`]
+    pub fn v6() -> Result<Layout, UuidError> {
+        UUID::v6_from_source(&default_node()?)
+    }
+
+    /// New UUID version-6 with the node of `source`
+    pub fn v6_from_source<S: NodeSource + ?Sized>(source: &S) -> Result<Layout, UuidError> {
+        let (utc, clock_seq) = V1Context::process()?.next(TimeStamp::new()?.ticks())?;
+        Ok(Layout::ordered_time_fields(utc, clock_seq, source.node()?))
     }
+}
 
-    /// New UUID with specific timestamp
-    pub fn new_from_utc(utc: u64) -> Layout {
-        let clock_seq = crate::clock_seq_high_and_reserved(Variant::RFC as u8);
-        Layout::time_fields(utc, clock_seq, device_mac_addr())
+pub(crate) fn device_mac_addr() -> Result<Node, UuidError> {
+    match mac_address::get_mac_address() {
+        Ok(Some(addr)) => Ok(Node(addr.bytes())),
+        _ => Err(UuidError::Node),
     }
 }
 
-fn device_mac_addr() -> Node {
-    Node(mac_address::get_mac_address().unwrap().unwrap().bytes())
+// The MAC-address of this device, else the random node of the process.
+pub(crate) fn default_node() -> Result<Node, UuidError> {
+    match device_mac_addr() {
+        #[cfg(feature = "rand_num")]
+        Err(UuidError::Node) => RandomNode.node(),
+        node => node,
+    }
 }
 
 /// `UUID` version-1
@@ -59,32 +120,85 @@ fn device_mac_addr() -> Node {
 #[macro_export]
 macro_rules! v1 {
     () => {
-        format!("{:x}", $crate::UUID::new_from_sys_time().as_bytes())
+        $crate::UUID::new_from_sys_time().map(|uuid| format!("{:x}", uuid.to_uuid()))
+    };
+}
+
+/// `UUID` version-6
+#[doc(cfg(feature = "mac_addr"))]
+#[macro_export]
+macro_rules! uuid_v6 {
+    () => {
+        $crate::UUID::v6().map(|uuid| format!("{:x}", uuid.to_uuid()))
     };
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Variant;
 
     #[test]
     fn new_uuid_from_timestamp() {
-        let uuid = UUID::new_from_sys_time();
+        let uuid = UUID::new_from_sys_time().unwrap();
         assert_eq!(uuid.get_version(), Some(Version::TIME));
         assert_eq!(uuid.get_variant(), Some(Variant::RFC));
     }
 
     #[test]
     fn new_uuid_from_user_defined_mac_address() {
-        let uuid = UUID::new_from_node(Node([0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80]));
+        let uuid = UUID::new_from_node(Node([0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80])).unwrap();
         assert_eq!(uuid.get_version(), Some(Version::TIME));
         assert_eq!(uuid.get_mac_addr().0, [0x03, 0x2a, 0x35, 0x0d, 0x13, 0x80]);
+        assert_eq!(uuid.get_node(), uuid.get_mac_addr());
+        assert!(uuid.get_clock_seq() < 1 << 14);
+
+        let node = Node([0x07, 0x2a, 0x35, 0x0d, 0x13, 0x80]);
+        assert_eq!(UUID::new_from_source(&node).unwrap().get_node(), node);
+        assert_eq!(UUID::v6_from_source(&node).unwrap().node, node);
+    }
+
+    #[cfg(feature = "rand_num")]
+    #[test]
+    fn new_uuid_v1mc() {
+        let a = UUID::v1mc().unwrap();
+        let b = UUID::v1mc().unwrap();
+        assert_eq!(a.get_version(), Some(Version::TIME));
+        assert_eq!(a.get_variant(), Some(Variant::RFC));
+        assert_eq!(a.get_node().0[0] & 0x01, 0x01);
+        assert_ne!(a.get_node(), b.get_node());
+        assert!(a.get_timestamp() < b.get_timestamp());
+    }
+
+    #[test]
+    fn new_uuid_v6() {
+        let uuid = UUID::v6().unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::REORDERED));
+        assert_eq!(uuid.get_variant(), Some(Variant::RFC));
+        assert!(uuid_v6!()
+            .unwrap()
+            .starts_with(&format!("{:08x}", uuid.field_low)[..4]));
+
+        // From the `rfc9562` v6 example.
+        let layout = Layout::ordered_time_fields(
+            0x01ec_9414_c232_ab00,
+            (0xb3, 0xc8),
+            Node([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]),
+        );
+        assert_eq!(layout.to_string(), "1ec9414c-232a-6b00-b3c8-9f6bdeced846");
     }
 
     #[test]
     fn new_uuid_from_custom_time() {
-        let uuid = UUID::new_from_utc(0x1234_u64);
+        let uuid = UUID::new_from_utc(0x1234_u64).unwrap();
         assert_eq!(uuid.get_version(), Some(Version::TIME));
         assert_eq!(uuid.get_timestamp(), 0x1234_u64);
+        let again = UUID::new_from_utc(0x1234_u64).unwrap();
+        assert_eq!(again.get_timestamp(), 0x1234_u64);
+        assert_ne!(again.to_uuid(), uuid.to_uuid());
+        assert_eq!(
+            UUID::NAMESPACE_DNS.decode().get_timestamp(),
+            0x01d1_9dad_6ba7_b810
+        );
     }
 }
@@ -0,0 +1,152 @@
+use core::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+use crate::UuidError;
+
+/// Where time-based generation reads the time from.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum TimeSource {
+    /// The system clock; generation fails with `UuidError::Clock` when it is unavailable.
+    System,
+    /// The system clock while it works, else a counter incremented from the
+    /// last time read, so generation keeps going in restricted sandboxes.
+    Fallback,
+}
+
+/// Change of the clock seen by `TimeSource::Fallback`.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ClockEvent {
+    /// The clock failed; timestamps now count up from `anchor`.
+    Degraded {
+        /// Last time read from the clock, in nanoseconds since the UNIX epoch.
+        anchor: u64,
+    },
+    /// The clock works again and returned `now`.
+    Recovered {
+        /// First time read after the failure, in nanoseconds since the UNIX epoch.
+        now: u64,
+    },
+}
+
+static SOURCE: AtomicU8 = AtomicU8::new(0);
+
+static STATE: FallbackState = FallbackState::new();
+
+/// Nanoseconds the fallback counter advances per reading, one version-1 tick.
+const FALLBACK_STEP: u64 = 100;
+
+impl TimeSource {
+    /// Use this source for all time-based generation of the process.
+    pub fn install(self) {
+        SOURCE.store(self as u8, Ordering::Relaxed);
+    }
+
+    /// The source currently used, `System` unless another was installed.
+    pub fn current() -> TimeSource {
+        match SOURCE.load(Ordering::Relaxed) {
+            1 => TimeSource::Fallback,
+            _ => TimeSource::System,
+        }
+    }
+
+    /// Call `hook` whenever the fallback source degrades or recovers.
+    pub fn on_event(hook: fn(ClockEvent)) {
+        *STATE.hook.write().unwrap_or_else(|e| e.into_inner()) = Some(hook);
+    }
+
+    /// Whether the fallback source is counting instead of reading the clock.
+    pub fn is_degraded() -> bool {
+        STATE.degraded.load(Ordering::Relaxed)
+    }
+
+    /// Time since the UNIX epoch according to the installed source.
+    pub(crate) fn now() -> Result<Duration, UuidError> {
+        let read = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|d| u64::try_from(d.as_nanos()).ok());
+        match TimeSource::current() {
+            TimeSource::System => read.map(Duration::from_nanos).ok_or(UuidError::Clock),
+            TimeSource::Fallback => Ok(Duration::from_nanos(STATE.next(read))),
+        }
+    }
+}
+
+struct FallbackState {
+    last: AtomicU64,
+    degraded: AtomicBool,
+    hook: RwLock<Option<fn(ClockEvent)>>,
+}
+
+impl FallbackState {
+    const fn new() -> Self {
+        Self {
+            last: AtomicU64::new(0),
+            degraded: AtomicBool::new(false),
+            hook: RwLock::new(None),
+        }
+    }
+
+    fn next(&self, read: Option<u64>) -> u64 {
+        match read {
+            Some(now) => {
+                self.last.fetch_max(now, Ordering::SeqCst);
+                if self.degraded.swap(false, Ordering::SeqCst) {
+                    self.notify(ClockEvent::Recovered { now });
+                }
+                now
+            }
+            None => {
+                let next = self.last.fetch_add(FALLBACK_STEP, Ordering::SeqCst) + FALLBACK_STEP;
+                if !self.degraded.swap(true, Ordering::SeqCst) {
+                    self.notify(ClockEvent::Degraded {
+                        anchor: next - FALLBACK_STEP,
+                    });
+                }
+                next
+            }
+        }
+    }
+
+    fn notify(&self, event: ClockEvent) {
+        if let Some(hook) = *self.hook.read().unwrap_or_else(|e| e.into_inner()) {
+            hook(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static EVENTS: Mutex<Vec<ClockEvent>> = Mutex::new(Vec::new());
+
+    #[test]
+    fn fallback_counts_from_last_time() {
+        let state = FallbackState::new();
+        *state.hook.write().unwrap() = Some(|e| EVENTS.lock().unwrap().push(e));
+
+        assert_eq!(state.next(Some(1_000)), 1_000);
+        assert_eq!(state.next(None), 1_100);
+        assert_eq!(state.next(None), 1_200);
+        assert_eq!(state.next(Some(5_000)), 5_000);
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            vec![
+                ClockEvent::Degraded { anchor: 1_000 },
+                ClockEvent::Recovered { now: 5_000 }
+            ]
+        );
+    }
+
+    #[test]
+    fn system_source_by_default() {
+        assert_eq!(TimeSource::current(), TimeSource::System);
+        assert!(TimeSource::now().unwrap() > Duration::from_secs(1_600_000_000));
+        assert!(!TimeSource::is_degraded());
+    }
+}
@@ -0,0 +1,235 @@
+use core::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Clock, Layout, SystemClock, TimeStamp, UuidError, UTC_EPOCH, UUID};
+
+/// 100-ns ticks in one millisecond.
+const TICKS_PER_MS: u64 = 10_000;
+
+impl TimeStamp {
+    /// Current time, read through the installed `TimeSource`.
+    pub fn new() -> Result<TimeStamp, UuidError> {
+        TimeStamp::from_clock(&SystemClock)
+    }
+
+    /// Time of `clock`.
+    pub(crate) fn from_clock<C: Clock + ?Sized>(clock: &C) -> Result<TimeStamp, UuidError> {
+        clock
+            .try_now_100ns()?
+            .checked_add(UTC_EPOCH)
+            .and_then(TimeStamp::from_ticks)
+            .ok_or(UuidError::Clock)
+    }
+
+    /// Time `ticks` 100-ns intervals past the Gregorian epoch, `None` if it
+    /// does not fit in 60 bits.
+    pub const fn from_ticks(ticks: u64) -> Option<TimeStamp> {
+        match ticks >> 60 {
+            0 => Some(TimeStamp(ticks)),
+            _ => None,
+        }
+    }
+
+    /// 100-ns intervals since the Gregorian epoch.
+    pub const fn ticks(&self) -> u64 {
+        self.0
+    }
+
+    /// Timestamp of `time`, truncated to 100 ns; fails with
+    /// `UuidError::Clock` before 1582-10-15 or past the year 5236.
+    pub fn from_system_time(time: SystemTime) -> Result<TimeStamp, UuidError> {
+        let ticks = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => u64::try_from(since.as_nanos() / 100)
+                .ok()
+                .and_then(|t| t.checked_add(UTC_EPOCH)),
+            Err(before) => u64::try_from(before.duration().as_nanos().div_ceil(100))
+                .ok()
+                .and_then(|t| UTC_EPOCH.checked_sub(t)),
+        };
+        ticks
+            .and_then(TimeStamp::from_ticks)
+            .ok_or(UuidError::Clock)
+    }
+
+    /// Timestamp `ms` milliseconds after the UNIX epoch; fails with
+    /// `UuidError::Clock` past the year 5236.
+    pub fn from_unix_millis(ms: u64) -> Result<TimeStamp, UuidError> {
+        ms.checked_mul(TICKS_PER_MS)
+            .and_then(|t| t.checked_add(UTC_EPOCH))
+            .and_then(TimeStamp::from_ticks)
+            .ok_or(UuidError::Clock)
+    }
+
+    /// Milliseconds since the UNIX epoch, `None` before it.
+    pub const fn to_unix_millis(&self) -> Option<u64> {
+        match self.0.checked_sub(UTC_EPOCH) {
+            Some(ticks) => Some(ticks / TICKS_PER_MS),
+            None => None,
+        }
+    }
+
+    /// Timestamp `secs` seconds after the UNIX epoch; fails with
+    /// `UuidError::Clock` past the year 5236.
+    pub fn from_unix_secs(secs: u64) -> Result<TimeStamp, UuidError> {
+        TimeStamp::from_unix_millis(secs.checked_mul(1_000).ok_or(UuidError::Clock)?)
+    }
+
+    /// Seconds since the UNIX epoch, `None` before it.
+    pub const fn to_unix_secs(&self) -> Option<u64> {
+        match self.to_unix_millis() {
+            Some(ms) => Some(ms / 1_000),
+            None => None,
+        }
+    }
+
+    /// The instant of the timestamp.
+    pub fn to_system_time(&self) -> SystemTime {
+        match self.0.checked_sub(UTC_EPOCH) {
+            Some(since) => UNIX_EPOCH + ticks_duration(since),
+            None => UNIX_EPOCH - ticks_duration(UTC_EPOCH - self.0),
+        }
+    }
+}
+
+// Duration of `ticks` 100-ns ticks, built from seconds since the ticks of
+// a Gregorian timestamp overflow `u64` nanoseconds after 584 years.
+fn ticks_duration(ticks: u64) -> Duration {
+    Duration::new(ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100)
+}
+
+impl UUID {
+    /// Time embedded in a version-1, 6 or 7 UUID, `None` for other versions
+    /// and for version-7 times past the year 5236.
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let uuid = UUID::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+    /// assert_eq!(uuid.get_time().and_then(|t| t.to_unix_secs()), Some(1_645_557_742));
+    /// ```
+    pub fn get_time(&self) -> Option<TimeStamp> {
+        let b = self.0;
+        let ticks = match self.get_version_num() {
+            1 => {
+                u64::from(b[6] & 0xf) << 56
+                    | u64::from(b[7]) << 48
+                    | u64::from(b[4]) << 40
+                    | u64::from(b[5]) << 32
+                    | u64::from(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+            }
+            6 => {
+                u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]]) << 12
+                    | u64::from(b[6] & 0xf) << 8
+                    | u64::from(b[7])
+            }
+            7 => return TimeStamp::from_unix_millis(self.timestamp_ms()?).ok(),
+            _ => return None,
+        };
+        Some(TimeStamp(ticks))
+    }
+
+    /// Milliseconds since the UNIX epoch embedded in a version-1, 6 or 7 UUID.
+    ///
+    /// Returns `None` for other versions and for Gregorian timestamps before 1970.
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        let b = self.0;
+        match self.get_version_num() {
+            1 | 6 => self.get_time()?.to_unix_millis(),
+            7 => Some(u64::from_be_bytes([
+                0, 0, b[0], b[1], b[2], b[3], b[4], b[5],
+            ])),
+            _ => None,
+        }
+    }
+}
+
+impl Layout {
+    /// Time embedded in a version-1, 6 or 7 UUID, `None` for other versions.
+    pub fn get_time(&self) -> Option<TimeStamp> {
+        self.to_uuid().get_time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_ms_v1() {
+        // 2022-02-22T19:22:22Z, from the `rfc9562` v1 example.
+        let uuid = UUID([
+            0xc2, 0x32, 0xab, 0x00, 0x94, 0x14, 0x11, 0xec, 0xb3, 0xc8, 0x9f, 0x6b, 0xde, 0xce,
+            0xd8, 0x46,
+        ]);
+        assert_eq!(uuid.timestamp_ms(), Some(1_645_557_742_000));
+    }
+
+    #[test]
+    fn timestamp_ms_v6() {
+        let uuid = UUID([
+            0x1e, 0xc9, 0x41, 0x4c, 0x23, 0x2a, 0x6b, 0x00, 0xb3, 0xc8, 0x9f, 0x6b, 0xde, 0xce,
+            0xd8, 0x46,
+        ]);
+        assert_eq!(uuid.timestamp_ms(), Some(1_645_557_742_000));
+    }
+
+    #[test]
+    fn timestamp_ms_v7() {
+        let uuid = UUID([
+            0x01, 0x7f, 0x22, 0xe2, 0x79, 0xb0, 0x7c, 0xc3, 0x98, 0xc4, 0xdc, 0x0c, 0x0c, 0x07,
+            0x39, 0x8f,
+        ]);
+        assert_eq!(uuid.timestamp_ms(), Some(1_645_557_742_000));
+    }
+
+    #[test]
+    fn system_time_round_trip() {
+        let uuid = UUID([
+            0xc2, 0x32, 0xab, 0x00, 0x94, 0x14, 0x11, 0xec, 0xb3, 0xc8, 0x9f, 0x6b, 0xde, 0xce,
+            0xd8, 0x46,
+        ]);
+        let time = uuid.get_time().unwrap();
+        assert_eq!(time.ticks(), 0x01ec_9414_c232_ab00);
+        let instant = UNIX_EPOCH + Duration::from_secs(1_645_557_742);
+        assert_eq!(time.to_system_time(), instant);
+        assert_eq!(TimeStamp::from_system_time(instant), Ok(time));
+
+        assert_eq!(
+            TimeStamp::from_system_time(UNIX_EPOCH).unwrap().ticks(),
+            UTC_EPOCH
+        );
+        let gregorian = UNIX_EPOCH - Duration::from_nanos(UTC_EPOCH * 100);
+        assert_eq!(TimeStamp::from_system_time(gregorian), Ok(TimeStamp(0)));
+        assert_eq!(TimeStamp(0).to_system_time(), gregorian);
+        assert_eq!(
+            TimeStamp::from_system_time(gregorian - Duration::from_nanos(1)),
+            Err(UuidError::Clock)
+        );
+        assert_eq!(TimeStamp::from_ticks(1 << 60), None);
+
+        let last = UUID::parse_str("ffffffff-ffff-1fff-8000-000000000000").unwrap();
+        let last = last.get_time().unwrap();
+        assert_eq!(last.ticks(), (1 << 60) - 1);
+        assert_eq!(TimeStamp::from_system_time(last.to_system_time()), Ok(last));
+
+        assert_eq!(time.to_unix_secs(), Some(1_645_557_742));
+        assert_eq!(time.to_unix_millis(), Some(1_645_557_742_000));
+        assert_eq!(TimeStamp::from_unix_secs(1_645_557_742), Ok(time));
+        assert_eq!(TimeStamp::from_unix_millis(1_645_557_742_000), Ok(time));
+        assert_eq!(TimeStamp(0).to_unix_millis(), None);
+        assert_eq!(TimeStamp::from_unix_secs(u64::MAX), Err(UuidError::Clock));
+
+        let now = TimeStamp::new().unwrap().to_system_time();
+        let elapsed = SystemTime::now().duration_since(now).unwrap();
+        assert!(elapsed < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn timestamp_ms_other_versions() {
+        assert_eq!(UUID::default().timestamp_ms(), None);
+        assert_eq!(
+            UUID([0, 0, 0, 0, 0, 0, 0x10, 0, 0x80, 0, 0, 0, 0, 0, 0, 0]).timestamp_ms(),
+            None
+        );
+    }
+}
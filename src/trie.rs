@@ -0,0 +1,234 @@
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+
+use crate::{ParseError, UUID};
+
+/// Prefix length under which [`UuidTrie::resolve_prefix`] refuses to look,
+/// as `git` does for abbreviated hashes.
+const DEFAULT_MIN_PREFIX_LEN: usize = 4;
+
+/// Outcome of [`UuidTrie::resolve_prefix`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Resolution {
+    /// Exactly one UUID starts with the prefix.
+    Unique(UUID),
+    /// Several UUIDs start with the prefix, in order.
+    Ambiguous(Vec<UUID>),
+    /// No UUID starts with the prefix.
+    NotFound,
+}
+
+/// Set of UUIDs resolving abbreviated hexadecimal prefixes, for short IDs
+/// in command-line and admin tools.
+///
+/// The UUIDs are kept in order, so the UUIDs of a prefix are a range of the
+/// set found in logarithmic time.
+///
+/// ```rust
+/// use simple_uuid::{Resolution, UuidTrie, UUID};
+///
+/// let trie: UuidTrie = [UUID::NAMESPACE_DNS, UUID::NAMESPACE_URL].iter().copied().collect();
+/// assert_eq!(trie.resolve_prefix("6ba7b81"), Ok(Resolution::Ambiguous(vec![
+///     UUID::NAMESPACE_DNS,
+///     UUID::NAMESPACE_URL,
+/// ])));
+/// assert_eq!(trie.resolve_prefix("6ba7b811"), Ok(Resolution::Unique(UUID::NAMESPACE_URL)));
+/// assert_eq!(trie.resolve_prefix("0000"), Ok(Resolution::NotFound));
+/// assert!(trie.resolve_prefix("6b").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct UuidTrie {
+    uuids: BTreeSet<UUID>,
+    min_prefix_len: usize,
+}
+
+impl Default for UuidTrie {
+    fn default() -> Self {
+        Self {
+            uuids: BTreeSet::new(),
+            min_prefix_len: DEFAULT_MIN_PREFIX_LEN,
+        }
+    }
+}
+
+impl UuidTrie {
+    /// New empty set, resolving prefixes of at least four digits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse prefixes of less than `len` hexadecimal digits, at most 32.
+    pub fn with_min_prefix_len(self, len: usize) -> Self {
+        Self {
+            min_prefix_len: len.min(32),
+            ..self
+        }
+    }
+
+    /// Minimal number of digits of a prefix.
+    pub fn min_prefix_len(&self) -> usize {
+        self.min_prefix_len
+    }
+
+    /// Add `uuid`, returning whether it was new.
+    pub fn insert(&mut self, uuid: UUID) -> bool {
+        self.uuids.insert(uuid)
+    }
+
+    /// Remove `uuid`, returning whether it was present.
+    pub fn remove(&mut self, uuid: &UUID) -> bool {
+        self.uuids.remove(uuid)
+    }
+
+    /// Whether `uuid` is in the set.
+    pub fn contains(&self, uuid: &UUID) -> bool {
+        self.uuids.contains(uuid)
+    }
+
+    /// Number of UUIDs.
+    pub fn len(&self) -> usize {
+        self.uuids.len()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.uuids.is_empty()
+    }
+
+    /// The UUIDs starting with `prefix`, hexadecimal digits of either case
+    /// where hyphens are ignored.
+    ///
+    /// Fails with `ParseError::InvalidLength` for prefixes shorter than
+    /// [`UuidTrie::min_prefix_len`] or longer than a UUID, and with
+    /// `ParseError::InvalidCharacter` for other characters.
+    pub fn resolve_prefix(&self, prefix: &str) -> Result<Resolution, ParseError> {
+        let mut value = 0u128;
+        let mut digits = 0;
+        for (index, c) in prefix.char_indices() {
+            if c == '-' {
+                continue;
+            }
+            let digit = c
+                .to_digit(16)
+                .ok_or(ParseError::InvalidCharacter { index, found: c })?;
+            if digits == 32 {
+                return Err(ParseError::InvalidLength {
+                    expected: 32,
+                    found: digits + 1,
+                });
+            }
+            value = value << 4 | u128::from(digit);
+            digits += 1;
+        }
+        if digits < self.min_prefix_len {
+            return Err(ParseError::InvalidLength {
+                expected: self.min_prefix_len,
+                found: digits,
+            });
+        }
+
+        let free = 128 - 4 * digits as u32;
+        let low = value.checked_shl(free).unwrap_or(0);
+        let high = low | u128::MAX.checked_shr(4 * digits as u32).unwrap_or(0);
+        let mut range = self
+            .uuids
+            .range(UUID::from_u128(low)..=UUID::from_u128(high));
+        Ok(match (range.next(), range.next()) {
+            (None, _) => Resolution::NotFound,
+            (Some(uuid), None) => Resolution::Unique(*uuid),
+            (Some(first), Some(second)) => {
+                let mut uuids = vec![*first, *second];
+                uuids.extend(range);
+                Resolution::Ambiguous(uuids)
+            }
+        })
+    }
+}
+
+impl FromIterator<UUID> for UuidTrie {
+    fn from_iter<I: IntoIterator<Item = UUID>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        trie.extend(iter);
+        trie
+    }
+}
+
+impl Extend<UUID> for UuidTrie {
+    fn extend<I: IntoIterator<Item = UUID>>(&mut self, iter: I) {
+        self.uuids.extend(iter);
+    }
+}
+
+#[cfg(all(test, feature = "rand_num"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_like_a_linear_scan() {
+        let mut uuids = vec![UUID::default(); 100_000];
+        UUID::fill_v4(&mut uuids).unwrap();
+        let trie: UuidTrie = uuids.iter().copied().collect();
+        assert_eq!(trie.len(), 100_000);
+
+        for uuid in uuids.iter().step_by(997) {
+            let hex = format!("{:x}", uuid);
+            assert_eq!(trie.resolve_prefix(&hex), Ok(Resolution::Unique(*uuid)));
+            assert_eq!(
+                trie.resolve_prefix(&hex.to_uppercase()),
+                Ok(Resolution::Unique(*uuid))
+            );
+
+            for len in [4, 5, 6] {
+                let prefix = &hex[..len];
+                let mut expected: Vec<_> = uuids
+                    .iter()
+                    .filter(|u| format!("{:x}", u).starts_with(prefix))
+                    .copied()
+                    .collect();
+                expected.sort();
+                let resolution = match expected.as_slice() {
+                    [only] => Resolution::Unique(*only),
+                    _ => Resolution::Ambiguous(expected),
+                };
+                assert_eq!(trie.resolve_prefix(prefix), Ok(resolution));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_short_and_invalid_prefixes() {
+        let trie: UuidTrie = [UUID::NAMESPACE_DNS].iter().copied().collect();
+        assert_eq!(
+            trie.resolve_prefix("6ba"),
+            Err(ParseError::InvalidLength {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            trie.resolve_prefix("6bz7"),
+            Err(ParseError::InvalidCharacter {
+                index: 2,
+                found: 'z'
+            })
+        );
+        assert_eq!(
+            trie.resolve_prefix("6ba7b810-9dad-11d1-80b4-00c04fd430c80"),
+            Err(ParseError::InvalidLength {
+                expected: 32,
+                found: 33
+            })
+        );
+        assert_eq!(
+            trie.resolve_prefix("6ba7b810-9d"),
+            Ok(Resolution::Unique(UUID::NAMESPACE_DNS))
+        );
+
+        let trie = trie.with_min_prefix_len(0);
+        assert_eq!(
+            trie.resolve_prefix(""),
+            Ok(Resolution::Unique(UUID::NAMESPACE_DNS))
+        );
+        assert_eq!(trie.resolve_prefix("7"), Ok(Resolution::NotFound));
+    }
+}
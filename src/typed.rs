@@ -0,0 +1,174 @@
+//! UUIDs whose version is known at compile time.
+//!
+//! ```rust
+//! use simple_uuid::typed::{Uuid, V7};
+//! use simple_uuid::UUID;
+//!
+//! fn created_at(key: Uuid<V7>) -> u64 {
+//!     key.timestamp_ms()
+//! }
+//!
+//! assert!(Uuid::<V7>::try_downcast(UUID::NAMESPACE_DNS).is_err());
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::format::decode_hex_formats;
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+use crate::UuidError;
+use crate::UUID;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Type-level UUID version.
+pub trait VersionMarker: sealed::Sealed {
+    /// Version number held in the UUID.
+    const NUM: u8;
+}
+
+macro_rules! marker {
+    ($($(#[$doc:meta])* $name:ident = $num:expr;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+            pub struct $name;
+
+            impl sealed::Sealed for $name {}
+
+            impl VersionMarker for $name {
+                const NUM: u8 = $num;
+            }
+        )*
+    };
+}
+
+marker! {
+    /// Time-based version-1.
+    V1 = 1;
+    /// DCE Security version-2.
+    V2 = 2;
+    /// MD5 name-based version-3.
+    V3 = 3;
+    /// Random version-4.
+    V4 = 4;
+    /// SHA-1 name-based version-5.
+    V5 = 5;
+    /// Reordered time-based version-6.
+    V6 = 6;
+    /// UNIX epoch time-based version-7.
+    V7 = 7;
+    /// Custom version-8.
+    V8 = 8;
+}
+
+/// A UUID statically known to be of version `V`.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub struct Uuid<V: VersionMarker> {
+    uuid: UUID,
+    version: PhantomData<V>,
+}
+
+impl<V: VersionMarker> Uuid<V> {
+    /// Check the version of `uuid`, handing it back on mismatch.
+    pub fn try_downcast(uuid: UUID) -> Result<Self, UUID> {
+        match uuid.get_version_num() == V::NUM {
+            true => Ok(Uuid {
+                uuid,
+                version: PhantomData,
+            }),
+            false => Err(uuid),
+        }
+    }
+
+    /// Parse the simple, hyphenated, braced or urn form and check its version.
+    pub fn parse(s: &str) -> Option<Self> {
+        decode_hex_formats(s).and_then(|uuid| Self::try_downcast(uuid).ok())
+    }
+
+    /// Erase the version.
+    pub const fn upcast(self) -> UUID {
+        self.uuid
+    }
+}
+
+impl Uuid<V1> {
+    /// Milliseconds since the UNIX epoch, `None` for timestamps before 1970.
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        self.uuid.timestamp_ms()
+    }
+}
+
+impl Uuid<V6> {
+    /// Milliseconds since the UNIX epoch, `None` for timestamps before 1970.
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        self.uuid.timestamp_ms()
+    }
+}
+
+impl Uuid<V7> {
+    /// Milliseconds since the UNIX epoch.
+    pub fn timestamp_ms(&self) -> u64 {
+        self.uuid.timestamp_ms().unwrap()
+    }
+}
+
+#[cfg(any(feature = "rand_num", feature = "getrandom"))]
+impl Uuid<V4> {
+    /// New random UUID.
+    #[doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+    pub fn new() -> Result<Self, UuidError> {
+        Ok(Uuid {
+            uuid: UUID::new_from_rand()?.to_uuid(),
+            version: PhantomData,
+        })
+    }
+}
+
+impl<V: VersionMarker> From<Uuid<V>> for UUID {
+    fn from(uuid: Uuid<V>) -> Self {
+        uuid.upcast()
+    }
+}
+
+impl<V: VersionMarker> fmt::LowerHex for Uuid<V> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.uuid, fmt)
+    }
+}
+
+impl<V: VersionMarker> fmt::UpperHex for Uuid<V> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.uuid, fmt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downcast_checks_version() {
+        let v1 = Uuid::<V1>::try_downcast(UUID::NAMESPACE_DNS).unwrap();
+        assert_eq!(v1.upcast(), UUID::NAMESPACE_DNS);
+        assert_eq!(
+            Uuid::<V4>::try_downcast(UUID::NAMESPACE_DNS),
+            Err(UUID::NAMESPACE_DNS)
+        );
+    }
+
+    #[test]
+    fn parse_and_timestamp() {
+        let v7 = Uuid::<V7>::parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+        assert_eq!(v7.timestamp_ms(), 1_645_557_742_000);
+        assert!(Uuid::<V6>::parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").is_none());
+    }
+
+    #[cfg(any(feature = "rand_num", feature = "getrandom"))]
+    #[test]
+    fn new_v4() {
+        assert_eq!(Uuid::<V4>::new().unwrap().upcast().get_version_num(), 4);
+    }
+}
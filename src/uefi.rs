@@ -0,0 +1,91 @@
+//! GUIDs of UEFI firmware and conversions to the `uefi` crate.
+//!
+//! The conversions copy the 16 octets of the GUID without allocating. This
+//! crate needs `std`, so they serve host tools such as firmware updaters
+//! and variable editors, not pre-boot code.
+#![doc(cfg(feature = "uefi"))]
+#![cfg(feature = "uefi")]
+
+use crate::{Guid, UUID};
+
+/// Vendor GUID of the UEFI global variables, such as `BootOrder`.
+pub const GLOBAL_VARIABLE: UUID = UUID::from_fields(
+    0x8be4_df61,
+    0x93ca,
+    0x11d2,
+    &[0xaa, 0x0d, 0x00, 0xe0, 0x98, 0x03, 0x2b, 0x8c],
+);
+
+/// Vendor GUID of the Secure Boot signature databases `db` and `dbx`.
+pub const IMAGE_SECURITY_DATABASE: UUID = UUID::from_fields(
+    0xd719_b2cb,
+    0x3d3a,
+    0x4596,
+    &[0xa3, 0xbc, 0xda, 0xd0, 0x0e, 0x67, 0x65, 0x6f],
+);
+
+/// Configuration table GUID of the EFI System Resource Table (ESRT).
+pub const ESRT: UUID = UUID::from_fields(
+    0xb122_a263,
+    0x3661,
+    0x4f68,
+    &[0x99, 0x29, 0x78, 0xf8, 0xb0, 0xd6, 0x21, 0x80],
+);
+
+/// Vendor GUID of the `CapsuleNNNN` variables reporting capsule updates.
+pub const CAPSULE_REPORT: UUID = UUID::from_fields(
+    0x39b6_8c46,
+    0xf7fb,
+    0x441b,
+    &[0xb6, 0xec, 0x16, 0xb0, 0xf6, 0x98, 0x21, 0xf3],
+);
+
+/// Capsule GUID of firmware management protocol update capsules.
+pub const FIRMWARE_MANAGEMENT_CAPSULE: UUID = UUID::from_fields(
+    0x6dcb_d5ed,
+    0xe82d,
+    0x4c44,
+    &[0xbd, 0xa1, 0x71, 0x94, 0x19, 0x9a, 0xd9, 0x2a],
+);
+
+impl From<Guid> for ::uefi::Guid {
+    fn from(guid: Guid) -> Self {
+        ::uefi::Guid::from_bytes(guid.to_bytes_le())
+    }
+}
+
+impl From<::uefi::Guid> for Guid {
+    fn from(guid: ::uefi::Guid) -> Self {
+        Guid::from_bytes_le(guid.to_bytes())
+    }
+}
+
+impl From<UUID> for ::uefi::Guid {
+    fn from(uuid: UUID) -> Self {
+        Guid::from_uuid(uuid).into()
+    }
+}
+
+impl From<::uefi::Guid> for UUID {
+    fn from(guid: ::uefi::Guid) -> Self {
+        Guid::from(guid).to_uuid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uefi_guid_conversions() {
+        assert_eq!(
+            ::uefi::Guid::from(GLOBAL_VARIABLE),
+            ::uefi::guid!("8be4df61-93ca-11d2-aa0d-00e098032b8c")
+        );
+        assert_eq!(::uefi::Guid::from(ESRT), ::uefi::table::cfg::ESRT_GUID);
+        let guid = ::uefi::Guid::from(CAPSULE_REPORT);
+        assert_eq!(guid.to_string(), CAPSULE_REPORT.to_string());
+        assert_eq!(UUID::from(guid), CAPSULE_REPORT);
+        assert_eq!(Guid::from(guid), Guid::from(CAPSULE_REPORT));
+    }
+}
@@ -0,0 +1,170 @@
+#![doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+#![cfg(any(feature = "rand_num", feature = "getrandom"))]
+
+use core::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "rand_num")]
+use rand_core::RngCore;
+
+use crate::entropy;
+use crate::fields::VARIANT_RFC;
+use crate::{Clock, Layout, Node, SystemClock, UuidError, Version, UUID};
+
+impl Layout {
+    fn unix_fields(ms: u64, rand: [u8; 10]) -> Self {
+        Self {
+            field_low: (ms >> 16 & 0xffff_ffff) as u32,
+            field_mid: (ms & 0xffff) as u16,
+            field_high_and_version: ((rand[0] as u16) << 8 | rand[1] as u16) & 0xfff
+                | (Version::UNIX as u16) << 12,
+            clock_seq_high_and_reserved: (rand[2] & 0x3f) | VARIANT_RFC,
+            clock_seq_low: rand[3],
+            node: Node([rand[4], rand[5], rand[6], rand[7], rand[8], rand[9]]),
+        }
+    }
+}
+
+impl UUID {
+    /// New UUID version-7 from the UNIX time in milliseconds and random bits
+    pub fn v7() -> Result<Layout, UuidError> {
+        UUID::v7_with_clock(&SystemClock)
+    }
+
+    /// New UUID version-7 at the time of `clock`.
+    pub fn v7_with_clock<C: Clock + ?Sized>(clock: &C) -> Result<Layout, UuidError> {
+        UUID::v7_from_millis(clock.try_now_100ns()? / 10_000)
+    }
+
+    /// Overwrite every UUID of `uuids` with a new version-7 UUID of the
+    /// current millisecond, drawing the random bits in bulk.
+    ///
+    /// The UUIDs are sorted, so the slice is increasing as if generated one
+    /// after the other; unlike those of a [`V7Generator`](crate::V7Generator),
+    /// they are not ordered with UUIDs of other calls in the same millisecond.
+    ///
+    /// ```rust
+    /// use simple_uuid::UUID;
+    ///
+    /// let mut uuids = vec![UUID::default(); 1_000];
+    /// UUID::fill_v7(&mut uuids).unwrap();
+    /// assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    pub fn fill_v7(uuids: &mut [UUID]) -> Result<(), UuidError> {
+        let ms = (SystemClock.try_now_100ns()? / 10_000).to_be_bytes();
+        entropy::fill_uuids(uuids)?;
+        for uuid in uuids.iter_mut() {
+            uuid.0[..6].copy_from_slice(&ms[2..]);
+            uuid.0[6] = uuid.0[6] & 0x0f | (Version::UNIX as u8) << 4;
+            uuid.0[8] = uuid.0[8] & 0x3f | VARIANT_RFC;
+        }
+        uuids.sort_unstable();
+        Ok(())
+    }
+
+    /// New UUID version-7 embedding `time`, for backfilling historical records;
+    /// fails for times before the UNIX epoch or past the year 10889.
+    pub fn v7_from_timestamp(time: SystemTime) -> Result<Layout, UuidError> {
+        let ms = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| UuidError::Clock)?
+            .as_millis();
+        UUID::v7_from_millis(u64::try_from(ms).map_err(|_| UuidError::Clock)?)
+    }
+
+    /// New UUID version-7 embedding `ms` milliseconds since the UNIX epoch;
+    /// fails if `ms` does not fit in 48 bits.
+    pub fn v7_from_millis(ms: u64) -> Result<Layout, UuidError> {
+        if ms >> 48 != 0 {
+            return Err(UuidError::Clock);
+        }
+        let mut rand = [0u8; 10];
+        entropy::fill(&mut rand)?;
+        Ok(Layout::unix_fields(ms, rand))
+    }
+
+    /// New UUID version-7 with the random bits of `rng`, see
+    /// [`UUID::v4_from_rng`].
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn v7_from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Result<Layout, UuidError> {
+        UUID::v7_from_millis_and_rng(SystemClock.try_now_100ns()? / 10_000, rng)
+    }
+
+    /// New UUID version-7 embedding `ms` milliseconds since the UNIX epoch,
+    /// with the random bits of `rng`; fails if `ms` does not fit in 48 bits.
+    #[cfg(feature = "rand_num")]
+    #[doc(cfg(feature = "rand_num"))]
+    pub fn v7_from_millis_and_rng<R: RngCore + ?Sized>(
+        ms: u64,
+        rng: &mut R,
+    ) -> Result<Layout, UuidError> {
+        if ms >> 48 != 0 {
+            return Err(UuidError::Clock);
+        }
+        let mut rand = [0u8; 10];
+        rng.try_fill_bytes(&mut rand)
+            .map_err(|_| UuidError::Entropy)?;
+        Ok(Layout::unix_fields(ms, rand))
+    }
+}
+
+/// `UUID` version-7
+#[doc(cfg(any(feature = "rand_num", feature = "getrandom")))]
+#[macro_export]
+macro_rules! uuid_v7 {
+    () => {
+        $crate::UUID::v7().map(|uuid| format!("{:x}", uuid.to_uuid()))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn new_uuid_v7() {
+        let uuid = UUID::v7().unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::UNIX));
+        assert_eq!(uuid.get_variant(), Some(Variant::RFC));
+        assert!(uuid.to_uuid().timestamp_ms().unwrap() > 1_600_000_000_000);
+        assert_eq!(uuid_v7!().unwrap().as_bytes()[14], b'7');
+    }
+
+    #[test]
+    fn v7_backfill() {
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1_645_557_742_000);
+        let a = UUID::v7_from_timestamp(time).unwrap().to_uuid();
+        let b = UUID::v7_from_millis(1_645_557_742_000).unwrap().to_uuid();
+        assert_eq!(a.timestamp_ms(), Some(1_645_557_742_000));
+        assert_eq!(a.prefix_bytes::<6>(), b.prefix_bytes::<6>());
+        assert_ne!(a, b);
+
+        assert!(UUID::v7_from_millis(1 << 48).is_err());
+        assert!(UUID::v7_from_timestamp(UNIX_EPOCH - std::time::Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn fill_v7() {
+        let before = UUID::v7().unwrap().to_uuid();
+        let mut uuids = vec![UUID::default(); 1_000];
+        UUID::fill_v7(&mut uuids).unwrap();
+        assert!(uuids.windows(2).all(|w| w[0] < w[1]));
+        for uuid in &uuids {
+            assert_eq!(uuid.decode().get_version(), Some(Version::UNIX));
+            assert_eq!(uuid.decode().get_variant(), Some(Variant::RFC));
+            assert!(uuid.timestamp_ms() >= before.timestamp_ms());
+        }
+    }
+
+    #[test]
+    fn unix_fields_layout() {
+        // From the `rfc9562` v7 example.
+        let layout = Layout::unix_fields(
+            0x017f_22e2_79b0,
+            [0xcc, 0xc3, 0x98, 0xc4, 0xdc, 0x0c, 0x0c, 0x07, 0x39, 0x8f],
+        );
+        assert_eq!(layout.to_string(), "017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+    }
+}
@@ -0,0 +1,147 @@
+use core::fmt;
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+use crate::{Version, UUID};
+
+/// Set of version numbers, one bit per number, for validators.
+///
+/// ```rust
+/// use simple_uuid::{UUID, VersionSet};
+///
+/// const TIME_ORDERED: VersionSet = VersionSet::V6.union(VersionSet::V7);
+/// assert!(!UUID::NAMESPACE_DNS.version_in(TIME_ORDERED));
+/// assert!(UUID::NAMESPACE_DNS.version_in(VersionSet::V1 | VersionSet::V4));
+/// ```
+#[derive(Eq, PartialEq, Hash, Default, Clone, Copy)]
+pub struct VersionSet(u16);
+
+impl VersionSet {
+    /// No version.
+    pub const EMPTY: VersionSet = VersionSet(0);
+    /// Every version number, including those this crate has no `Version` for.
+    pub const ANY: VersionSet = VersionSet(u16::MAX);
+    /// Version-1, time-based.
+    pub const V1: VersionSet = VersionSet::of(Version::TIME);
+    /// Version-2, DCE Security.
+    pub const V2: VersionSet = VersionSet::of(Version::DCE);
+    /// Version-3, name-based with MD5.
+    pub const V3: VersionSet = VersionSet::of(Version::MD5);
+    /// Version-4, random.
+    pub const V4: VersionSet = VersionSet::of(Version::RAND);
+    /// Version-5, name-based with SHA-1.
+    pub const V5: VersionSet = VersionSet::of(Version::SHA1);
+    /// Version-6, reordered time.
+    pub const V6: VersionSet = VersionSet::of(Version::REORDERED);
+    /// Version-7, UNIX time.
+    pub const V7: VersionSet = VersionSet::of(Version::UNIX);
+    /// Version-8, custom.
+    pub const V8: VersionSet = VersionSet::of(Version::CUSTOM);
+
+    /// Set of the single `version`.
+    pub const fn of(version: Version) -> VersionSet {
+        VersionSet::from_num(version as u8)
+    }
+
+    /// Set of the single version number `num`, empty past 15.
+    pub const fn from_num(num: u8) -> VersionSet {
+        match num {
+            0..=15 => VersionSet(1 << num),
+            _ => VersionSet::EMPTY,
+        }
+    }
+
+    /// Versions of either set.
+    pub const fn union(self, other: VersionSet) -> VersionSet {
+        VersionSet(self.0 | other.0)
+    }
+
+    /// Versions of both sets.
+    pub const fn intersection(self, other: VersionSet) -> VersionSet {
+        VersionSet(self.0 & other.0)
+    }
+
+    /// Whether the set has the version number `num`.
+    pub const fn contains_num(self, num: u8) -> bool {
+        num < 16 && self.0 >> num & 1 == 1
+    }
+
+    /// Whether the set has `version`.
+    pub const fn contains(self, version: Version) -> bool {
+        self.contains_num(version as u8)
+    }
+
+    /// Whether the set has no version.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for VersionSet {
+    type Output = VersionSet;
+
+    fn bitor(self, other: VersionSet) -> VersionSet {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for VersionSet {
+    fn bitor_assign(&mut self, other: VersionSet) {
+        *self = self.union(other);
+    }
+}
+
+impl BitAnd for VersionSet {
+    type Output = VersionSet;
+
+    fn bitand(self, other: VersionSet) -> VersionSet {
+        self.intersection(other)
+    }
+}
+
+impl From<Version> for VersionSet {
+    fn from(version: Version) -> Self {
+        VersionSet::of(version)
+    }
+}
+
+impl fmt::Debug for VersionSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set()
+            .entries((0..16).filter(|n| self.contains_num(*n)))
+            .finish()
+    }
+}
+
+impl UUID {
+    /// Whether the version number of the UUID is in `set`, whatever its
+    /// variant.
+    pub const fn version_in(&self, set: VersionSet) -> bool {
+        set.contains_num(self.get_version_num())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let mut set = VersionSet::V4 | VersionSet::V7;
+        assert!(set.contains(Version::RAND) && set.contains(Version::UNIX));
+        assert!(!set.contains(Version::TIME) && !set.contains_num(16));
+        assert_eq!(set & VersionSet::V7, VersionSet::V7);
+        assert!((set & VersionSet::V1).is_empty());
+        set |= VersionSet::from_num(15);
+        assert_eq!(format!("{:?}", set), "{4, 7, 15}");
+        assert_eq!(VersionSet::from(Version::SHA1), VersionSet::V5);
+        assert!(VersionSet::ANY.contains_num(0));
+    }
+
+    #[test]
+    fn uuid_version_in() {
+        assert!(UUID::NAMESPACE_DNS.version_in(VersionSet::V1));
+        assert!(!UUID::NAMESPACE_DNS.version_in(VersionSet::V4 | VersionSet::V7));
+        assert!(UUID::NIL.version_in(VersionSet::from_num(0)));
+        assert!(!UUID::MAX.version_in(VersionSet::ANY.intersection(VersionSet::V8)));
+    }
+}
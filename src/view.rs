@@ -0,0 +1,181 @@
+//! Fields of version-6, 7 and 8 UUIDs under their `rfc9562` names.
+//!
+//! `Layout` names the fields of a version-1 UUID; these views give the
+//! other layouts of `rfc9562` their own field semantics.
+
+use core::fmt;
+
+use crate::fields::{VARIANT_BYTE, VARIANT_MASK, VARIANT_RFC};
+use crate::{Node, UTC_EPOCH, UUID};
+
+/// Fields of a version-6 UUID, the reordered Gregorian time layout.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct V6View {
+    /// Most significant 32 bits of the 60-bit timestamp.
+    pub time_high: u32,
+    /// Middle 16 bits of the timestamp.
+    pub time_mid: u16,
+    /// Least significant 12 bits of the timestamp.
+    pub time_low: u16,
+    /// 14-bit clock sequence.
+    pub clock_seq: u16,
+    /// IEEE 802 MAC-address, or random bits.
+    pub node: Node,
+}
+
+/// Fields of a version-7 UUID, the UNIX time layout.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct V7View {
+    /// 48-bit milliseconds since the UNIX epoch.
+    pub unix_ts_ms: u64,
+    /// 12 random bits, or a counter.
+    pub rand_a: u16,
+    /// 62 random bits.
+    pub rand_b: u64,
+}
+
+/// Fields of a version-8 UUID, whose content is application-defined.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct V8View {
+    /// First 48 bits.
+    pub custom_a: u64,
+    /// 12 bits after the version.
+    pub custom_b: u16,
+    /// 62 bits after the variant.
+    pub custom_c: u64,
+}
+
+impl V6View {
+    /// 60-bit count of 100-ns intervals since 1582-10-15.
+    pub const fn timestamp(&self) -> u64 {
+        (self.time_high as u64) << 28 | (self.time_mid as u64) << 12 | self.time_low as u64
+    }
+
+    /// Milliseconds since the UNIX epoch, if not before it.
+    pub const fn unix_ts_ms(&self) -> Option<u64> {
+        match self.timestamp().checked_sub(UTC_EPOCH) {
+            Some(ticks) => Some(ticks / 10_000),
+            None => None,
+        }
+    }
+}
+
+impl UUID {
+    /// Fields of a version-6 UUID of the `rfc4122` variant.
+    pub fn v6_view(&self) -> Option<V6View> {
+        let b = self.rfc_fields(6)?;
+        Some(V6View {
+            time_high: u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            time_mid: u16::from_be_bytes([b[4], b[5]]),
+            time_low: u16::from_be_bytes([b[6], b[7]]) & 0xfff,
+            clock_seq: u16::from_be_bytes([b[8], b[9]]) & 0x3fff,
+            node: Node([b[10], b[11], b[12], b[13], b[14], b[15]]),
+        })
+    }
+
+    /// Fields of a version-7 UUID of the `rfc4122` variant.
+    pub fn v7_view(&self) -> Option<V7View> {
+        let (a, b, c) = self.rfc_parts(7)?;
+        Some(V7View {
+            unix_ts_ms: a,
+            rand_a: b,
+            rand_b: c,
+        })
+    }
+
+    /// Fields of a version-8 UUID of the `rfc4122` variant.
+    pub fn v8_view(&self) -> Option<V8View> {
+        let (a, b, c) = self.rfc_parts(8)?;
+        Some(V8View {
+            custom_a: a,
+            custom_b: b,
+            custom_c: c,
+        })
+    }
+
+    // The octets, if of version `v` and of the `rfc4122` variant.
+    fn rfc_fields(&self, v: u8) -> Option<[u8; 16]> {
+        match (self.get_version_num(), self.0[VARIANT_BYTE] & VARIANT_MASK) {
+            (n, VARIANT_RFC) if n == v => Some(self.0),
+            _ => None,
+        }
+    }
+
+    // The 48, 12 and 62 bits around the version and variant.
+    fn rfc_parts(&self, v: u8) -> Option<(u64, u16, u64)> {
+        let b = self.rfc_fields(v)?;
+        Some((
+            u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]]),
+            u16::from_be_bytes([b[6], b[7]]) & 0xfff,
+            u64::from_be_bytes([b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]])
+                & 0x3fff_ffff_ffff_ffff,
+        ))
+    }
+}
+
+impl fmt::Display for V6View {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "time_high={:08x} time_mid={:04x} time_low={:03x} clock_seq={:04x} node={}",
+            self.time_high, self.time_mid, self.time_low, self.clock_seq, self.node
+        )
+    }
+}
+
+impl fmt::Display for V7View {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "unix_ts_ms={} rand_a={:03x} rand_b={:016x}",
+            self.unix_ts_ms, self.rand_a, self.rand_b
+        )
+    }
+}
+
+impl fmt::Display for V8View {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "custom_a={:012x} custom_b={:03x} custom_c={:016x}",
+            self.custom_a, self.custom_b, self.custom_c
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v7_fields() {
+        // rfc9562 appendix A.6
+        let uuid = UUID::from_u128(0x017f_22e2_79b0_7cc3_98c4_dc0c_0c07_398f);
+        let view = uuid.v7_view().unwrap();
+        assert_eq!(view.unix_ts_ms, 1_645_557_742_000);
+        assert_eq!(view.rand_a, 0xcc3);
+        assert_eq!(view.rand_b, 0x18c4_dc0c_0c07_398f);
+        assert_eq!(
+            view.to_string(),
+            "unix_ts_ms=1645557742000 rand_a=cc3 rand_b=18c4dc0c0c07398f"
+        );
+        assert_eq!(uuid.v6_view(), None);
+        assert_eq!(UUID::NAMESPACE_DNS.v7_view(), None);
+    }
+
+    #[test]
+    fn v6_and_v8_fields() {
+        // rfc9562 appendix A.5
+        let uuid = UUID::from_u128(0x1ec9_414c_232a_6b00_b3c8_9f6b_dece_d846);
+        let view = uuid.v6_view().unwrap();
+        assert_eq!(view.timestamp(), 0x01ec_9414_c232_ab00);
+        assert_eq!(view.clock_seq, 0x33c8);
+        assert_eq!(view.unix_ts_ms(), uuid.timestamp_ms());
+
+        let v8 = UUID::v8([0xff; 16]).v8_view().unwrap();
+        assert_eq!(
+            (v8.custom_a, v8.custom_b, v8.custom_c),
+            (0xffff_ffff_ffff, 0xfff, 0x3fff_ffff_ffff_ffff)
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Validated UUID parameters for web handlers.
+//!
+//! [`UuidParam`] parses a path or query parameter and checks it against a
+//! [`ParamPolicy`], failing with a [`ParamError`] meant to become a
+//! `400 Bad Request`. It is framework-agnostic through [`FromStr`], and
+//! extracts directly in Axum and Actix handlers with the `axum` and
+//! `actix-web` features.
+//!
+//! ```
+//! use simple_uuid::web::{ParamPolicy, UuidParam};
+//! use simple_uuid::Version;
+//!
+//! struct Orders;
+//!
+//! impl ParamPolicy for Orders {
+//!     const VERSIONS: &'static [Version] = &[Version::RAND, Version::UNIX];
+//! }
+//!
+//! let id: UuidParam<Orders> = "936da01f-9abd-4d9d-80c7-02af85c822a8".parse().unwrap();
+//! assert_eq!(id.get_version_num(), 4);
+//! assert!("6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse::<UuidParam<Orders>>().is_err());
+//! ```
+
+mod actix;
+mod axum;
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::{ParseError, ParseOptions, Version, UUID};
+
+/// Checks applied to a [`UuidParam`], set at the type level so that the
+/// parameter can be extracted without handler-side state.
+pub trait ParamPolicy {
+    /// Accepted versions; empty accepts any version.
+    const VERSIONS: &'static [Version] = &[];
+    /// Reject the nil UUID.
+    const REJECT_NIL: bool = true;
+}
+
+/// Accepts any non-nil UUID.
+#[derive(Debug, Clone, Copy)]
+pub struct AnyUuid;
+
+impl ParamPolicy for AnyUuid {}
+
+/// A UUID request parameter that satisfies the policy `P`.
+pub struct UuidParam<P = AnyUuid>(UUID, PhantomData<fn() -> P>);
+
+impl<P: ParamPolicy> UuidParam<P> {
+    /// Parse `s` in the simple, hyphenated, braced or urn form and check it
+    /// against `P`.
+    pub fn parse(s: &str) -> Result<Self, ParamError> {
+        let uuid = UUID::parse_with(s, &ParseOptions::new()).map_err(ParamError::Parse)?;
+        UuidParam::new(uuid)
+    }
+
+    /// Check `uuid` against `P`.
+    pub fn new(uuid: UUID) -> Result<Self, ParamError> {
+        if P::REJECT_NIL && uuid.is_nil() {
+            return Err(ParamError::Nil);
+        }
+        let version = uuid.get_version_num();
+        if !P::VERSIONS.is_empty() && !P::VERSIONS.iter().any(|v| *v as u8 == version) {
+            return Err(ParamError::Version {
+                found: version,
+                allowed: P::VERSIONS,
+            });
+        }
+        Ok(UuidParam(uuid, PhantomData))
+    }
+}
+
+impl<P> UuidParam<P> {
+    /// The validated UUID.
+    pub const fn into_inner(self) -> UUID {
+        self.0
+    }
+}
+
+impl<P> Deref for UuidParam<P> {
+    type Target = UUID;
+
+    fn deref(&self) -> &UUID {
+        &self.0
+    }
+}
+
+impl<P> Clone for UuidParam<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for UuidParam<P> {}
+
+impl<P> PartialEq for UuidParam<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P> Eq for UuidParam<P> {}
+
+impl<P> fmt::Debug for UuidParam<P> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("UuidParam").field(&self.0).finish()
+    }
+}
+
+impl<P> fmt::Display for UuidParam<P> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl<P: ParamPolicy> FromStr for UuidParam<P> {
+    type Err = ParamError;
+
+    fn from_str(s: &str) -> Result<Self, ParamError> {
+        UuidParam::parse(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: ParamPolicy> ::serde::Deserialize<'de> for UuidParam<P> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let uuid = UUID::deserialize(deserializer)?;
+        UuidParam::new(uuid).map_err(::serde::de::Error::custom)
+    }
+}
+
+/// A rejected UUID parameter.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParamError {
+    /// The request has no such parameter.
+    Missing,
+    /// The parameter is not a UUID.
+    Parse(ParseError),
+    /// The parameter is the nil UUID.
+    Nil,
+    /// The version of the parameter is not accepted.
+    Version {
+        /// Version number of the parameter.
+        found: u8,
+        /// Accepted versions.
+        allowed: &'static [Version],
+    },
+}
+
+impl ParamError {
+    /// HTTP status of the error response, `400 Bad Request`.
+    pub const STATUS: u16 = 400;
+
+    /// Stable, machine-readable code of the error.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ParamError::Missing => "missing_uuid",
+            ParamError::Parse(_) => "invalid_uuid",
+            ParamError::Nil => "nil_uuid",
+            ParamError::Version { .. } => "unsupported_uuid_version",
+        }
+    }
+
+    /// JSON body of the error response, as `{"error": code, "message": text}`.
+    pub fn to_json(&self) -> String {
+        let mut body = format!("{{\"error\":\"{}\",\"message\":\"", self.code());
+        for c in self.to_string().chars() {
+            match c {
+                '"' | '\\' => {
+                    body.push('\\');
+                    body.push(c);
+                }
+                c if c.is_control() => body.push_str(&format!("\\u{:04x}", c as u32)),
+                c => body.push(c),
+            }
+        }
+        body + "\"}"
+    }
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Missing => write!(fmt, "missing UUID parameter"),
+            ParamError::Parse(e) => write!(fmt, "invalid UUID: {}", e),
+            ParamError::Nil => write!(fmt, "the nil UUID is not accepted"),
+            ParamError::Version { found, allowed } => {
+                write!(fmt, "UUID version {} is not accepted, expected", found)?;
+                for (i, v) in allowed.iter().enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(fmt, "{}{}", sep, *v as u8)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParamError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TimeOrdered;
+
+    impl ParamPolicy for TimeOrdered {
+        const VERSIONS: &'static [Version] = &[Version::REORDERED, Version::UNIX];
+    }
+
+    #[test]
+    fn policy_checks() {
+        let id: UuidParam = "urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+            .parse()
+            .unwrap();
+        assert_eq!(id.into_inner(), UUID::NAMESPACE_DNS);
+        assert_eq!(
+            "00000000-0000-0000-0000-000000000000".parse::<UuidParam>(),
+            Err(ParamError::Nil)
+        );
+        assert_eq!(
+            UuidParam::<TimeOrdered>::new(UUID::NAMESPACE_DNS),
+            Err(ParamError::Version {
+                found: 1,
+                allowed: TimeOrdered::VERSIONS
+            })
+        );
+        let v7 = UUID::from_u128(0x0189_0000_0000_7000_8000_0000_0000_0000);
+        assert_eq!(*UuidParam::<TimeOrdered>::new(v7).unwrap(), v7);
+    }
+
+    #[test]
+    fn error_body() {
+        let e = UuidParam::<TimeOrdered>::parse("6ba7b810").unwrap_err();
+        assert_eq!(
+            e.to_json(),
+            "{\"error\":\"invalid_uuid\",\"message\":\"invalid UUID: invalid length: expected 36, found 8\"}"
+        );
+        let e = UuidParam::<TimeOrdered>::new(UUID::NAMESPACE_DNS).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "UUID version 1 is not accepted, expected 6, 7"
+        );
+        let e = UuidParam::<AnyUuid>::parse("6ba7b810-9dad-11d1-80b4-00c04fd430\"8").unwrap_err();
+        assert!(e.to_json().contains("'\\\"'"));
+    }
+}
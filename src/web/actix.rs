@@ -0,0 +1,64 @@
+//! Actix extractor of [`UuidParam`] and response of [`ParamError`].
+#![doc(cfg(feature = "actix-web"))]
+#![cfg(feature = "actix-web")]
+
+use core::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+use super::{ParamError, ParamPolicy, UuidParam};
+
+/// Extracts the first path parameter; use `web::Query<T>` or `web::Path<T>`
+/// with a struct of `UuidParam` fields for other parameters.
+impl<P: ParamPolicy> FromRequest for UuidParam<P> {
+    type Error = ParamError;
+    type Future = Ready<Result<Self, ParamError>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(match req.match_info().iter().next() {
+            Some((_, value)) => UuidParam::parse(value),
+            None => Err(ParamError::Missing),
+        })
+    }
+}
+
+impl ResponseError for ParamError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .content_type("application/json")
+            .body(self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::AnyUuid;
+    use crate::UUID;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn extract_path_parameter() {
+        let (req, mut payload) = TestRequest::default()
+            .param("id", "6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+            .to_http_parts();
+        let id =
+            futures::executor::block_on(UuidParam::<AnyUuid>::from_request(&req, &mut payload));
+        assert_eq!(id.map(UuidParam::into_inner), Ok(UUID::NAMESPACE_DNS));
+
+        let (req, mut payload) = TestRequest::default().to_http_parts();
+        let id =
+            futures::executor::block_on(UuidParam::<AnyUuid>::from_request(&req, &mut payload));
+        assert_eq!(id, Err(ParamError::Missing));
+        assert_eq!(
+            ParamError::Missing.error_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Axum extractor of [`UuidParam`] and response of [`ParamError`].
+#![doc(cfg(feature = "axum"))]
+#![cfg(feature = "axum")]
+
+use axum::extract::{FromRequestParts, RawPathParams};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+
+use super::{ParamError, ParamPolicy, UuidParam};
+
+/// Extracts the first path parameter; use `Query<T>` or `Path<T>` with a
+/// struct of `UuidParam` fields for other parameters.
+impl<S: Send + Sync, P: ParamPolicy> FromRequestParts<S> for UuidParam<P> {
+    type Rejection = ParamError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, ParamError> {
+        let params = RawPathParams::from_request_parts(parts, state)
+            .await
+            .map_err(|_| ParamError::Missing)?;
+        match params.iter().next() {
+            Some((_, value)) => UuidParam::parse(value),
+            None => Err(ParamError::Missing),
+        }
+    }
+}
+
+impl IntoResponse for ParamError {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::BAD_REQUEST,
+            [(CONTENT_TYPE, "application/json")],
+            self.to_json(),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::AnyUuid;
+    use axum::http::Request;
+
+    #[test]
+    fn rejection_is_bad_request() {
+        let response = ParamError::Nil.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    }
+
+    #[test]
+    fn missing_path_parameter() {
+        let (mut parts, _) = Request::new(()).into_parts();
+        let extracted =
+            futures::executor::block_on(UuidParam::<AnyUuid>::from_request_parts(&mut parts, &()));
+        assert_eq!(extracted, Err(ParamError::Missing));
+    }
+}
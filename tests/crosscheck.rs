@@ -0,0 +1,43 @@
+#![cfg(feature = "crosscheck")]
+
+use simple_uuid::crosscheck::{Check, Harness, Implementation, Outcome};
+
+// Python's `uuid.UUID` ignores where the hyphens are, this crate does not.
+fn known_divergence(outcome: &Outcome) -> bool {
+    outcome.implementation == Implementation::Python
+        && outcome.check
+            == Check::Parse {
+                input: "6ba7b8109dad-11d1-80b4-00c04fd430c8".to_string(),
+            }
+}
+
+#[test]
+fn compatibility_report() {
+    let harness = Harness::with_default_corpus();
+    let report = harness.run(&Implementation::ALL);
+
+    let compared = Implementation::ALL.len() - report.skipped.len();
+    assert!(report.outcomes.len() <= compared * harness.checks().len());
+    let unexpected = report.mismatches().filter(|o| !known_divergence(o));
+    assert_eq!(unexpected.count(), 0, "{}", report);
+}
+
+#[test]
+fn parse_acceptance_matches_python() {
+    if !Implementation::Python.is_available() {
+        eprintln!("skipped: {} not found", Implementation::Python.program());
+        return;
+    }
+
+    let mut harness = Harness::new();
+    harness.add(Check::Parse {
+        input: "6ba7b810-9dad-11d1-80b4-00c04fd430c8".to_string(),
+    });
+    harness.add(Check::Parse {
+        input: "not-a-uuid".to_string(),
+    });
+
+    let report = harness.run(&[Implementation::Python]);
+    assert_eq!(report.outcomes.len(), 2, "{}", report);
+    assert_eq!(report.mismatches().count(), 0, "{}", report);
+}
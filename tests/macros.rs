@@ -0,0 +1,24 @@
+#![cfg(feature = "macros")]
+
+use simple_uuid::{uuid, UUID};
+
+const DNS: UUID = uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+
+#[test]
+fn literal_forms() {
+    assert_eq!(DNS, UUID::NAMESPACE_DNS);
+    assert_eq!(uuid!("6BA7B8109DAD11D180B400C04FD430C8"), DNS);
+    assert_eq!(uuid!("{6ba7b810-9dad-11d1-80b4-00c04fd430c8}"), DNS);
+    assert_eq!(uuid!("urn:uuid:6ba7b810-9dad-11d1-80b4-00c04fd430c8"), DNS);
+}
+
+macro_rules! forward {
+    ($s:expr) => {
+        uuid!($s)
+    };
+}
+
+#[test]
+fn literal_through_macro_rules() {
+    assert_eq!(forward!("00000000-0000-0000-0000-000000000000"), UUID::NIL);
+}